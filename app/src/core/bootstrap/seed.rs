@@ -1,4 +1,4 @@
-use entity::user;
+use entity::{enums::UserRole, user};
 use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
 
 use crate::shared::password::hash_password;
@@ -9,7 +9,7 @@ use crate::{AppError, error::AuthError};
 /// 种子数据本身固定写在代码里，避免把默认账号、邮箱、密码散落到 `.env`。
 const SEED_ENABLED_ENV: &str = "APP_SEED_DEMO";
 
-/// 默认演示用户。
+/// 默认演示用户（管理员角色，便于体验管理接口）。
 ///
 /// 仅在 `APP_SEED_DEMO=true` 时创建；重复启动会按邮箱幂等跳过。
 const DEMO_USERNAME: &str = "demo";
@@ -57,6 +57,7 @@ async fn seed_demo_user(db: &DatabaseConnection) -> Result<(), AppError> {
             hash_password(DEMO_PASSWORD).map_err(|error| AuthError::Internal(error.to_string()))?
         ),
         status: Set(0),
+        role: Set(UserRole::Admin.into()),
         ..Default::default()
     }
     .insert(db)
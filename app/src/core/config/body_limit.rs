@@ -0,0 +1,75 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::section::{ConfigSection, load_route_map, validate_route_map};
+
+/// 请求体大小限制配置
+///
+/// 限制按解压后的字节数计算：携带 `Content-Encoding: gzip / deflate / br` 的请求体
+/// 在读取时逐块解压，解压结果超出上限立即中止并返回 413，防止压缩炸弹。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BodyLimitConfig {
+    /// 全局请求体上限，单位字节（默认：1 MiB）
+    pub max_size: usize,
+
+    /// 按路径前缀覆盖请求体上限，单位字节（默认：登录、注册 16 KiB）
+    ///
+    /// 键为以 `/` 开头的路径前缀，匹配规则见 [`RoutePrefixMap`](crate::core::http::RoutePrefixMap)。
+    /// 头像和导入路由的上限由 `storage.avatar_max_size` / `import_max_size` 加 multipart 开销得出，
+    /// 这里的覆盖只能把它们调高。
+    pub route_limits: BTreeMap<String, usize>,
+
+    /// 是否解压 gzip / deflate / br 编码的请求体（默认：true），关闭时带编码的请求返回 415
+    pub decompression: bool,
+}
+
+impl Default for BodyLimitConfig {
+    fn default() -> Self {
+        Self {
+            max_size: 1024 * 1024,
+            route_limits: BTreeMap::from([
+                ("/v1/user/login".to_string(), 16 * 1024),
+                ("/v1/user/register".to_string(), 16 * 1024),
+            ]),
+            decompression: true,
+        }
+    }
+}
+
+impl ConfigSection for BodyLimitConfig {
+    fn section_name(&self) -> &str {
+        "body_limit"
+    }
+
+    fn load_from_value(&mut self, value: &Value) -> Result<(), String> {
+        if let Some(obj) = value.as_object() {
+            if let Some(size) = obj.get("max_size").and_then(|v| v.as_u64()) {
+                self.max_size = size as usize;
+            }
+            if let Some(decompression) = obj.get("decompression").and_then(|v| v.as_bool()) {
+                self.decompression = decompression;
+            }
+            load_route_map(
+                value,
+                "route_limits",
+                &mut self.route_limits,
+                "正整数字节数",
+                |v| v.as_u64().map(|size| size as usize),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.max_size == 0 {
+            return Err("请求体上限必须大于 0".to_string());
+        }
+        validate_route_map("route_limits", &self.route_limits, |size| match size {
+            0 => Err("请求体上限必须大于 0".to_string()),
+            _ => Ok(()),
+        })
+    }
+}
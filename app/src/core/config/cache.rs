@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::section::ConfigSection;
+
+/// 响应缓存配置
+///
+/// 服务层的旁路缓存（cache-aside）和路由级 GET 响应缓存共用此配置。
+/// 配置了 Redis 时缓存在 Redis 中（多副本共享），否则缓存在进程内存中。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// 是否启用（默认：true）
+    pub enabled: bool,
+
+    /// 默认缓存时间，单位秒（默认：60）
+    ///
+    /// 路由未单独指定时使用，同时作为 `Cache-Control` 的 `max-age`。
+    pub ttl: u64,
+
+    /// 可缓存的响应体上限，单位字节（默认：1 MiB）
+    pub max_body_size: usize,
+
+    /// 进程内存缓存的最大条目数（默认：10000），仅未配置 Redis 时生效
+    pub max_entries: usize,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl: 60,
+            max_body_size: 1024 * 1024,
+            max_entries: 10_000,
+        }
+    }
+}
+
+impl ConfigSection for CacheConfig {
+    fn section_name(&self) -> &str {
+        "cache"
+    }
+
+    fn load_from_value(&mut self, value: &Value) -> Result<(), String> {
+        if let Some(obj) = value.as_object() {
+            if let Some(enabled) = obj.get("enabled").and_then(|v| v.as_bool()) {
+                self.enabled = enabled;
+            }
+            if let Some(ttl) = obj.get("ttl").and_then(|v| v.as_u64()) {
+                self.ttl = ttl;
+            }
+            if let Some(size) = obj.get("max_body_size").and_then(|v| v.as_u64()) {
+                self.max_body_size = size as usize;
+            }
+            if let Some(entries) = obj.get("max_entries").and_then(|v| v.as_u64()) {
+                self.max_entries = entries as usize;
+            }
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.ttl == 0 {
+            return Err("缓存时间必须大于 0".to_string());
+        }
+        if self.max_body_size == 0 {
+            return Err("可缓存的响应体上限必须大于 0".to_string());
+        }
+        if self.max_entries == 0 {
+            return Err("内存缓存条目数必须大于 0".to_string());
+        }
+        Ok(())
+    }
+}
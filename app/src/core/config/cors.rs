@@ -52,9 +52,18 @@ impl Default for CorsConfig {
                 "Content-Type".to_string(),
                 "Accept".to_string(),
                 "X-Request-ID".to_string(),
+                "Idempotency-Key".to_string(),
+                "Content-Encoding".to_string(),
+                "traceparent".to_string(),
+                "tracestate".to_string(),
             ],
             allow_credentials: false,
-            expose_headers: vec!["Content-Type".to_string(), "X-Total-Count".to_string()],
+            expose_headers: vec![
+                "Content-Type".to_string(),
+                "X-Total-Count".to_string(),
+                "Idempotent-Replayed".to_string(),
+                "X-Request-ID".to_string(),
+            ],
             max_age: 3600,
         }
     }
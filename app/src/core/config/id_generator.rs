@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::env;
+
+use super::section::ConfigSection;
+
+/// 分布式 ID 生成器配置
+///
+/// 基于 Sonyflake 生成全局有序的 64 位 ID。同一时刻运行的每个副本
+/// 必须持有不同的机器 ID，否则可能生成重复 ID。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IdGeneratorConfig {
+    /// 机器 ID（0-65535，可通过 MACHINE_ID 环境变量设置）
+    /// 未设置时：配置了 Redis 则通过 Redis 租约分配，否则由主机名派生（仅适用于单副本部署）
+    pub machine_id: Option<u16>,
+
+    /// ID 时间戳起点（RFC 3339 格式，上线后不可修改）
+    /// （默认：2025-01-01T00:00:00Z）
+    pub start_time: String,
+
+    /// Redis 机器 ID 租约时长，单位秒（默认：60）
+    pub lease_ttl: u64,
+}
+
+impl Default for IdGeneratorConfig {
+    fn default() -> Self {
+        Self {
+            machine_id: None,
+            start_time: "2025-01-01T00:00:00Z".to_string(),
+            lease_ttl: 60,
+        }
+    }
+}
+
+impl IdGeneratorConfig {
+    /// 解析时间戳起点
+    pub fn start_time(&self) -> Result<DateTime<Utc>, String> {
+        DateTime::parse_from_rfc3339(&self.start_time)
+            .map(|time| time.with_timezone(&Utc))
+            .map_err(|e| format!("无效的 ID 起始时间 {}：{}", self.start_time, e))
+    }
+}
+
+impl ConfigSection for IdGeneratorConfig {
+    fn section_name(&self) -> &str {
+        "id_generator"
+    }
+
+    fn load_from_value(&mut self, value: &Value) -> Result<(), String> {
+        if let Some(obj) = value.as_object() {
+            if let Some(machine_id) = obj.get("machine_id").and_then(|v| v.as_u64()) {
+                self.machine_id =
+                    Some(u16::try_from(machine_id).map_err(|_| "机器 ID 必须在 0-65535 之间")?);
+            }
+            if let Some(start_time) = obj.get("start_time").and_then(|v| v.as_str()) {
+                self.start_time = start_time.to_string();
+            }
+            if let Some(ttl) = obj.get("lease_ttl").and_then(|v| v.as_u64()) {
+                self.lease_ttl = ttl;
+            }
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.start_time()? > Utc::now() {
+            return Err("ID 起始时间不能晚于当前时间".to_string());
+        }
+        if self.lease_ttl < 3 {
+            return Err("机器 ID 租约时长至少为 3 秒".to_string());
+        }
+        Ok(())
+    }
+
+    fn apply_env_overrides(&mut self) -> Result<(), String> {
+        if let Ok(machine_id) = env::var("MACHINE_ID") {
+            self.machine_id = Some(
+                machine_id
+                    .parse()
+                    .map_err(|_| format!("MACHINE_ID 必须在 0-65535 之间：{}", machine_id))?,
+            );
+        }
+        Ok(())
+    }
+}
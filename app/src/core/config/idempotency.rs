@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::section::ConfigSection;
+
+/// 幂等请求配置
+///
+/// 携带 `Idempotency-Key` 的 POST 请求首次成功的响应会被保存，
+/// 客户端用同一个 key 重试时直接重放该响应，不再重复执行。
+/// 配置了 Redis 时保存在 Redis 中（多副本共享），否则保存在进程内存中。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IdempotencyConfig {
+    /// 是否启用（默认：true）
+    pub enabled: bool,
+
+    /// 已完成响应的保存时间，单位秒（默认：86400）
+    pub ttl: u64,
+
+    /// 处理中请求的占用时间，单位秒（默认：300）
+    ///
+    /// 请求结束或被取消时会立即释放；只有进程崩溃时才需等待占用过期。
+    pub lock_ttl: u64,
+
+    /// 参与指纹计算的请求体和可保存的响应体上限，单位字节（默认：1 MiB）
+    ///
+    /// 携带 `Idempotency-Key` 的请求体超过上限时返回 413；批量导入按导入文件上限放宽。
+    pub max_body_size: usize,
+}
+
+impl Default for IdempotencyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl: 24 * 60 * 60,
+            lock_ttl: 300,
+            max_body_size: 1024 * 1024,
+        }
+    }
+}
+
+impl ConfigSection for IdempotencyConfig {
+    fn section_name(&self) -> &str {
+        "idempotency"
+    }
+
+    fn load_from_value(&mut self, value: &Value) -> Result<(), String> {
+        if let Some(obj) = value.as_object() {
+            if let Some(enabled) = obj.get("enabled").and_then(|v| v.as_bool()) {
+                self.enabled = enabled;
+            }
+            if let Some(ttl) = obj.get("ttl").and_then(|v| v.as_u64()) {
+                self.ttl = ttl;
+            }
+            if let Some(ttl) = obj.get("lock_ttl").and_then(|v| v.as_u64()) {
+                self.lock_ttl = ttl;
+            }
+            if let Some(size) = obj.get("max_body_size").and_then(|v| v.as_u64()) {
+                self.max_body_size = size as usize;
+            }
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.ttl == 0 {
+            return Err("幂等响应保存时间必须大于 0".to_string());
+        }
+        if self.lock_ttl == 0 {
+            return Err("幂等请求占用时间必须大于 0".to_string());
+        }
+        if self.max_body_size == 0 {
+            return Err("幂等请求体上限必须大于 0".to_string());
+        }
+        Ok(())
+    }
+}
@@ -1,18 +1,36 @@
+mod body_limit;
+mod cache;
 mod cors;
 mod database;
+mod id_generator;
+mod idempotency;
 mod logging;
+mod privacy;
+mod public_id;
 mod redis;
+mod response;
 mod secrets;
 mod section;
+mod security_headers;
 mod server;
+mod storage;
 
+pub use body_limit::BodyLimitConfig;
+pub use cache::CacheConfig;
 pub use cors::CorsConfig;
 pub use database::DatabaseConfig;
+pub use id_generator::IdGeneratorConfig;
+pub use idempotency::IdempotencyConfig;
 pub use logging::LoggingConfig;
+pub use privacy::PrivacyConfig;
+pub use public_id::{DEFAULT_PUBLIC_ID_ALPHABET, PublicIdConfig};
 pub use redis::RedisConfig;
+pub use response::ResponseConfig;
 pub use secrets::SecretsConfig;
 pub use section::ConfigSection;
+pub use security_headers::{DEFAULT_CSP, DOCS_CSP, SecurityHeadersConfig};
 pub use server::ServerConfig;
+pub use storage::StorageConfig;
 
 use crate::error::ConfigError;
 use config::{Config, Environment, File};
@@ -20,7 +38,7 @@ use serde::{Deserialize, Serialize};
 
 /// 应用程序配置入口
 ///
-/// 聚合所有配置段（服务器、数据库、日志、敏感信息、跨域、Redis）。
+/// 聚合所有配置段（服务器、数据库、日志、敏感信息、跨域、Redis、对外 ID、ID 生成器、文件存储、个人数据保护、响应格式、幂等请求、响应缓存、安全响应头、请求体大小限制）。
 /// 通过 `load()` 方法从配置文件和环境变量加载配置，支持多层次优先级管理。
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -43,6 +61,33 @@ pub struct AppConfig {
 
     /// Redis 连接池配置
     pub redis: RedisConfig,
+
+    /// 对外 ID 编码配置
+    pub public_id: PublicIdConfig,
+
+    /// 分布式 ID 生成器配置
+    pub id_generator: IdGeneratorConfig,
+
+    /// 文件存储配置
+    pub storage: StorageConfig,
+
+    /// 个人数据保护配置
+    pub privacy: PrivacyConfig,
+
+    /// 响应格式配置
+    pub response: ResponseConfig,
+
+    /// 幂等请求配置
+    pub idempotency: IdempotencyConfig,
+
+    /// 响应缓存配置
+    pub cache: CacheConfig,
+
+    /// 安全响应头配置
+    pub security_headers: SecurityHeadersConfig,
+
+    /// 请求体大小限制配置
+    pub body_limit: BodyLimitConfig,
 }
 
 impl AppConfig {
@@ -111,6 +156,15 @@ impl AppConfig {
         self.secrets = app_config.secrets;
         self.cors = app_config.cors;
         self.redis = app_config.redis;
+        self.public_id = app_config.public_id;
+        self.id_generator = app_config.id_generator;
+        self.storage = app_config.storage;
+        self.privacy = app_config.privacy;
+        self.response = app_config.response;
+        self.idempotency = app_config.idempotency;
+        self.cache = app_config.cache;
+        self.security_headers = app_config.security_headers;
+        self.body_limit = app_config.body_limit;
 
         Ok(())
     }
@@ -126,6 +180,15 @@ impl AppConfig {
             &mut self.secrets,
             &mut self.cors,
             &mut self.redis,
+            &mut self.public_id,
+            &mut self.id_generator,
+            &mut self.storage,
+            &mut self.privacy,
+            &mut self.response,
+            &mut self.idempotency,
+            &mut self.cache,
+            &mut self.security_headers,
+            &mut self.body_limit,
         ];
 
         for section in sections {
@@ -152,6 +215,15 @@ impl AppConfig {
             &self.secrets,
             &self.cors,
             &self.redis,
+            &self.public_id,
+            &self.id_generator,
+            &self.storage,
+            &self.privacy,
+            &self.response,
+            &self.idempotency,
+            &self.cache,
+            &self.security_headers,
+            &self.body_limit,
         ];
 
         for section in sections {
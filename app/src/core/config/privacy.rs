@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::section::ConfigSection;
+
+/// 个人数据保护配置
+///
+/// 控制账号注销后的匿名化宽限期，以及数据导出任务的超时和导出文件的保留时间。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrivacyConfig {
+    /// 注销后到匿名化个人信息的宽限期，单位天（默认：30）
+    pub deletion_grace_days: u64,
+
+    /// 数据导出文件保留时间，单位天（默认：7）
+    pub export_retention_days: u64,
+
+    /// 导出任务超时时间，单位分钟（默认：30）
+    ///
+    /// 超过该时间仍未完成的任务视为中断（如生成期间进程重启），
+    /// 不再阻止创建新任务，并由清理任务标记为失败。
+    pub export_timeout_minutes: u64,
+
+    /// 后台清理任务执行间隔，单位小时（默认：24）
+    pub purge_interval: u64,
+}
+
+impl Default for PrivacyConfig {
+    fn default() -> Self {
+        Self {
+            deletion_grace_days: 30,
+            export_retention_days: 7,
+            export_timeout_minutes: 30,
+            purge_interval: 24,
+        }
+    }
+}
+
+impl ConfigSection for PrivacyConfig {
+    fn section_name(&self) -> &str {
+        "privacy"
+    }
+
+    fn load_from_value(&mut self, value: &Value) -> Result<(), String> {
+        if let Some(obj) = value.as_object() {
+            if let Some(days) = obj.get("deletion_grace_days").and_then(|v| v.as_u64()) {
+                self.deletion_grace_days = days;
+            }
+            if let Some(days) = obj.get("export_retention_days").and_then(|v| v.as_u64()) {
+                self.export_retention_days = days;
+            }
+            if let Some(minutes) = obj.get("export_timeout_minutes").and_then(|v| v.as_u64()) {
+                self.export_timeout_minutes = minutes;
+            }
+            if let Some(hours) = obj.get("purge_interval").and_then(|v| v.as_u64()) {
+                self.purge_interval = hours;
+            }
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.export_retention_days == 0 {
+            return Err("导出文件保留时间必须大于 0".to_string());
+        }
+        if self.export_timeout_minutes == 0 {
+            return Err("导出任务超时时间必须大于 0".to_string());
+        }
+        if self.purge_interval == 0 {
+            return Err("清理任务执行间隔必须大于 0".to_string());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::env;
+
+use super::section::ConfigSection;
+
+/// sqids 默认字母表
+pub const DEFAULT_PUBLIC_ID_ALPHABET: &str =
+    "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+/// 对外 ID 编码配置
+///
+/// 控制数据库自增 ID 对外暴露时的混淆方式。修改字母表或盐值会让
+/// 已经发出去的 ID 全部失效，上线后应保持不变。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PublicIdConfig {
+    /// 编码字母表（至少 3 个互不重复的 ASCII 字符）
+    pub alphabet: String,
+
+    /// 盐值，用于打乱字母表（可通过 PUBLIC_ID_SALT 环境变量设置）
+    pub salt: String,
+
+    /// 编码结果的最小长度（默认：8）
+    pub min_length: u8,
+}
+
+impl Default for PublicIdConfig {
+    fn default() -> Self {
+        Self {
+            alphabet: DEFAULT_PUBLIC_ID_ALPHABET.to_string(),
+            salt: String::new(),
+            min_length: 8,
+        }
+    }
+}
+
+impl ConfigSection for PublicIdConfig {
+    fn section_name(&self) -> &str {
+        "public_id"
+    }
+
+    fn load_from_value(&mut self, value: &Value) -> Result<(), String> {
+        if let Some(obj) = value.as_object() {
+            if let Some(alphabet) = obj.get("alphabet").and_then(|v| v.as_str()) {
+                self.alphabet = alphabet.to_string();
+            }
+            if let Some(salt) = obj.get("salt").and_then(|v| v.as_str()) {
+                self.salt = salt.to_string();
+            }
+            if let Some(min_length) = obj.get("min_length").and_then(|v| v.as_u64()) {
+                self.min_length = u8::try_from(min_length)
+                    .map_err(|_| format!("ID 最小长度不能超过 {}：{}", u8::MAX, min_length))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if !self.alphabet.is_ascii() {
+            return Err("ID 字母表只能包含 ASCII 字符".to_string());
+        }
+        if self.alphabet.len() < 3 {
+            return Err("ID 字母表长度至少为 3".to_string());
+        }
+        let unique: HashSet<char> = self.alphabet.chars().collect();
+        if unique.len() != self.alphabet.len() {
+            return Err("ID 字母表不能包含重复字符".to_string());
+        }
+        Ok(())
+    }
+
+    fn apply_env_overrides(&mut self) -> Result<(), String> {
+        if let Ok(salt) = env::var("PUBLIC_ID_SALT") {
+            self.salt = salt;
+        }
+        Ok(())
+    }
+}
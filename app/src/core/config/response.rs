@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::section::ConfigSection;
+use crate::core::i18n::Locale;
+use crate::core::response::ErrorFormat;
+
+/// 响应格式配置
+///
+/// 控制错误响应的默认格式和默认语言。客户端可通过 `Accept` 头显式选择
+/// `application/json`（Google JSON 格式）或 `application/problem+json`（RFC 9457），
+/// 通过 `Accept-Language` 选择消息语言，未明确偏好时使用这里的默认值。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResponseConfig {
+    /// 默认错误格式，`google` 或 `problem`（默认：google）
+    pub error_format: ErrorFormat,
+
+    /// Problem Details 中 `type` URI 的前缀（默认：/problems）
+    pub problem_type_base: String,
+
+    /// 默认消息语言，`zh-CN` 或 `en`（默认：zh-CN）
+    pub default_locale: Locale,
+
+    /// 分页列表响应是否输出 RFC 8288 `Link` 与 `X-Total-Count` 响应头（默认：true）
+    pub pagination_headers: bool,
+}
+
+impl Default for ResponseConfig {
+    fn default() -> Self {
+        Self {
+            error_format: ErrorFormat::Google,
+            problem_type_base: "/problems".to_string(),
+            default_locale: Locale::ZhCn,
+            pagination_headers: true,
+        }
+    }
+}
+
+impl ConfigSection for ResponseConfig {
+    fn section_name(&self) -> &str {
+        "response"
+    }
+
+    fn load_from_value(&mut self, value: &Value) -> Result<(), String> {
+        if let Some(obj) = value.as_object() {
+            if let Some(format) = obj.get("error_format").and_then(|v| v.as_str()) {
+                self.error_format = ErrorFormat::parse(format)
+                    .ok_or_else(|| format!("不支持的错误格式：{}", format))?;
+            }
+            if let Some(base) = obj.get("problem_type_base").and_then(|v| v.as_str()) {
+                self.problem_type_base = base.to_string();
+            }
+            if let Some(tag) = obj.get("default_locale").and_then(|v| v.as_str()) {
+                self.default_locale =
+                    Locale::from_tag(tag).ok_or_else(|| format!("不支持的语言：{}", tag))?;
+            }
+            if let Some(enabled) = obj.get("pagination_headers").and_then(|v| v.as_bool()) {
+                self.pagination_headers = enabled;
+            }
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.problem_type_base.trim().is_empty() {
+            return Err("Problem Details 类型 URI 前缀不能为空".to_string());
+        }
+        Ok(())
+    }
+}
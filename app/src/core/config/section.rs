@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde_json::Value;
 
 /// 配置段的统一接口，支持灵活扩展
@@ -47,3 +49,39 @@ pub trait ConfigSection: Send + Sync {
         Ok(())
     }
 }
+
+/// 读取按路径前缀覆盖的配置表（如 `route_timeouts`），逐项合并到 `routes`
+///
+/// `parse` 无法解析的值返回错误，`expected` 描述期望的取值（如"正整数秒数"）。
+pub(super) fn load_route_map<T>(
+    value: &Value,
+    key: &str,
+    routes: &mut BTreeMap<String, T>,
+    expected: &str,
+    parse: impl Fn(&Value) -> Option<T>,
+) -> Result<(), String> {
+    let Some(table) = value.get(key).and_then(|v| v.as_object()) else {
+        return Ok(());
+    };
+    for (prefix, value) in table {
+        let value =
+            parse(value).ok_or_else(|| format!("{} 中 {} 的值必须是{}", key, prefix, expected))?;
+        routes.insert(prefix.clone(), value);
+    }
+    Ok(())
+}
+
+/// 校验按路径前缀覆盖的配置表：前缀必须以 `/` 开头，值由 `check` 校验
+pub(super) fn validate_route_map<T>(
+    key: &str,
+    routes: &BTreeMap<String, T>,
+    check: impl Fn(&T) -> Result<(), String>,
+) -> Result<(), String> {
+    for (prefix, value) in routes {
+        if !prefix.starts_with('/') {
+            return Err(format!("{} 的路径前缀必须以 / 开头：{}", key, prefix));
+        }
+        check(value).map_err(|e| format!("{} 中 {} 的值无效：{}", key, prefix, e))?;
+    }
+    Ok(())
+}
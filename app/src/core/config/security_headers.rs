@@ -0,0 +1,152 @@
+use std::collections::BTreeMap;
+
+use axum::http::HeaderValue;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::section::{ConfigSection, load_route_map, validate_route_map};
+
+/// API 响应的默认内容安全策略：不加载任何资源，不允许被嵌入
+pub const DEFAULT_CSP: &str = "default-src 'none'; frame-ancestors 'none'";
+
+/// API 文档（Scalar）页面的内容安全策略
+///
+/// Scalar 页面由内联脚本和样式构成，从同源读取 OpenAPI 文档并加载 fonts.scalar.com 的字体。
+pub const DOCS_CSP: &str = "default-src 'self'; script-src 'self' 'unsafe-inline'; \
+     style-src 'self' 'unsafe-inline'; font-src 'self' data: https://fonts.scalar.com; \
+     img-src 'self' data: https:; connect-src 'self'; frame-ancestors 'none'";
+
+/// 安全响应头配置
+///
+/// 为所有响应添加 HSTS、`X-Content-Type-Options`、`X-Frame-Options`、`Referrer-Policy`、
+/// `Permissions-Policy` 和 `Content-Security-Policy`。值为空字符串（HSTS 为 `hsts_max_age = 0`）
+/// 时不输出对应的头；处理器已设置的头保持不变。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SecurityHeadersConfig {
+    /// 是否启用（默认：true）
+    pub enabled: bool,
+
+    /// HSTS 有效期，单位秒（默认：31536000，即一年），0 表示不输出
+    ///
+    /// 浏览器只采信 HTTPS 响应中的 HSTS，经 TLS 终止代理访问时同样生效。
+    pub hsts_max_age: u64,
+
+    /// HSTS 是否包含子域名（默认：true）
+    pub hsts_include_subdomains: bool,
+
+    /// HSTS 是否声明 preload（默认：false）
+    pub hsts_preload: bool,
+
+    /// `X-Frame-Options`（默认：DENY）
+    pub frame_options: String,
+
+    /// `Referrer-Policy`（默认：no-referrer）
+    pub referrer_policy: String,
+
+    /// `Permissions-Policy`（默认：禁用摄像头、麦克风、定位和支付）
+    pub permissions_policy: String,
+
+    /// 默认的 `Content-Security-Policy`（默认：不加载任何资源、不允许被嵌入）
+    pub content_security_policy: String,
+
+    /// 按路径前缀覆盖 `Content-Security-Policy`（默认：`/docs` 使用 Scalar 页面所需的策略）
+    ///
+    /// 键为以 `/` 开头的路径前缀，匹配规则见 [`RoutePrefixMap`](crate::core::http::RoutePrefixMap)。
+    pub route_csp: BTreeMap<String, String>,
+}
+
+impl Default for SecurityHeadersConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            hsts_max_age: 365 * 24 * 60 * 60,
+            hsts_include_subdomains: true,
+            hsts_preload: false,
+            frame_options: "DENY".to_string(),
+            referrer_policy: "no-referrer".to_string(),
+            permissions_policy: "camera=(), microphone=(), geolocation=(), payment=()".to_string(),
+            content_security_policy: DEFAULT_CSP.to_string(),
+            route_csp: BTreeMap::from([("/docs".to_string(), DOCS_CSP.to_string())]),
+        }
+    }
+}
+
+impl SecurityHeadersConfig {
+    /// `Strict-Transport-Security` 的值，`hsts_max_age` 为 0 时返回 None
+    pub fn hsts(&self) -> Option<String> {
+        if self.hsts_max_age == 0 {
+            return None;
+        }
+        let mut value = format!("max-age={}", self.hsts_max_age);
+        if self.hsts_include_subdomains {
+            value.push_str("; includeSubDomains");
+        }
+        if self.hsts_preload {
+            value.push_str("; preload");
+        }
+        Some(value)
+    }
+}
+
+impl ConfigSection for SecurityHeadersConfig {
+    fn section_name(&self) -> &str {
+        "security_headers"
+    }
+
+    fn load_from_value(&mut self, value: &Value) -> Result<(), String> {
+        if let Some(obj) = value.as_object() {
+            if let Some(enabled) = obj.get("enabled").and_then(|v| v.as_bool()) {
+                self.enabled = enabled;
+            }
+            if let Some(max_age) = obj.get("hsts_max_age").and_then(|v| v.as_u64()) {
+                self.hsts_max_age = max_age;
+            }
+            if let Some(include) = obj.get("hsts_include_subdomains").and_then(|v| v.as_bool()) {
+                self.hsts_include_subdomains = include;
+            }
+            if let Some(preload) = obj.get("hsts_preload").and_then(|v| v.as_bool()) {
+                self.hsts_preload = preload;
+            }
+            for (key, field) in [
+                ("frame_options", &mut self.frame_options),
+                ("referrer_policy", &mut self.referrer_policy),
+                ("permissions_policy", &mut self.permissions_policy),
+                ("content_security_policy", &mut self.content_security_policy),
+            ] {
+                if let Some(value) = obj.get(key).and_then(|v| v.as_str()) {
+                    *field = value.to_string();
+                }
+            }
+            load_route_map(value, "route_csp", &mut self.route_csp, "字符串", |v| {
+                v.as_str().map(str::to_string)
+            })?;
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.hsts_preload && (self.hsts_max_age < 31_536_000 || !self.hsts_include_subdomains) {
+            return Err("HSTS preload 要求有效期至少一年且包含子域名".to_string());
+        }
+        let frame_options = self.frame_options.to_ascii_uppercase();
+        if !matches!(frame_options.as_str(), "" | "DENY" | "SAMEORIGIN") {
+            return Err(format!(
+                "X-Frame-Options 只能是 DENY 或 SAMEORIGIN：{}",
+                self.frame_options
+            ));
+        }
+        for (name, value) in [
+            ("Referrer-Policy", &self.referrer_policy),
+            ("Permissions-Policy", &self.permissions_policy),
+            ("Content-Security-Policy", &self.content_security_policy),
+        ] {
+            HeaderValue::from_str(value).map_err(|_| format!("无效的 {} 值：{}", name, value))?;
+        }
+        validate_route_map("route_csp", &self.route_csp, |csp| {
+            HeaderValue::from_str(csp)
+                .map(drop)
+                .map_err(|_| format!("无效的 Content-Security-Policy 值：{}", csp))
+        })
+    }
+}
@@ -1,7 +1,11 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use super::section::ConfigSection;
+use super::section::{ConfigSection, load_route_map, validate_route_map};
+use crate::core::http::TrustedProxies;
 
 /// 服务器配置
 ///
@@ -17,6 +21,21 @@ pub struct ServerConfig {
 
     /// 请求超时时间，单位秒（默认：30）
     pub timeout: u64,
+
+    /// 按路径前缀覆盖请求超时，单位秒（默认：空）
+    ///
+    /// 键为以 `/` 开头的路径前缀，匹配规则见 [`RoutePrefixMap`](crate::core::http::RoutePrefixMap)。
+    pub route_timeouts: BTreeMap<String, u64>,
+
+    /// 修改资源时是否必须携带 `If-Match`（默认：false）
+    ///
+    /// 开启后未携带的请求返回 428；关闭时未携带则不做版本检查，携带时仍按版本拒绝过期修改。
+    pub require_if_match: bool,
+
+    /// 受信任的反向代理，IP 地址或 CIDR 网段（默认：空）
+    ///
+    /// 只有来自这些地址的请求才采信 `X-Forwarded-*` 头，用于还原分页链接等绝对 URL。
+    pub trusted_proxies: Vec<String>,
 }
 
 impl Default for ServerConfig {
@@ -25,10 +44,20 @@ impl Default for ServerConfig {
             host: "127.0.0.1".to_string(),
             port: 3001,
             timeout: 30,
+            route_timeouts: BTreeMap::new(),
+            require_if_match: false,
+            trusted_proxies: Vec::new(),
         }
     }
 }
 
+impl ServerConfig {
+    /// 全局请求超时
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout)
+    }
+}
+
 impl ConfigSection for ServerConfig {
     fn section_name(&self) -> &str {
         "server"
@@ -45,6 +74,27 @@ impl ConfigSection for ServerConfig {
             if let Some(timeout) = obj.get("timeout").and_then(|v| v.as_u64()) {
                 self.timeout = timeout;
             }
+            if let Some(require) = obj.get("require_if_match").and_then(|v| v.as_bool()) {
+                self.require_if_match = require;
+            }
+            if let Some(proxies) = obj.get("trusted_proxies").and_then(|v| v.as_array()) {
+                self.trusted_proxies = proxies
+                    .iter()
+                    .map(|proxy| {
+                        proxy
+                            .as_str()
+                            .map(str::to_string)
+                            .ok_or_else(|| "受信任代理地址必须是字符串".to_string())
+                    })
+                    .collect::<Result<_, _>>()?;
+            }
+            load_route_map(
+                value,
+                "route_timeouts",
+                &mut self.route_timeouts,
+                "正整数秒数",
+                |v| v.as_u64(),
+            )?;
         }
         Ok(())
     }
@@ -56,6 +106,14 @@ impl ConfigSection for ServerConfig {
         if self.timeout == 0 {
             return Err("服务器超时时间必须大于 0".to_string());
         }
-        Ok(())
+        TrustedProxies::parse(&self.trusted_proxies)?;
+        validate_route_map(
+            "route_timeouts",
+            &self.route_timeouts,
+            |timeout| match timeout {
+                0 => Err("超时时间必须大于 0".to_string()),
+                _ => Ok(()),
+            },
+        )
     }
 }
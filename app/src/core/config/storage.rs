@@ -0,0 +1,128 @@
+use std::path::{Component, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::section::ConfigSection;
+
+/// 文件存储配置
+///
+/// 包含上传文件的存储位置、对外访问路径和大小限制。
+/// 目前仅支持本地文件系统：公开目录通过 `/static` 路由对外提供，
+/// 私有目录（如数据导出文件）只能经由鉴权接口读取。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StorageConfig {
+    /// 本地存储根目录（默认：./uploads）
+    pub local_dir: String,
+
+    /// 私有文件存储根目录，不对外提供（默认：./data/private）
+    pub private_dir: String,
+
+    /// 对外访问 URL 前缀（默认：/static）
+    pub public_url_prefix: String,
+
+    /// 头像上传大小上限，单位字节（默认：5 MiB）
+    pub avatar_max_size: usize,
+
+    /// 批量导入文件大小上限，单位字节（默认：10 MiB）
+    pub import_max_size: usize,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            local_dir: "./uploads".to_string(),
+            private_dir: "./data/private".to_string(),
+            public_url_prefix: "/static".to_string(),
+            avatar_max_size: 5 * 1024 * 1024,
+            import_max_size: 10 * 1024 * 1024,
+        }
+    }
+}
+
+impl ConfigSection for StorageConfig {
+    fn section_name(&self) -> &str {
+        "storage"
+    }
+
+    fn load_from_value(&mut self, value: &Value) -> Result<(), String> {
+        if let Some(obj) = value.as_object() {
+            if let Some(dir) = obj.get("local_dir").and_then(|v| v.as_str()) {
+                self.local_dir = dir.to_string();
+            }
+            if let Some(dir) = obj.get("private_dir").and_then(|v| v.as_str()) {
+                self.private_dir = dir.to_string();
+            }
+            if let Some(prefix) = obj.get("public_url_prefix").and_then(|v| v.as_str()) {
+                self.public_url_prefix = prefix.to_string();
+            }
+            if let Some(size) = obj.get("avatar_max_size").and_then(|v| v.as_u64()) {
+                self.avatar_max_size = size as usize;
+            }
+            if let Some(size) = obj.get("import_max_size").and_then(|v| v.as_u64()) {
+                self.import_max_size = size as usize;
+            }
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.local_dir.is_empty() {
+            return Err("本地存储目录不能为空".to_string());
+        }
+        if self.private_dir.is_empty() {
+            return Err("私有存储目录不能为空".to_string());
+        }
+        // 私有目录位于公开目录之内（或反之）时，私有文件会经 `/static` 被公开下载
+        let local_dir = resolve_dir(&self.local_dir)?;
+        let private_dir = resolve_dir(&self.private_dir)?;
+        if private_dir.starts_with(&local_dir) || local_dir.starts_with(&private_dir) {
+            return Err("私有存储目录与公开存储目录不能相同或互相包含".to_string());
+        }
+        if self.avatar_max_size == 0 {
+            return Err("头像上传大小上限必须大于 0".to_string());
+        }
+        if self.import_max_size == 0 {
+            return Err("批量导入文件大小上限必须大于 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// 将存储目录解析为规范的绝对路径
+///
+/// 目录可能尚未创建：先按词法去掉 `.` 和 `..`，再规范化最近的已存在祖先目录
+/// （解析符号链接），最后拼回尚不存在的部分。
+fn resolve_dir(dir: &str) -> Result<PathBuf, String> {
+    let absolute =
+        std::path::absolute(dir).map_err(|e| format!("无法解析存储目录 {}：{}", dir, e))?;
+    let mut normalized = PathBuf::new();
+    for component in absolute.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+
+    let mut missing = Vec::new();
+    let mut existing = normalized.as_path();
+    loop {
+        if let Ok(resolved) = existing.canonicalize() {
+            return Ok(missing
+                .iter()
+                .rev()
+                .fold(resolved, |path, name| path.join(name)));
+        }
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                missing.push(name);
+                existing = parent;
+            }
+            _ => return Ok(normalized.clone()),
+        }
+    }
+}
@@ -0,0 +1,305 @@
+//! 条件请求（`ETag` / `If-None-Match` / `If-Match`）
+//!
+//! `etag_middleware` 为成功的 GET/HEAD JSON 响应生成强 ETag 并处理 `If-None-Match`；
+//! 本模块提供其共用的 ETag 计算与比较、修改资源时的 `If-Match` 版本检查，
+//! 以及处理器提前返回 304 的辅助类型：
+//!
+//! ```ignore
+//! pub async fn get_user(
+//!     if_none_match: IfNoneMatch,
+//!     ...
+//! ) -> Result<Conditional<UserResponse>, AppError> {
+//!     let version = service.user_version(id).await?; // 廉价的版本号（如 updated_at）
+//!     if if_none_match.matches_version(&version) {
+//!         return Ok(Conditional::not_modified(if_none_match.etag_for(&version)));
+//!     }
+//!     let user = service.get_user(id).await?; // 昂贵的查询
+//!     Ok(Conditional::Modified(ApiResponse::success(user).with_etag(version)))
+//! }
+//! ```
+
+use aide::OperationOutput;
+use aide::generate::GenContext;
+use aide::openapi::Operation;
+use axum::extract::FromRequestParts;
+use axum::http::header::{ETAG, IF_MATCH, IF_NONE_MATCH};
+use axum::http::request::Parts;
+use axum::http::{HeaderMap, HeaderName, HeaderValue, StatusCode, Uri};
+use axum::response::{IntoResponse, Response};
+use schemars::JsonSchema;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::core::i18n::{Locale, current_locale};
+use crate::core::response::{ApiResponse, FIELDS_PARAM};
+use crate::error::PreconditionError;
+
+/// ETag 摘要保留的字节数（SHA-256 前 128 位）
+const DIGEST_BYTES: usize = 16;
+
+/// 由内容摘要生成强 ETag（带引号）
+pub fn digest_etag(content: &[u8]) -> String {
+    let digest = Sha256::digest(content);
+    let hex: String = digest[..DIGEST_BYTES]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    format!("\"{hex}\"")
+}
+
+/// 由处理器提供的版本生成强 ETag
+///
+/// 版本两端的引号会被规范化。成功响应的 `data.lang` 随协商出的语言变化，
+/// 在 `locale_middleware` 范围内时语言标签追加在版本之后（如 `"3;en"`），使不同语言的表示
+/// 不会共用同一个强 ETag。请求携带 `fields` 参数时同一版本对应不同的表示，
+/// 此时将版本、`fields` 与语言一起摘要，保证不同的部分响应不会共用同一个强 ETag。
+pub fn version_etag(version: &str, fields: Option<&str>) -> String {
+    let version = version.trim_matches('"');
+    let locale = current_locale().map(Locale::tag);
+    match (fields, locale) {
+        (Some(fields), Some(locale)) => {
+            digest_etag(format!("{version}\n{fields}\n{locale}").as_bytes())
+        }
+        (Some(fields), None) => digest_etag(format!("{version}\n{fields}").as_bytes()),
+        (None, Some(locale)) => format!("\"{version};{locale}\""),
+        (None, None) => format!("\"{version}\""),
+    }
+}
+
+/// 处理器通过 `ApiResponse::with_etag` 提供的资源版本
+///
+/// 写入响应扩展，使 `fields` 裁剪掉 `data.etag` 后中间件仍能据此生成 ETag。
+#[derive(Debug, Clone)]
+pub(crate) struct ResourceVersion(pub(crate) String);
+
+/// 请求的 `fields` 参数原文
+pub(crate) fn fields_param(uri: &Uri) -> Option<String> {
+    form_urlencoded::parse(uri.query()?.as_bytes())
+        .find(|(key, _)| key == FIELDS_PARAM)
+        .map(|(_, value)| value.into_owned())
+}
+
+/// 请求的 `If-None-Match` 条件
+#[derive(Debug, Clone, Default)]
+pub struct IfNoneMatch {
+    /// 客户端缓存的 ETag 列表（`*` 表示任意）
+    tags: Vec<String>,
+
+    /// 请求的 `fields` 参数，参与处理器版本的 ETag 计算
+    fields: Option<String>,
+}
+
+impl IfNoneMatch {
+    /// 从请求头解析，未携带时为空条件（不匹配任何 ETag）
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        Self {
+            tags: entity_tags(headers, IF_NONE_MATCH),
+            fields: None,
+        }
+    }
+
+    /// 是否携带了 `If-None-Match`
+    pub fn is_present(&self) -> bool {
+        !self.tags.is_empty()
+    }
+
+    /// ETag 是否与客户端缓存匹配（弱比较，忽略 `W/` 前缀）
+    pub fn matches(&self, etag: &str) -> bool {
+        let etag = opaque_tag(etag);
+        self.tags
+            .iter()
+            .any(|tag| tag == "*" || opaque_tag(tag) == etag)
+    }
+
+    /// 处理器版本在当前请求下对应的 ETag（与 `etag_middleware` 的计算一致）
+    pub fn etag_for(&self, version: &str) -> String {
+        version_etag(version, self.fields.as_deref())
+    }
+
+    /// 处理器版本是否与客户端缓存匹配，匹配时处理器可直接返回 304
+    pub fn matches_version(&self, version: &str) -> bool {
+        self.is_present() && self.matches(&self.etag_for(version))
+    }
+}
+
+/// 解析条件请求头中逗号分隔的 ETag 列表
+fn entity_tags(headers: &HeaderMap, name: HeaderName) -> Vec<String> {
+    headers
+        .get_all(name)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// 去掉弱标记，得到用于弱比较的不透明标签
+fn opaque_tag(tag: &str) -> &str {
+    tag.strip_prefix("W/").unwrap_or(tag)
+}
+
+impl<S> FromRequestParts<S> for IfNoneMatch
+where
+    S: Send + Sync,
+{
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self {
+            fields: fields_param(&parts.uri),
+            ..Self::from_headers(&parts.headers)
+        })
+    }
+}
+
+/// 条件来自请求头，不产生额外的 OpenAPI 参数
+impl aide::OperationInput for IfNoneMatch {}
+
+/// 请求的 `If-Match` 条件（乐观并发控制）
+///
+/// 资源的 ETag 为带引号的版本号（如 `"3"`，协商了语言时为 `"3;en"`），修改时由服务层以
+/// `UPDATE ... WHERE version = ?` 原子地检查并递增版本。
+#[derive(Debug, Clone, Default)]
+pub struct IfMatch {
+    /// 客户端期望的 ETag 列表（`*` 表示资源存在即可）
+    tags: Vec<String>,
+}
+
+impl IfMatch {
+    /// 从请求头解析
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        Self {
+            tags: entity_tags(headers, IF_MATCH),
+        }
+    }
+
+    /// 是否携带了 `If-Match`
+    pub fn is_present(&self) -> bool {
+        !self.tags.is_empty()
+    }
+
+    /// ETag 是否满足条件（强比较，弱 ETag 永不匹配）
+    pub fn matches(&self, etag: &str) -> bool {
+        self.tags
+            .iter()
+            .any(|tag| tag == "*" || (!tag.starts_with("W/") && tag == etag))
+    }
+
+    /// 客户端期望的资源版本
+    ///
+    /// - 未携带：`required` 时返回 `PreconditionError::Required`，否则 `None`（不检查版本）
+    /// - `*`：`None`（资源存在即可）
+    /// - 带引号的版本号（可带 `;语言` 后缀）：返回该版本；不是版本号的 ETag 不可能匹配，
+    ///   返回 `PreconditionError::Failed`
+    pub fn version(&self, required: bool) -> Result<Option<i32>, PreconditionError> {
+        if !self.is_present() {
+            return if required {
+                Err(PreconditionError::Required)
+            } else {
+                Ok(None)
+            };
+        }
+        if self.tags.iter().any(|tag| tag == "*") {
+            return Ok(None);
+        }
+        self.tags
+            .iter()
+            .filter(|tag| !tag.starts_with("W/"))
+            .find_map(|tag| {
+                let tag = tag.strip_prefix('"')?.strip_suffix('"')?;
+                tag.split_once(';')
+                    .map_or(tag, |(version, _)| version)
+                    .parse()
+                    .ok()
+            })
+            .map(Some)
+            .ok_or(PreconditionError::Failed)
+    }
+}
+
+impl<S> FromRequestParts<S> for IfMatch
+where
+    S: Send + Sync,
+{
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self::from_headers(&parts.headers))
+    }
+}
+
+/// 条件来自请求头，不产生额外的 OpenAPI 参数
+impl aide::OperationInput for IfMatch {}
+
+/// 资源版本号对应的强 ETag（如 `"3"`）
+pub fn version_tag(version: i32) -> String {
+    format!("\"{version}\"")
+}
+
+/// 条件请求的响应：完整响应或 304
+// 每个请求只构造一次并立即转为响应，变体大小差异不影响性能，装箱反而多一次分配
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug)]
+pub enum Conditional<T: Serialize> {
+    /// 资源已变化，返回完整响应
+    Modified(ApiResponse<T>),
+
+    /// 资源未变化，返回不带响应体的 304
+    NotModified {
+        /// 资源当前的 ETag
+        etag: String,
+    },
+}
+
+impl<T: Serialize> Conditional<T> {
+    /// 以指定 ETag 返回 304
+    pub fn not_modified(etag: impl Into<String>) -> Self {
+        Self::NotModified { etag: etag.into() }
+    }
+}
+
+impl<T: Serialize + JsonSchema> IntoResponse for Conditional<T> {
+    fn into_response(self) -> Response {
+        match self {
+            Self::Modified(response) => response.into_response(),
+            Self::NotModified { etag } => {
+                let mut response = StatusCode::NOT_MODIFIED.into_response();
+                if let Ok(value) = HeaderValue::from_str(&etag) {
+                    response.headers_mut().insert(ETAG, value);
+                }
+                response
+            }
+        }
+    }
+}
+
+impl<T: Serialize + JsonSchema> OperationOutput for Conditional<T> {
+    type Inner = T;
+
+    fn operation_response(
+        ctx: &mut GenContext,
+        operation: &mut Operation,
+    ) -> Option<aide::openapi::Response> {
+        ApiResponse::<T>::operation_response(ctx, operation)
+    }
+
+    fn inferred_responses(
+        ctx: &mut GenContext,
+        operation: &mut Operation,
+    ) -> Vec<(Option<u16>, aide::openapi::Response)> {
+        let mut responses = Vec::new();
+        if let Some(response) = Self::operation_response(ctx, operation) {
+            responses.push((Some(200), response));
+        }
+        responses.push((
+            Some(304),
+            aide::openapi::Response {
+                description: "资源未修改（If-None-Match 命中）".to_string(),
+                ..Default::default()
+            },
+        ));
+        responses
+    }
+}
@@ -0,0 +1,249 @@
+//! 请求体与响应体编码（JSON / MessagePack / CBOR）
+//!
+//! JSON 为默认编码。带宽受限的客户端可以用 `Accept: application/msgpack` 或 `application/cbor`
+//! 获取二进制编码的响应，并以相同的 `Content-Type` 提交请求体；数据结构与 JSON 完全一致。
+//!
+//! 响应编码由 `encoding_middleware` 按 `Accept` 协商，在请求处理期间设为当前编码，
+//! `ApiResponse` 据此序列化；请求体由 `Json<T>` 按 `Content-Type` 解码。
+
+use aide::openapi::{Operation, ReferenceOr};
+use axum::http::header::CONTENT_TYPE;
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use indexmap::IndexMap;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::core::response::media_quality;
+
+/// MessagePack 的媒体类型
+pub const MSGPACK: &str = "application/msgpack";
+
+/// CBOR 的媒体类型
+pub const CBOR: &str = "application/cbor";
+
+/// 请求体中也接受的 MessagePack 媒体类型别名
+const MSGPACK_ALIASES: [&str; 3] = [MSGPACK, "application/x-msgpack", "application/vnd.msgpack"];
+
+/// 请求体或响应体的编码
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BodyEncoding {
+    /// `application/json`
+    #[default]
+    Json,
+
+    /// `application/msgpack`（结构体编码为以字段名为键的 map）
+    MessagePack,
+
+    /// `application/cbor`
+    Cbor,
+}
+
+impl BodyEncoding {
+    /// 响应的媒体类型
+    pub fn media_type(self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::MessagePack => MSGPACK,
+            Self::Cbor => CBOR,
+        }
+    }
+
+    /// 按 `Accept` 头协商响应编码
+    ///
+    /// 只有 MessagePack 或 CBOR 被显式列出且 q 值高于 `application/json` 时才选用二进制编码，
+    /// 其余情况（未携带、`*/*`、q 值相同）均为 JSON。
+    pub fn negotiate(accept: Option<&str>) -> Self {
+        let Some(accept) = accept else {
+            return Self::Json;
+        };
+        let json = media_quality(accept, "application/json");
+        let msgpack = MSGPACK_ALIASES
+            .iter()
+            .map(|media_type| media_quality(accept, media_type))
+            .fold(0.0, f32::max);
+        let cbor = media_quality(accept, CBOR);
+
+        if msgpack > json && msgpack >= cbor {
+            Self::MessagePack
+        } else if cbor > json {
+            Self::Cbor
+        } else {
+            Self::Json
+        }
+    }
+
+    /// 由请求的 `Content-Type` 识别请求体编码，不受支持时返回 `None`
+    ///
+    /// `application/json` 与 `application/*+json` 均视为 JSON。
+    pub fn from_content_type(headers: &HeaderMap) -> Option<Self> {
+        let content_type = headers.get(CONTENT_TYPE)?.to_str().ok()?;
+        let essence = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        if essence == "application/json"
+            || essence
+                .strip_prefix("application/")
+                .is_some_and(|subtype| subtype.ends_with("+json"))
+        {
+            Some(Self::Json)
+        } else if MSGPACK_ALIASES.contains(&essence.as_str()) {
+            Some(Self::MessagePack)
+        } else if essence == CBOR {
+            Some(Self::Cbor)
+        } else {
+            None
+        }
+    }
+
+    /// 由响应的 `Content-Type` 识别响应体编码
+    pub fn of_response(response: &Response) -> Option<Self> {
+        Self::from_content_type(response.headers())
+    }
+
+    /// 序列化为当前编码
+    pub fn encode<T: Serialize + ?Sized>(self, value: &T) -> Result<Vec<u8>, String> {
+        match self {
+            Self::Json => serde_json::to_vec(value).map_err(|e| e.to_string()),
+            Self::MessagePack => rmp_serde::to_vec_named(value).map_err(|e| e.to_string()),
+            Self::Cbor => {
+                let mut bytes = Vec::new();
+                ciborium::into_writer(value, &mut bytes).map_err(|e| e.to_string())?;
+                Ok(bytes)
+            }
+        }
+    }
+
+    /// 按当前编码反序列化
+    ///
+    /// 返回的错误区分无法解码（语法错误）与字段缺失、类型不符（数据错误），
+    /// 数据错误附带出错字段的路径。值之后的多余内容视为语法错误。
+    /// `Json<T>` 解析 JSON 请求体时另行报告行列号，不经过这里。
+    pub fn decode<T: DeserializeOwned>(self, bytes: &[u8]) -> Result<T, DecodeError> {
+        let mut rest = bytes;
+        let value = match self {
+            Self::Json => {
+                let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+                let value = serde_path_to_error::deserialize(&mut deserializer).map_err(
+                    |error| match error.inner().classify() {
+                        serde_json::error::Category::Data => DecodeError::data(error),
+                        _ => DecodeError::Syntax(error.inner().to_string()),
+                    },
+                )?;
+                deserializer
+                    .end()
+                    .map_err(|e| DecodeError::Syntax(e.to_string()))?;
+                return Ok(value);
+            }
+            Self::MessagePack => {
+                let mut deserializer = rmp_serde::Deserializer::new(&mut rest);
+                serde_path_to_error::deserialize(&mut deserializer).map_err(|error| {
+                    use rmp_serde::decode::Error;
+                    match error.inner() {
+                        Error::Syntax(_) | Error::TypeMismatch(_) | Error::OutOfRange => {
+                            DecodeError::data(error)
+                        }
+                        _ => DecodeError::Syntax(error.inner().to_string()),
+                    }
+                })?
+            }
+            Self::Cbor => {
+                // ciborium 不公开其 Deserializer，无法直接套用 serde_path_to_error：
+                // 先解码为通用值（语法错误），再经 JSON 值反序列化并记录出错路径（数据错误）
+                let value: ciborium::Value = ciborium::from_reader(&mut rest)
+                    .map_err(|e| DecodeError::Syntax(e.to_string()))?;
+                let value =
+                    serde_json::to_value(value).map_err(|e| DecodeError::Syntax(e.to_string()))?;
+                serde_path_to_error::deserialize(value).map_err(DecodeError::data)?
+            }
+        };
+        if !rest.is_empty() {
+            return Err(DecodeError::Syntax(format!(
+                "值之后有 {} 字节多余内容",
+                rest.len()
+            )));
+        }
+        Ok(value)
+    }
+}
+
+/// 请求体的解码错误
+#[derive(Debug)]
+pub enum DecodeError {
+    /// 无法解码
+    Syntax(String),
+
+    /// 解码成功但字段缺失、类型或取值不符
+    Data {
+        /// 出错字段的路径
+        path: serde_path_to_error::Path,
+        message: String,
+    },
+}
+
+impl DecodeError {
+    fn data<E: std::fmt::Display>(error: serde_path_to_error::Error<E>) -> Self {
+        Self::Data {
+            message: error.inner().to_string(),
+            path: error.path().clone(),
+        }
+    }
+}
+
+tokio::task_local! {
+    static CURRENT_ENCODING: BodyEncoding;
+}
+
+/// 在指定响应编码下执行 future（请求处理期间 `current_encoding` 返回该编码）
+pub async fn with_encoding<F: Future>(encoding: BodyEncoding, future: F) -> F::Output {
+    CURRENT_ENCODING.scope(encoding, future).await
+}
+
+/// 当前请求协商出的响应编码，不在 `encoding_middleware` 作用域内时为 JSON
+pub fn current_encoding() -> BodyEncoding {
+    CURRENT_ENCODING
+        .try_with(|encoding| *encoding)
+        .unwrap_or_default()
+}
+
+/// 按当前响应编码序列化响应体
+pub(crate) fn encoded_response<T: Serialize + ?Sized>(status: StatusCode, value: &T) -> Response {
+    let encoding = current_encoding();
+    match encoding.encode(value) {
+        Ok(bytes) => (
+            status,
+            [(
+                CONTENT_TYPE,
+                HeaderValue::from_static(encoding.media_type()),
+            )],
+            bytes,
+        )
+            .into_response(),
+        Err(e) => {
+            tracing::error!(error = %e, encoding = ?encoding, "failed to serialize response body");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// 将 JSON 媒体类型的 schema 复制到 MessagePack 与 CBOR，供文档列出备选编码
+pub(crate) fn add_binary_media_types(content: &mut IndexMap<String, aide::openapi::MediaType>) {
+    let Some(json) = content.get("application/json").cloned() else {
+        return;
+    };
+    for media_type in [MSGPACK, CBOR] {
+        content
+            .entry(media_type.to_string())
+            .or_insert_with(|| json.clone());
+    }
+}
+
+/// 为请求体文档追加 MessagePack 与 CBOR 媒体类型
+pub(crate) fn document_binary_request_body(operation: &mut Operation) {
+    if let Some(ReferenceOr::Item(body)) = operation.request_body.as_mut() {
+        add_binary_media_types(&mut body.content);
+    }
+}
@@ -0,0 +1,231 @@
+use aide::OperationInput;
+use aide::generate::GenContext;
+use aide::openapi::Operation;
+use axum::body::Bytes;
+use axum::extract::{FromRequest, FromRequestParts, RawPathParams, Request};
+use axum::http::request::Parts;
+use axum::http::{StatusCode, Uri};
+use serde::de::DeserializeOwned;
+
+use super::encoding::{BodyEncoding, document_binary_request_body};
+use crate::core::response::{Domain, ErrorCase, Reason, error_responses, merge_error_responses};
+use crate::{AppError, RejectionError};
+
+/// `Json<T>` 拒绝请求时可能返回的错误
+const JSON_REJECTIONS: [ErrorCase; 5] = [
+    ErrorCase::new(
+        StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        Domain::VALIDATION,
+        Reason::UnsupportedMediaType,
+    ),
+    ErrorCase::new(
+        StatusCode::BAD_REQUEST,
+        Domain::VALIDATION,
+        Reason::InvalidFormat,
+    ),
+    ErrorCase::new(
+        StatusCode::UNPROCESSABLE_ENTITY,
+        Domain::VALIDATION,
+        Reason::InvalidFormat,
+    ),
+    ErrorCase::new(
+        StatusCode::UNPROCESSABLE_ENTITY,
+        Domain::VALIDATION,
+        Reason::RequiredFieldMissing,
+    ),
+    ErrorCase::new(
+        StatusCode::PAYLOAD_TOO_LARGE,
+        Domain::VALIDATION,
+        Reason::PayloadTooLarge,
+    ),
+];
+
+/// `Query<T>` 拒绝请求时可能返回的错误
+const QUERY_REJECTIONS: [ErrorCase; 2] = [
+    ErrorCase::new(
+        StatusCode::BAD_REQUEST,
+        Domain::VALIDATION,
+        Reason::InvalidFormat,
+    ),
+    ErrorCase::new(
+        StatusCode::BAD_REQUEST,
+        Domain::VALIDATION,
+        Reason::RequiredFieldMissing,
+    ),
+];
+
+/// `Path<T>` 拒绝请求时可能返回的错误
+const PATH_REJECTIONS: [ErrorCase; 1] = [ErrorCase::new(
+    StatusCode::BAD_REQUEST,
+    Domain::VALIDATION,
+    Reason::InvalidFormat,
+)];
+
+/// 将提取器的拒绝响应写入接口文档（与已有响应合并，多个提取器可共存）
+pub(crate) fn document_rejections(
+    ctx: &mut GenContext,
+    operation: &mut Operation,
+    cases: &[ErrorCase],
+) {
+    let responses = error_responses(ctx, cases);
+    merge_error_responses(operation, responses);
+}
+
+/// 解析请求体的提取器
+///
+/// 与 `axum::Json` 行为一致，但拒绝时返回统一格式的 `ApiError`：
+/// Content-Type 不是 JSON、MessagePack 或 CBOR 时返回 415，语法错误返回 400，
+/// 字段缺失或类型不符返回 422，`location` 为出错字段的 JSON Pointer；
+/// JSON 请求体的错误消息中还包含出错的行列号和字节偏移。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Json<T>(pub T);
+
+impl<T> Json<T>
+where
+    T: DeserializeOwned,
+{
+    /// 从原始字节解析
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, RejectionError> {
+        let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+        let value = serde_path_to_error::deserialize(&mut deserializer)
+            .map_err(|error| RejectionError::from_json(error, bytes))?;
+        // 拒绝 JSON 值之后的多余内容
+        deserializer
+            .end()
+            .map_err(|error| RejectionError::from_json_error(&error, bytes))?;
+        Ok(Self(value))
+    }
+}
+
+impl<T, S> FromRequest<S> for Json<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Some(encoding) = BodyEncoding::from_content_type(req.headers()) else {
+            return Err(RejectionError::UnsupportedMediaType.into());
+        };
+        let bytes = Bytes::from_request(req, state)
+            .await
+            .map_err(RejectionError::from)?;
+        match encoding {
+            BodyEncoding::Json => Ok(Self::from_bytes(&bytes)?),
+            encoding => encoding
+                .decode(&bytes)
+                .map(Self)
+                .map_err(|error| RejectionError::from_decode(encoding, error).into()),
+        }
+    }
+}
+
+impl<T> OperationInput for Json<T>
+where
+    T: schemars::JsonSchema,
+{
+    fn operation_input(ctx: &mut GenContext, operation: &mut Operation) {
+        axum::Json::<T>::operation_input(ctx, operation);
+        document_binary_request_body(operation);
+        document_rejections(ctx, operation, &JSON_REJECTIONS);
+    }
+}
+
+/// 解析查询字符串的提取器
+///
+/// 与 `axum::extract::Query` 行为一致，拒绝时返回 400，
+/// `location` 为出错参数的 JSON Pointer、`location_type` 为 `query`。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Query<T>(pub T);
+
+impl<T> Query<T>
+where
+    T: DeserializeOwned,
+{
+    /// 从 URI 的查询字符串解析
+    pub fn try_from_uri(uri: &Uri) -> Result<Self, RejectionError> {
+        let query = uri.query().unwrap_or_default();
+        let deserializer =
+            serde_urlencoded::Deserializer::new(form_urlencoded::parse(query.as_bytes()));
+        serde_path_to_error::deserialize(deserializer)
+            .map(Self)
+            .map_err(RejectionError::from_query)
+    }
+}
+
+impl<T, S> FromRequestParts<S> for Query<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self::try_from_uri(&parts.uri)?)
+    }
+}
+
+impl<T> OperationInput for Query<T>
+where
+    T: schemars::JsonSchema,
+{
+    fn operation_input(ctx: &mut GenContext, operation: &mut Operation) {
+        axum::extract::Query::<T>::operation_input(ctx, operation);
+        document_rejections(ctx, operation, &QUERY_REJECTIONS);
+    }
+}
+
+/// 解析路径参数的提取器
+///
+/// 包装 `axum::extract::Path`，拒绝时返回 400，
+/// `location` 为出错参数名、`location_type` 为 `path`。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Path<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for Path<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match axum::extract::Path::<T>::from_request_parts(parts, state).await {
+            Ok(axum::extract::Path(value)) => Ok(Self(value)),
+            Err(rejection) => {
+                let mut error = RejectionError::from(rejection);
+                // 单个标量参数解析失败时 axum 不报告参数名，从原始路径参数中补上
+                if let RejectionError::Path {
+                    key: key @ None, ..
+                } = &mut error
+                {
+                    *key = single_param_key(parts, state).await;
+                }
+                Err(error.into())
+            }
+        }
+    }
+}
+
+async fn single_param_key<S>(parts: &mut Parts, state: &S) -> Option<String>
+where
+    S: Send + Sync,
+{
+    let params = RawPathParams::from_request_parts(parts, state).await.ok()?;
+    let mut keys = params.iter().map(|(key, _)| key);
+    match (keys.next(), keys.next()) {
+        (Some(key), None) => Some(key.to_string()),
+        _ => None,
+    }
+}
+
+impl<T> OperationInput for Path<T>
+where
+    T: schemars::JsonSchema,
+{
+    fn operation_input(ctx: &mut GenContext, operation: &mut Operation) {
+        axum::extract::Path::<T>::operation_input(ctx, operation);
+        document_rejections(ctx, operation, &PATH_REJECTIONS);
+    }
+}
@@ -0,0 +1,270 @@
+//! 请求原始 URL 与分页链接
+//!
+//! `RequestUrl` 还原客户端看到的请求地址（经受信任代理时按 `X-Forwarded-*` 还原协议、主机和路径前缀），
+//! 供 `ApiResponse::with_page_links` 生成绝对的分页链接：
+//!
+//! ```ignore
+//! pub async fn list_users(url: RequestUrl, ...) -> Result<ApiResponse<UserListItem>, AppError> {
+//!     Ok(ApiResponse::list(items, total, page, page_size).with_page_links(&url))
+//! }
+//! ```
+
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use axum::extract::{FromRequestParts, OriginalUri};
+use axum::http::header::HOST;
+use axum::http::request::Parts;
+use axum::http::uri::Authority;
+use axum::http::{HeaderMap, Uri};
+use ipnetwork::IpNetwork;
+use serde::{Deserialize, Serialize};
+
+/// 分页链接中的页码参数名
+pub const PAGE_PARAM: &str = "page";
+
+/// 分页链接模板中的页码占位符
+pub const PAGE_PLACEHOLDER: &str = "{page}";
+
+/// 受信任的反向代理地址
+///
+/// 只有直接对端地址属于其中之一时才采信 `X-Forwarded-*` 请求头，
+/// 否则客户端可以伪造这些头让响应中的链接指向任意主机。
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies {
+    networks: Vec<IpNetwork>,
+}
+
+impl TrustedProxies {
+    /// 解析 IP 地址或 CIDR 网段列表（如 `127.0.0.1`、`10.0.0.0/8`）
+    pub fn parse<S: AsRef<str>>(entries: &[S]) -> Result<Self, String> {
+        let networks = entries
+            .iter()
+            .map(|entry| {
+                let entry = entry.as_ref().trim();
+                IpNetwork::from_str(entry).map_err(|_| format!("无效的受信任代理地址：{}", entry))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { networks })
+    }
+
+    /// 对端地址是否为受信任代理
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        let ip = ip.to_canonical();
+        self.networks.iter().any(|network| network.contains(ip))
+    }
+
+    /// 客户端地址
+    ///
+    /// 对端为受信任代理时，从右向左跳过 `X-Forwarded-For` 中的受信任代理，
+    /// 取第一个不受信任的地址（更左侧的值可由客户端伪造）；否则为对端地址本身。
+    pub fn client_ip(&self, headers: &HeaderMap, peer: IpAddr) -> IpAddr {
+        if !self.contains(peer) {
+            return peer;
+        }
+        let forwarded: Vec<IpAddr> = headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .filter_map(|entry| IpAddr::from_str(entry.trim()).ok())
+            .collect();
+        forwarded
+            .iter()
+            .rev()
+            .find(|ip| !self.contains(**ip))
+            .or(forwarded.first())
+            .copied()
+            .unwrap_or(peer)
+    }
+}
+
+/// 客户端看到的请求 URL
+///
+/// 路径取自 `OriginalUri`，不受 `nest` 剥离前缀的影响。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestUrl {
+    /// 协议和主机（如 `https://api.example.com`）
+    origin: String,
+
+    /// 路径（含代理的路径前缀）
+    path: String,
+
+    /// 查询参数（已解码）
+    query: Vec<(String, String)>,
+}
+
+impl RequestUrl {
+    /// 由请求头和 URI 还原
+    ///
+    /// `peer` 为直接对端地址，属于 `proxies` 时采信 `X-Forwarded-Proto`、`X-Forwarded-Host`、
+    /// `X-Forwarded-Port` 和 `X-Forwarded-Prefix`（多级代理追加的值取最左侧，即最外层代理收到的值）。
+    pub fn from_request(
+        headers: &HeaderMap,
+        uri: &Uri,
+        peer: Option<IpAddr>,
+        proxies: &TrustedProxies,
+    ) -> Self {
+        let trusted = peer.is_some_and(|ip| proxies.contains(ip));
+        let forwarded = |name: &str| {
+            trusted
+                .then(|| headers.get(name)?.to_str().ok()?.split(',').next())
+                .flatten()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+
+        let scheme = forwarded("x-forwarded-proto")
+            .filter(|proto| {
+                proto.eq_ignore_ascii_case("http") || proto.eq_ignore_ascii_case("https")
+            })
+            .map(str::to_ascii_lowercase)
+            .or_else(|| uri.scheme_str().map(str::to_string))
+            .unwrap_or_else(|| "http".to_string());
+
+        let mut host = forwarded("x-forwarded-host")
+            .and_then(|host| Authority::from_str(host).ok())
+            .or_else(|| {
+                let host = headers.get(HOST)?.to_str().ok()?;
+                Authority::from_str(host).ok()
+            })
+            .or_else(|| uri.authority().cloned())
+            .map_or_else(
+                || "localhost".to_string(),
+                |authority| authority.to_string(),
+            );
+        if let Some(port) = forwarded("x-forwarded-port").and_then(|port| port.parse::<u16>().ok())
+            && !host.contains(':')
+            && !matches!((scheme.as_str(), port), ("http", 80) | ("https", 443))
+        {
+            host = format!("{host}:{port}");
+        }
+
+        let prefix = forwarded("x-forwarded-prefix")
+            .filter(|prefix| prefix.starts_with('/') && !prefix.contains(['?', '#']))
+            .map_or("", |prefix| prefix.trim_end_matches('/'));
+        let query = uri
+            .query()
+            .map(|query| {
+                form_urlencoded::parse(query.as_bytes())
+                    .into_owned()
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            origin: format!("{scheme}://{host}"),
+            path: format!("{prefix}{}", uri.path()),
+            query,
+        }
+    }
+
+    /// 协议和主机
+    pub fn origin(&self) -> &str {
+        &self.origin
+    }
+
+    /// 完整 URL
+    pub fn href(&self) -> String {
+        let query = self.query_without(None);
+        if query.is_empty() {
+            format!("{}{}", self.origin, self.path)
+        } else {
+            format!("{}{}?{query}", self.origin, self.path)
+        }
+    }
+
+    /// 指定页码的 URL，保留其余查询参数（如 `page_size`、`fields`）
+    pub fn page_href(&self, page: i64) -> String {
+        self.page_url(&page.to_string())
+    }
+
+    /// 分页链接模板，页码位置为 `{page}`
+    pub fn page_template(&self) -> String {
+        self.page_url(PAGE_PLACEHOLDER)
+    }
+
+    fn page_url(&self, page: &str) -> String {
+        let query = self.query_without(Some(PAGE_PARAM));
+        let separator = if query.is_empty() { "" } else { "&" };
+        format!(
+            "{}{}?{query}{separator}{PAGE_PARAM}={page}",
+            self.origin, self.path
+        )
+    }
+
+    fn query_without(&self, excluded: Option<&str>) -> String {
+        let mut serializer = form_urlencoded::Serializer::new(String::new());
+        for (key, value) in &self.query {
+            if Some(key.as_str()) != excluded {
+                serializer.append_pair(key, value);
+            }
+        }
+        serializer.finish()
+    }
+}
+
+impl<S> FromRequestParts<S> for RequestUrl
+where
+    S: Send + Sync,
+{
+    type Rejection = std::convert::Infallible;
+
+    /// 优先使用 `pagination_links_middleware` 按受信任代理还原的 URL，
+    /// 未启用该中间件时不采信任何 `X-Forwarded-*` 请求头
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(url) = parts.extensions.get::<Self>() {
+            return Ok(url.clone());
+        }
+        let uri = parts
+            .extensions
+            .get::<OriginalUri>()
+            .map_or(&parts.uri, |original| &original.0);
+        Ok(Self::from_request(
+            &parts.headers,
+            uri,
+            None,
+            &TrustedProxies::default(),
+        ))
+    }
+}
+
+/// URL 来自请求本身，不产生额外的 OpenAPI 参数
+impl aide::OperationInput for RequestUrl {}
+
+/// 分页响应头（RFC 8288 `Link` 与 `X-Total-Count`）
+///
+/// 由 `ApiResponse` 写入响应扩展，`pagination_links_middleware` 按配置输出为响应头。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct PageLinks {
+    /// `Link` 头的值
+    pub(crate) link: String,
+
+    /// 总数据量
+    pub(crate) total: i64,
+}
+
+impl PageLinks {
+    /// 由分页链接模板生成 `first`、`prev`、`next`、`last` 关系
+    pub(crate) fn new(template: &str, page: i64, total_pages: i64, total: i64) -> Self {
+        let last = total_pages.max(1);
+        let mut relations = vec![("first", 1)];
+        if page > 1 {
+            relations.push(("prev", (page - 1).min(last)));
+        }
+        if page < total_pages {
+            relations.push(("next", page + 1));
+        }
+        relations.push(("last", last));
+
+        let link = relations
+            .into_iter()
+            .map(|(rel, page)| {
+                let href = template.replace(PAGE_PLACEHOLDER, &page.to_string());
+                format!("<{href}>; rel=\"{rel}\"")
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        Self { link, total }
+    }
+}
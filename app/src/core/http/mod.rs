@@ -1,5 +1,22 @@
+mod conditional;
+mod encoding;
+mod extract;
+mod links;
 mod pagination;
+mod request_id;
+mod route_prefix;
+mod validated;
 
+pub use conditional::{Conditional, IfMatch, IfNoneMatch, digest_etag, version_etag, version_tag};
+pub(crate) use conditional::{ResourceVersion, fields_param};
+pub use encoding::{BodyEncoding, CBOR, DecodeError, MSGPACK, current_encoding, with_encoding};
+pub(crate) use encoding::{add_binary_media_types, encoded_response};
+pub use extract::{Json, Path, Query};
+pub(crate) use links::PageLinks;
+pub use links::{PAGE_PARAM, PAGE_PLACEHOLDER, RequestUrl, TrustedProxies};
 pub use pagination::{
     DEFAULT_PAGE, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE, MIN_PAGE_SIZE, Pagination, PaginationQuery,
 };
+pub use request_id::{RequestId, TraceContext, X_REQUEST_ID, current_request_id, with_request_id};
+pub use route_prefix::RoutePrefixMap;
+pub use validated::{ValidatedJson, ValidatedPath, ValidatedQuery};
@@ -0,0 +1,159 @@
+//! 请求 ID 与 W3C Trace Context
+//!
+//! 网关传入的 `X-Request-ID` 格式合法时沿用，否则生成新的 UUID；`traceparent` / `tracestate`
+//! 按 [W3C Trace Context](https://www.w3.org/TR/trace-context/) 解析，用于跨服务关联日志。
+//! 两者由 `request_id_middleware` 写入请求扩展，请求 ID 同时在处理期间设为当前请求 ID，
+//! 使 `ApiResponse` 输出的错误对象带上 `request_id`。
+
+use std::fmt;
+
+use axum::http::{HeaderMap, HeaderName};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// `X-Request-ID` 请求头 / 响应头
+pub const X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+/// W3C `traceparent` 请求头
+pub const TRACEPARENT: HeaderName = HeaderName::from_static("traceparent");
+
+/// W3C `tracestate` 请求头
+pub const TRACESTATE: HeaderName = HeaderName::from_static("tracestate");
+
+/// 请求 ID 的最大长度
+pub const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// `tracestate` 的最大长度（W3C 要求至少支持 512 字符）
+const MAX_TRACESTATE_LEN: usize = 512;
+
+/// 请求 ID
+///
+/// 1 到 128 个字符，只能包含 ASCII 字母、数字和 `-`、`_`、`.`、`:`，
+/// 可以原样写入响应头和日志。
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(transparent)]
+pub struct RequestId(String);
+
+impl RequestId {
+    /// 生成新的请求 ID（UUID v4）
+    pub fn generate() -> Self {
+        Self(Uuid::new_v4().to_string())
+    }
+
+    /// 校验传入的请求 ID，长度或字符不合法时返回 `None`
+    pub fn parse(value: &str) -> Option<Self> {
+        let valid = !value.is_empty()
+            && value.len() <= MAX_REQUEST_ID_LEN
+            && value
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b':'));
+        valid.then(|| Self(value.to_string()))
+    }
+
+    /// 沿用请求头中合法的 `X-Request-ID`，没有或不合法时生成新的
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        headers
+            .get(X_REQUEST_ID)
+            .and_then(|value| value.to_str().ok())
+            .and_then(Self::parse)
+            .unwrap_or_else(Self::generate)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// W3C Trace Context（`traceparent` 与 `tracestate`）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    /// 追踪 ID（32 位小写十六进制），同一调用链上的所有请求相同
+    pub trace_id: String,
+
+    /// 上游调用方的 span ID（16 位小写十六进制）
+    pub parent_id: String,
+
+    /// 追踪标志位（最低位表示上游已采样）
+    pub flags: u8,
+
+    /// 各追踪系统的附加状态，原样传递
+    pub tracestate: Option<String>,
+}
+
+impl TraceContext {
+    /// 解析 `traceparent`（及可选的 `tracestate`），格式不合法时返回 `None`
+    ///
+    /// 版本 `00` 必须恰好包含四段；更高版本只取前四段，`ff` 版本无效。
+    /// 追踪 ID 和 span ID 不能全为 0。
+    pub fn parse(traceparent: &str, tracestate: Option<&str>) -> Option<Self> {
+        let mut parts = traceparent.trim().split('-');
+        let version = parts.next().filter(|v| is_lower_hex(v, 2) && *v != "ff")?;
+        let trace_id = parts
+            .next()
+            .filter(|id| is_lower_hex(id, 32) && !is_zero(id))?;
+        let parent_id = parts
+            .next()
+            .filter(|id| is_lower_hex(id, 16) && !is_zero(id))?;
+        let flags = parts.next().filter(|flags| is_lower_hex(flags, 2))?;
+        if version == "00" && parts.next().is_some() {
+            return None;
+        }
+
+        let tracestate = tracestate
+            .map(str::trim)
+            .filter(|state| !state.is_empty() && state.len() <= MAX_TRACESTATE_LEN)
+            .map(str::to_string);
+        Some(Self {
+            trace_id: trace_id.to_string(),
+            parent_id: parent_id.to_string(),
+            flags: u8::from_str_radix(flags, 16).ok()?,
+            tracestate,
+        })
+    }
+
+    /// 从请求头解析，没有或不合法的 `traceparent` 返回 `None`（此时 `tracestate` 一并忽略）
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let traceparent = headers.get(TRACEPARENT)?.to_str().ok()?;
+        let tracestate = headers
+            .get(TRACESTATE)
+            .and_then(|value| value.to_str().ok());
+        Self::parse(traceparent, tracestate)
+    }
+
+    /// 上游是否已采样
+    pub fn sampled(&self) -> bool {
+        self.flags & 0x01 == 0x01
+    }
+}
+
+fn is_lower_hex(value: &str, len: usize) -> bool {
+    value.len() == len
+        && value
+            .bytes()
+            .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+fn is_zero(value: &str) -> bool {
+    value.bytes().all(|b| b == b'0')
+}
+
+tokio::task_local! {
+    static CURRENT_REQUEST_ID: RequestId;
+}
+
+/// 在指定请求 ID 下执行 future（请求处理期间 `current_request_id` 返回该 ID）
+pub async fn with_request_id<F: Future>(request_id: RequestId, future: F) -> F::Output {
+    CURRENT_REQUEST_ID.scope(request_id, future).await
+}
+
+/// 当前请求的 ID，不在 `with_request_id` 范围内时返回 `None`
+pub fn current_request_id() -> Option<RequestId> {
+    CURRENT_REQUEST_ID.try_with(Clone::clone).ok()
+}
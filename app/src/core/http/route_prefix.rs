@@ -0,0 +1,43 @@
+//! 按路径前缀覆盖的设置
+//!
+//! 超时、请求体上限、内容安全策略等设置都有一个全局默认值，并允许按路径前缀覆盖。
+//! 前缀按路径段匹配（`/v1/user/import` 匹配 `/v1/user/import/csv`，不匹配 `/v1/user/imports`），
+//! 多个前缀匹配时取最长的一个。
+
+/// 默认值加按路径前缀的覆盖
+#[derive(Debug, Clone, Default)]
+pub struct RoutePrefixMap<T> {
+    default: T,
+
+    /// 按前缀长度降序排列，保证最长前缀优先匹配
+    routes: Vec<(String, T)>,
+}
+
+impl<T> RoutePrefixMap<T> {
+    pub fn new(default: T) -> Self {
+        Self {
+            default,
+            routes: Vec::new(),
+        }
+    }
+
+    /// 覆盖指定路径前缀下所有路由的值，末尾的 `/` 会被忽略，同一前缀后设置的生效
+    pub fn insert(&mut self, prefix: impl Into<String>, value: T) {
+        let prefix = prefix.into().trim_end_matches('/').to_string();
+        self.routes.retain(|(existing, _)| *existing != prefix);
+        self.routes.push((prefix, value));
+        self.routes
+            .sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+    }
+
+    /// 请求路径适用的值，没有前缀匹配时返回默认值
+    pub fn get(&self, path: &str) -> &T {
+        self.routes
+            .iter()
+            .find(|(prefix, _)| {
+                path.strip_prefix(prefix.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+            })
+            .map_or(&self.default, |(_, value)| value)
+    }
+}
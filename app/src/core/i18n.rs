@@ -0,0 +1,256 @@
+//! 错误消息本地化
+//!
+//! 消息目录以 `(domain, reason)` 为键，每种语言一个 TOML 文件（`app/locales/*.toml`），
+//! 编译时嵌入二进制。语言由 `locale_middleware` 按 `Accept-Language` 协商，
+//! 在请求处理期间保存在任务局部变量中，`ApiResponse` 渲染时据此本地化错误消息并设置 `data.lang`。
+//!
+//! 代码中的错误消息以简体中文书写（源语言）。源语言下只替换以错误码作为消息的条目，
+//! 保留带有具体上下文的原始消息；其他语言下统一使用目录中的译文。
+//!
+//! 目录消息可以包含 `{name}` 占位符，由错误详情的消息参数（如 `min`、`max`、`line`、`column`）填充。
+//! 先查 domain 表再查通用表，取第一条占位符都能填充的消息；错误详情带有参数而译文一个都没用到时
+//! 保留原始消息，避免丢失约束范围、解析位置等信息。
+
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::sync::LazyLock;
+
+use axum::extract::FromRequestParts;
+use axum::http::StatusCode;
+use axum::http::request::Parts;
+use serde::{Deserialize, Serialize};
+
+use crate::core::response::{ApiError, ErrorDetail};
+
+/// 支持的语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Locale {
+    /// 简体中文（源语言）
+    #[default]
+    #[serde(rename = "zh-CN")]
+    ZhCn,
+
+    /// 英语
+    #[serde(rename = "en")]
+    En,
+}
+
+impl Locale {
+    /// 代码中错误消息使用的语言
+    pub const SOURCE: Self = Self::ZhCn;
+
+    /// 全部支持的语言
+    pub const ALL: [Self; 2] = [Self::ZhCn, Self::En];
+
+    /// BCP 47 语言标签
+    pub const fn tag(self) -> &'static str {
+        match self {
+            Self::ZhCn => "zh-CN",
+            Self::En => "en",
+        }
+    }
+
+    /// 从语言标签解析，按主语言匹配（如 `zh-Hans`、`en-US`）
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag.trim().split(['-', '_']).next()?.to_ascii_lowercase();
+        match primary.as_str() {
+            "zh" => Some(Self::ZhCn),
+            "en" => Some(Self::En),
+            _ => None,
+        }
+    }
+
+    /// 按 `Accept-Language` 协商语言
+    ///
+    /// 按 q 值从高到低选择第一个支持的语言；未携带该头、只有 `*`
+    /// 或没有支持的语言时返回 `default`。
+    pub fn negotiate(accept_language: Option<&str>, default: Self) -> Self {
+        let Some(accept_language) = accept_language else {
+            return default;
+        };
+        let mut ranges: Vec<(&str, f32)> = accept_language
+            .split(',')
+            .filter_map(|range| {
+                let mut params = range.split(';');
+                let tag = params.next()?.trim();
+                let quality = params
+                    .filter_map(|param| param.trim().strip_prefix("q="))
+                    .find_map(|q| q.trim().parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                (!tag.is_empty() && quality > 0.0).then_some((tag, quality))
+            })
+            .collect();
+        // 稳定排序，q 值相同时保持客户端给出的顺序
+        ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+        ranges
+            .into_iter()
+            .find_map(|(tag, _)| Self::from_tag(tag))
+            .unwrap_or(default)
+    }
+}
+
+impl<S> FromRequestParts<S> for Locale
+where
+    S: Send + Sync,
+{
+    type Rejection = std::convert::Infallible;
+
+    /// 读取 `locale_middleware` 协商出的语言，未经过该中间件时返回默认语言
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts.extensions.get::<Self>().copied().unwrap_or_default())
+    }
+}
+
+/// 语言来自请求头协商，不产生额外的 OpenAPI 参数
+impl aide::OperationInput for Locale {}
+
+tokio::task_local! {
+    static CURRENT_LOCALE: Locale;
+}
+
+/// 在指定语言下执行 future（请求处理期间 `current_locale` 返回该语言）
+pub async fn with_locale<F: Future>(locale: Locale, future: F) -> F::Output {
+    CURRENT_LOCALE.scope(locale, future).await
+}
+
+/// 当前请求协商出的语言，不在 `with_locale` 范围内时返回 `None`
+pub fn current_locale() -> Option<Locale> {
+    CURRENT_LOCALE.try_with(|locale| *locale).ok()
+}
+
+/// 单一语言的消息表
+#[derive(Debug, Default)]
+struct Bundle {
+    /// 按 reason 的通用消息
+    reasons: HashMap<String, String>,
+
+    /// 按 domain 覆盖的消息
+    domains: HashMap<String, HashMap<String, String>>,
+
+    /// 按 HTTP 状态码的消息
+    status: HashMap<String, String>,
+}
+
+/// 错误消息目录
+#[derive(Debug, Default)]
+pub struct MessageCatalog {
+    bundles: HashMap<Locale, Bundle>,
+}
+
+static BUILTIN: LazyLock<MessageCatalog> = LazyLock::new(|| {
+    let mut catalog = MessageCatalog::default();
+    for (locale, source) in [
+        (Locale::ZhCn, include_str!("../../locales/zh-CN.toml")),
+        (Locale::En, include_str!("../../locales/en.toml")),
+    ] {
+        catalog
+            .load(locale, source)
+            .unwrap_or_else(|e| panic!("内置消息目录 {} 格式错误：{}", locale.tag(), e));
+    }
+    catalog
+});
+
+impl MessageCatalog {
+    /// 内置的消息目录（`app/locales/*.toml`）
+    pub fn builtin() -> &'static Self {
+        &BUILTIN
+    }
+
+    /// 加载一种语言的消息文件（TOML，表名为 domain，另有 `reasons` 和 `status` 两个特殊表）
+    pub fn load(&mut self, locale: Locale, source: &str) -> Result<(), String> {
+        let mut tables: HashMap<String, HashMap<String, String>> =
+            toml::from_str(source).map_err(|e| e.to_string())?;
+        let bundle = Bundle {
+            reasons: tables.remove("reasons").unwrap_or_default(),
+            status: tables.remove("status").unwrap_or_default(),
+            domains: tables,
+        };
+        self.bundles.insert(locale, bundle);
+        Ok(())
+    }
+
+    /// 查找 `(domain, reason)` 对应的消息，domain 未覆盖时回退到 reason 的通用消息
+    ///
+    /// 只返回不含占位符的消息，带参数的消息通过 [`MessageCatalog::localize`] 渲染。
+    pub fn message(&self, locale: Locale, domain: &str, reason: &str) -> Option<&str> {
+        self.candidates(locale, domain, reason)
+            .find(|message| !message.contains('{'))
+    }
+
+    /// `(domain, reason)` 的候选消息：domain 表中的条目在前，通用表中的条目在后
+    fn candidates(&self, locale: Locale, domain: &str, reason: &str) -> impl Iterator<Item = &str> {
+        let bundle = self.bundles.get(&locale);
+        let domain = bundle
+            .and_then(|bundle| bundle.domains.get(domain))
+            .and_then(|messages| messages.get(reason));
+        let shared = bundle.and_then(|bundle| bundle.reasons.get(reason));
+        domain.into_iter().chain(shared).map(String::as_str)
+    }
+
+    /// 按错误详情的参数渲染消息，返回消息及是否用到了参数
+    ///
+    /// 依次尝试 domain 表和通用表中的消息，跳过占位符无法全部填充的条目。
+    fn render(&self, locale: Locale, detail: &ErrorDetail) -> Option<(String, bool)> {
+        self.candidates(locale, &detail.domain, &detail.reason)
+            .find_map(|template| fill(template, &detail.params))
+    }
+
+    /// 查找 HTTP 状态码对应的消息
+    pub fn status_message(&self, locale: Locale, status: StatusCode) -> Option<&str> {
+        self.bundles
+            .get(&locale)?
+            .status
+            .get(status.as_str())
+            .map(String::as_str)
+    }
+
+    /// 本地化错误对象
+    ///
+    /// 错误详情按 `(domain, reason)` 取译文；顶层消息在只有一个详情时与其一致，
+    /// 有多个详情或没有详情时使用状态码对应的消息。源语言下只替换以错误码作为消息的条目。
+    pub fn localize(&self, mut error: ApiError, locale: Locale) -> ApiError {
+        let translate = locale != Locale::SOURCE;
+        let bare_code = error
+            .errors
+            .first()
+            .is_some_and(|detail| error.message == detail.reason);
+
+        for detail in &mut error.errors {
+            let bare = detail.message == detail.reason;
+            if (translate || bare)
+                && let Some((message, used_params)) = self.render(locale, detail)
+                && (bare || used_params || detail.params.is_empty())
+            {
+                detail.message = message;
+            }
+        }
+
+        let status_message = self.status_message(locale, error.status_code());
+        let message = match error.errors.as_slice() {
+            [] => status_message,
+            [only] if translate || bare_code => Some(only.message.as_str()),
+            _ if translate || bare_code => status_message,
+            _ => None,
+        };
+        if let Some(message) = message {
+            error.message = message.to_string();
+        }
+        error
+    }
+}
+
+/// 用参数填充消息中的 `{name}` 占位符，缺少参数时返回 `None`
+fn fill(template: &str, params: &BTreeMap<String, String>) -> Option<(String, bool)> {
+    let mut message = String::with_capacity(template.len());
+    let mut used_params = false;
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let end = start + rest[start..].find('}')?;
+        message.push_str(&rest[..start]);
+        message.push_str(params.get(&rest[start + 1..end])?);
+        used_params = true;
+        rest = &rest[end + 1..];
+    }
+    message.push_str(rest);
+    Some((message, used_params))
+}
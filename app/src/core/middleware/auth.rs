@@ -1,4 +1,9 @@
 use axum::{extract::Request, middleware::Next, response::Response};
+use entity::{
+    enums::{UserRole, UserStatus},
+    user,
+};
+use sea_orm::EntityTrait;
 use tracing::warn;
 
 use crate::{AppState, error::AppError};
@@ -8,9 +13,13 @@ use std::sync::Arc;
 #[derive(Debug, Clone)]
 pub struct CurrentUser {
     pub user_id: i32,
+    pub role: UserRole,
 }
 
 /// 认证中间件 - 验证 JWT token
+///
+/// 除签名和有效期外，还会校验用户仍处于激活状态，且令牌签发时间不早于
+/// 用户的令牌生效起点（注销账号等操作会推进该时间以吊销全部令牌）。
 pub async fn require_auth(
     state: axum::extract::State<Arc<AppState>>,
     mut request: Request,
@@ -31,13 +40,48 @@ pub async fn require_auth(
     };
 
     // 验证 token
-    let user_id = state.jwt_service.extract_user_id(token).map_err(|_| {
-        warn!("Invalid or expired token");
-        AppError::Auth(crate::error::AuthError::InvalidPassword)
-    })?;
+    let claims = state
+        .jwt_service
+        .verify_token(token)
+        .map_err(|_| {
+            warn!("Invalid or expired token");
+            AppError::Auth(crate::error::AuthError::InvalidPassword)
+        })?
+        .claims;
+    let user_id = claims.sub.get();
+
+    // 校验用户状态和令牌是否已被吊销
+    let user_model = user::Entity::find_by_id(user_id)
+        .one(&state.db)
+        .await?
+        .ok_or(AppError::Auth(crate::error::AuthError::InvalidToken))?;
+    let revoked = user_model
+        .tokens_valid_after
+        .is_some_and(|valid_after| claims.iat <= valid_after.timestamp());
+    if user_model.status != i16::from(UserStatus::Active) || revoked {
+        warn!(user_id, "Token revoked or user inactive");
+        return Err(AppError::Auth(crate::error::AuthError::InvalidToken));
+    }
 
     // 将当前用户注入到请求扩展中
-    request.extensions_mut().insert(CurrentUser { user_id });
+    let role = UserRole::try_from(user_model.role).unwrap_or_default();
+    request
+        .extensions_mut()
+        .insert(CurrentUser { user_id, role });
+
+    Ok(next.run(request).await)
+}
+
+/// 管理员权限中间件 - 必须放在 `require_auth` 之后执行
+pub async fn require_admin(request: Request, next: Next) -> Result<Response, AppError> {
+    let is_admin = request
+        .extensions()
+        .get::<CurrentUser>()
+        .is_some_and(|user| user.role == UserRole::Admin);
+    if !is_admin {
+        warn!("Admin permission required");
+        return Err(AppError::Auth(crate::error::AuthError::PermissionDenied));
+    }
 
     Ok(next.run(request).await)
 }
@@ -0,0 +1,162 @@
+use std::convert::Infallible;
+
+use axum::body::Body;
+use axum::extract::{Request, State};
+use axum::http::header::{CONTENT_ENCODING, CONTENT_LENGTH};
+use axum::http::{HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use http_body_util::Limited;
+use tower::{ServiceExt, service_fn};
+use tower_http::decompression::{DecompressionBody, RequestDecompression};
+
+use crate::core::config::BodyLimitConfig;
+use crate::core::http::RoutePrefixMap;
+use crate::error::RejectionError;
+
+/// 上传路由在文件大小之外为 multipart 边界和字段头预留的开销
+pub const MULTIPART_OVERHEAD: usize = 64 * 1024;
+
+/// 支持解压的请求体编码
+const SUPPORTED_ENCODINGS: [&str; 3] = ["gzip", "deflate", "br"];
+
+/// 请求体大小限制：全局上限加按路径前缀的覆盖
+#[derive(Debug, Clone)]
+pub struct BodyLimit {
+    limits: RoutePrefixMap<usize>,
+
+    /// 是否解压 gzip / deflate / br 编码的请求体
+    decompression: bool,
+}
+
+impl BodyLimit {
+    pub fn new(default: usize) -> Self {
+        Self {
+            limits: RoutePrefixMap::new(default),
+            decompression: true,
+        }
+    }
+
+    /// 从配置构建（`max_size`、`route_limits` 与 `decompression`）
+    pub fn from_config(config: &BodyLimitConfig) -> Self {
+        config
+            .route_limits
+            .iter()
+            .fold(Self::new(config.max_size), |limit, (prefix, size)| {
+                limit.with_route(prefix.clone(), *size)
+            })
+            .with_decompression(config.decompression)
+    }
+
+    /// 覆盖指定路径前缀下所有路由的请求体上限
+    pub fn with_route(mut self, prefix: impl Into<String>, limit: usize) -> Self {
+        self.limits.insert(prefix, limit);
+        self
+    }
+
+    /// 保证上传路由的上限能容纳 `file_size` 字节的文件（加 multipart 开销）
+    ///
+    /// 上限只会被提高：配置中更大的覆盖值保持不变。
+    pub fn with_upload_route(self, prefix: impl Into<String>, file_size: usize) -> Self {
+        let prefix = prefix.into();
+        let required = file_size.saturating_add(MULTIPART_OVERHEAD);
+        if self.limit_for(&prefix) >= required {
+            return self;
+        }
+        self.with_route(prefix, required)
+    }
+
+    /// 是否解压请求体，关闭时带 `Content-Encoding` 的请求返回 415
+    pub fn with_decompression(mut self, enabled: bool) -> Self {
+        self.decompression = enabled;
+        self
+    }
+
+    /// 请求路径适用的请求体上限
+    pub fn limit_for(&self, path: &str) -> usize {
+        *self.limits.get(path)
+    }
+
+    /// 解析 `Content-Encoding`：返回需要解压的编码，不支持时返回 415 错误
+    fn encoding(&self, request: &Request) -> Result<Option<&'static str>, RejectionError> {
+        let Some(value) = request.headers().get(CONTENT_ENCODING) else {
+            return Ok(None);
+        };
+        let encoding = value.to_str().unwrap_or_default().trim();
+        if encoding.eq_ignore_ascii_case("identity") {
+            return Ok(None);
+        }
+        if self.decompression
+            && let Some(supported) = SUPPORTED_ENCODINGS
+                .into_iter()
+                .find(|supported| encoding.eq_ignore_ascii_case(supported))
+        {
+            return Ok(Some(supported));
+        }
+        Err(RejectionError::UnsupportedEncoding {
+            encoding: String::from_utf8_lossy(value.as_bytes()).into_owned(),
+            supported: if self.decompression {
+                SUPPORTED_ENCODINGS.join(", ")
+            } else {
+                "identity".to_string()
+            },
+        })
+    }
+}
+
+fn too_large(limit: usize) -> Response {
+    RejectionError::Body {
+        status: StatusCode::PAYLOAD_TOO_LARGE,
+        message: format!("请求体不能超过 {} 字节", limit),
+    }
+    .into_response()
+}
+
+/// 请求体大小限制与解压中间件
+///
+/// `Content-Length` 超出上限时不读取请求体，直接返回 413；其余请求体在读取时计数，
+/// 超出上限由提取器返回 413（`PAYLOAD_TOO_LARGE`，上传文件为 `FILE_TOO_LARGE`）。
+/// gzip / deflate / br 编码的请求体边读边解压，上限按解压后的字节数计算，
+/// 解压出的数据一超出上限就中止，不会把压缩炸弹完整展开到内存中。
+pub async fn body_limit_middleware(
+    State(body_limit): State<BodyLimit>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let limit = body_limit.limit_for(request.uri().path());
+
+    let encoding = match body_limit.encoding(&request) {
+        Ok(encoding) => encoding,
+        Err(e) => return e.into_response(),
+    };
+    let declared = request
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok()?.parse::<u64>().ok());
+    if declared.is_some_and(|length| length > limit as u64) {
+        return too_large(limit);
+    }
+
+    let Some(encoding) = encoding else {
+        request.headers_mut().remove(CONTENT_ENCODING);
+        return next
+            .run(request.map(|body| Body::new(Limited::new(body, limit))))
+            .await;
+    };
+
+    // 统一编码名的大小写，解压层只识别小写的编码名
+    request
+        .headers_mut()
+        .insert(CONTENT_ENCODING, HeaderValue::from_static(encoding));
+    let inner = service_fn(move |request: Request<DecompressionBody<Body>>| {
+        let next = next.clone();
+        async move {
+            let request = request.map(|body| Body::new(Limited::new(body, limit)));
+            Ok::<_, Infallible>(next.run(request).await)
+        }
+    });
+    match RequestDecompression::new(inner).oneshot(request).await {
+        Ok(response) => response.map(Body::new),
+        Err(infallible) => match infallible {},
+    }
+}
@@ -0,0 +1,183 @@
+use std::time::Duration;
+
+use axum::body::to_bytes;
+use axum::extract::{OriginalUri, Request, State};
+use axum::http::header::{ACCEPT, ACCEPT_LANGUAGE, CACHE_CONTROL};
+use axum::http::{HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::auth::CurrentUser;
+use super::idempotency::{fits_in, restore, stored_headers};
+use crate::core::http::{PageLinks, RequestUrl, ResourceVersion};
+use crate::shared::{Cache, StoredResponse};
+
+/// 路由级 GET 响应缓存设置
+///
+/// ```ignore
+/// get_with(handler::get_user, handler::get_user_docs)
+///     .layer(axum::middleware::from_fn_with_state(
+///         ResponseCache::new(state.cache.clone(), USERS_CACHE),
+///         response_cache_middleware,
+///     ))
+/// ```
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    cache: Cache,
+
+    /// 缓存命名空间，服务层通过 `Cache::invalidate` 使其失效
+    namespace: String,
+
+    ttl: Duration,
+}
+
+impl ResponseCache {
+    /// 使用缓存的默认缓存时间
+    pub fn new(cache: Cache, namespace: impl Into<String>) -> Self {
+        let ttl = cache.ttl();
+        Self {
+            cache,
+            namespace: namespace.into(),
+            ttl,
+        }
+    }
+
+    /// 覆盖缓存时间
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+}
+
+/// 缓存的响应及其响应扩展（资源版本和分页链接由外层中间件输出为响应头）
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedResponse {
+    response: StoredResponse,
+    version: Option<String>,
+    page_links: Option<PageLinks>,
+}
+
+impl CachedResponse {
+    /// 保存 200 响应，其余响应或无法保存的响应原样返回
+    async fn capture(response: Response, max_body_size: usize) -> Result<Self, Response> {
+        if response.status() != StatusCode::OK || !fits_in(&response, max_body_size) {
+            return Err(response);
+        }
+        let (mut parts, body) = response.into_parts();
+        let body = match to_bytes(body, max_body_size).await {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::error!(error = %e, "failed to buffer response body for cache");
+                return Err(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+            }
+        };
+        Ok(Self {
+            response: StoredResponse {
+                status: parts.status.as_u16(),
+                headers: stored_headers(&parts.headers),
+                body: body.to_vec(),
+            },
+            version: parts
+                .extensions
+                .remove::<ResourceVersion>()
+                .map(|version| version.0),
+            page_links: parts.extensions.remove::<PageLinks>(),
+        })
+    }
+
+    fn into_response(self) -> Response {
+        let mut response = restore(self.response);
+        if let Some(version) = self.version {
+            response.extensions_mut().insert(ResourceVersion(version));
+        }
+        if let Some(page_links) = self.page_links {
+            response.extensions_mut().insert(page_links);
+        }
+        response
+    }
+}
+
+/// GET 响应缓存中间件
+///
+/// 缓存 key 由请求 URL（路径和查询参数）、当前用户、`Accept-Language` 和 `Accept` 组成，
+/// 只缓存大小已知且不超过上限的 200 响应。命中和未命中的响应都会带上
+/// `Cache-Control: max-age`（已登录用户的响应为 `private`，其余为 `public`），
+/// 处理器已设置 `Cache-Control` 时保持不变。
+///
+/// 需放在 `require_auth` 之内，使缓存 key 区分当前用户；
+/// 数据变化后由服务层调用 `Cache::invalidate` 使命名空间失效。
+pub async fn response_cache_middleware(
+    State(response_cache): State<ResponseCache>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !response_cache.cache.is_enabled() || request.method() != Method::GET {
+        return next.run(request).await;
+    }
+    let principal = request
+        .extensions()
+        .get::<CurrentUser>()
+        .map(|user| user.user_id);
+    let key = cache_key(&request, principal);
+
+    let max_body_size = response_cache.cache.max_body_size();
+    let result = response_cache
+        .cache
+        .get_or_load_with_ttl(
+            &response_cache.namespace,
+            &key,
+            response_cache.ttl,
+            || async move { CachedResponse::capture(next.run(request).await, max_body_size).await },
+        )
+        .await;
+    let mut response = match result {
+        Ok(cached) => cached.into_response(),
+        Err(response) => return response,
+    };
+
+    let visibility = if principal.is_some() {
+        "private"
+    } else {
+        "public"
+    };
+    let cache_control = format!("{visibility}, max-age={}", response_cache.ttl.as_secs());
+    if let Ok(value) = HeaderValue::from_str(&cache_control) {
+        response.headers_mut().entry(CACHE_CONTROL).or_insert(value);
+    }
+    response
+}
+
+/// 缓存 key：请求 URL、当前用户、`Accept-Language` 与 `Accept` 的 SHA-256
+fn cache_key(request: &Request, principal: Option<i32>) -> String {
+    // 优先使用按受信任代理还原的完整 URL，分页链接随主机和路径前缀变化
+    let url = match request.extensions().get::<RequestUrl>() {
+        Some(url) => url.href(),
+        None => request
+            .extensions()
+            .get::<OriginalUri>()
+            .map_or(request.uri(), |original| &original.0)
+            .to_string(),
+    };
+    let header = |name| {
+        request
+            .headers()
+            .get(name)
+            .map_or(&b""[..], HeaderValue::as_bytes)
+    };
+
+    let mut hasher = Sha256::new();
+    hasher.update(url);
+    hasher.update(b"\n");
+    hasher.update(principal.map(|id| id.to_string()).unwrap_or_default());
+    hasher.update(b"\n");
+    hasher.update(header(ACCEPT_LANGUAGE));
+    hasher.update(b"\n");
+    hasher.update(header(ACCEPT));
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
@@ -0,0 +1,39 @@
+use axum::extract::Request;
+use axum::http::header::{ACCEPT, VARY};
+use axum::http::{HeaderMap, HeaderValue};
+use axum::middleware::Next;
+use axum::response::Response;
+
+use crate::core::http::{BodyEncoding, with_encoding};
+
+/// 响应编码协商中间件
+///
+/// 按 `Accept` 头选择 JSON（默认）、MessagePack 或 CBOR，写入请求扩展，
+/// 并在处理期间设为当前编码，使 `ApiResponse` 按该编码序列化。
+/// 响应追加 `Vary: Accept`。需放在错误格式协商中间件之内。
+pub async fn encoding_middleware(mut request: Request, next: Next) -> Response {
+    let encoding = BodyEncoding::negotiate(
+        request
+            .headers()
+            .get(ACCEPT)
+            .and_then(|value| value.to_str().ok()),
+    );
+    request.extensions_mut().insert(encoding);
+
+    let mut response = with_encoding(encoding, next.run(request)).await;
+    append_vary(response.headers_mut(), "accept");
+    response
+}
+
+/// 追加 `Vary` 值，已包含同名请求头时不重复追加
+pub(crate) fn append_vary(headers: &mut HeaderMap, name: &'static str) {
+    let present = headers
+        .get_all(VARY)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|token| token.trim().eq_ignore_ascii_case(name));
+    if !present {
+        headers.append(VARY, HeaderValue::from_static(name));
+    }
+}
@@ -0,0 +1,57 @@
+use axum::extract::{Request, State};
+use axum::http::header::{ACCEPT, CONTENT_LENGTH, CONTENT_TYPE};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use super::encoding::append_vary;
+use crate::core::config::ResponseConfig;
+use crate::core::http::BodyEncoding;
+use crate::core::response::{ApiError, ErrorFormat, ProblemDetails};
+
+/// 错误响应格式协商中间件
+///
+/// 按 `Accept` 头（未明确偏好时按配置默认值）决定错误响应的格式：
+/// 选中 Problem Details 时，将 `ApiResponse::error` 生成的响应改写为
+/// `application/problem+json`，`instance` 取自 `x-request-id`，其余响应头保持不变。
+/// 以 MessagePack / CBOR 编码的错误响应不改写。
+/// 错误响应均追加 `Vary: Accept`。需放在请求 ID 中间件之内。
+pub async fn error_format_middleware(
+    State(config): State<ResponseConfig>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let format = ErrorFormat::negotiate(
+        request
+            .headers()
+            .get(ACCEPT)
+            .and_then(|value| value.to_str().ok()),
+        config.error_format,
+    );
+    let request_id = request
+        .headers()
+        .get("x-request-id")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+
+    let mut response = next.run(request).await;
+    let Some(error) = response.extensions().get::<ApiError>().cloned() else {
+        return response;
+    };
+    append_vary(response.headers_mut(), "accept");
+    // MessagePack / CBOR 编码的错误响应保持协商出的编码
+    if format == ErrorFormat::Google
+        || BodyEncoding::of_response(&response).is_some_and(|e| e != BodyEncoding::Json)
+    {
+        return response;
+    }
+
+    let (mut parts, _) = response.into_parts();
+    parts.headers.remove(CONTENT_TYPE);
+    parts.headers.remove(CONTENT_LENGTH);
+
+    let mut problem = ProblemDetails::from_api_error(&error, &config.problem_type_base, request_id)
+        .into_response();
+    problem.headers_mut().extend(parts.headers);
+    problem.extensions_mut().extend(parts.extensions);
+    problem
+}
@@ -0,0 +1,157 @@
+use axum::body::{Body, HttpBody, to_bytes};
+use axum::extract::Request;
+use axum::http::header::{CONTENT_LENGTH, CONTENT_TYPE, ETAG};
+use axum::http::{HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use serde_json::Value;
+
+use crate::core::http::{
+    BodyEncoding, IfNoneMatch, ResourceVersion, digest_etag, fields_param, version_etag,
+};
+
+/// 参与 ETag 计算的响应体上限，超过时不生成 ETag
+const MAX_ETAG_BODY: usize = 8 * 1024 * 1024;
+
+/// ETag 与条件请求中间件
+///
+/// 只处理 GET/HEAD 的 200 JSON、MessagePack、CBOR 响应：
+/// - 处理器已设置 `ETag` 头时原样使用；
+/// - 否则处理器用 `ApiResponse::with_etag` 提供了版本时由版本和协商出的语言生成，
+///   不存在时由序列化后的响应体摘要生成强 ETag；
+/// - ETag 同时写入 `ETag` 头和 `data.etag`（请求携带 `fields` 时不改动 `data`）；
+/// - MessagePack / CBOR 响应由响应体摘要生成 ETag，不写入 `data.etag`；
+/// - `If-None-Match` 命中时返回不带响应体的 304。
+///
+/// HEAD 请求按 GET 执行处理器后丢弃响应体，保证两者的 ETag 一致。
+/// 需放在响应压缩之内，使 ETag 基于未压缩的表示计算。
+pub async fn etag_middleware(mut request: Request, next: Next) -> Response {
+    let method = request.method().clone();
+    if method != Method::GET && method != Method::HEAD {
+        return next.run(request).await;
+    }
+    let if_none_match = IfNoneMatch::from_headers(request.headers());
+    let fields = fields_param(request.uri());
+    if method == Method::HEAD {
+        *request.method_mut() = Method::GET;
+    }
+
+    let response = next.run(request).await;
+    if response.status() != StatusCode::OK {
+        return strip_body(response, &method);
+    }
+
+    let (mut response, etag) = match response.headers().get(ETAG).cloned() {
+        Some(etag) => (response, etag),
+        None if fits_in_memory(&response) => match BodyEncoding::of_response(&response) {
+            Some(BodyEncoding::Json) => match with_etag(response, fields.as_deref()).await {
+                Ok(tagged) => tagged,
+                Err(response) => return response,
+            },
+            Some(_) => match with_digest_etag(response).await {
+                Ok(tagged) => tagged,
+                Err(response) => return response,
+            },
+            None => return strip_body(response, &method),
+        },
+        None => return strip_body(response, &method),
+    };
+
+    let matched = etag.to_str().is_ok_and(|etag| if_none_match.matches(etag));
+    response.headers_mut().insert(ETAG, etag);
+    if matched {
+        return not_modified(response);
+    }
+    strip_body(response, &method)
+}
+
+/// 响应体大小已知且不超过上限（流式或过大的响应体不生成 ETag）
+fn fits_in_memory(response: &Response) -> bool {
+    response
+        .body()
+        .size_hint()
+        .upper()
+        .is_some_and(|upper| upper <= MAX_ETAG_BODY as u64)
+}
+
+/// 读取 JSON 响应体，计算 ETag 并写回 `data.etag`
+async fn with_etag(
+    response: Response,
+    fields: Option<&str>,
+) -> Result<(Response, HeaderValue), Response> {
+    let (mut parts, body) = response.into_parts();
+    let version = parts
+        .extensions
+        .remove::<ResourceVersion>()
+        .map(|version| version.0);
+    let bytes = match to_bytes(body, MAX_ETAG_BODY).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::error!(error = %e, "failed to buffer response body for etag");
+            return Err(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+        }
+    };
+
+    let mut json = serde_json::from_slice::<Value>(&bytes).ok();
+    let data = json
+        .as_mut()
+        .and_then(|json| json.get_mut("data"))
+        .and_then(Value::as_object_mut);
+    let etag = match version {
+        Some(version) => version_etag(&version, fields),
+        None => digest_etag(&bytes),
+    };
+
+    let body = match data {
+        // fields 已裁剪 data，不再添加未选择的字段
+        Some(data) if fields.is_none() => {
+            data.insert("etag".to_string(), Value::String(etag.clone()));
+            parts.headers.remove(CONTENT_LENGTH);
+            Body::from(serde_json::to_vec(&json).unwrap_or_else(|_| bytes.to_vec()))
+        }
+        _ => Body::from(bytes),
+    };
+
+    let Ok(etag) = HeaderValue::from_str(&etag) else {
+        return Err(Response::from_parts(parts, body));
+    };
+    Ok((Response::from_parts(parts, body), etag))
+}
+
+/// 读取 MessagePack / CBOR 响应体，由摘要生成 ETag，响应体不做改动
+///
+/// 不使用资源版本，使同一资源不同编码的表示具有不同的强 ETag。
+async fn with_digest_etag(response: Response) -> Result<(Response, HeaderValue), Response> {
+    let (mut parts, body) = response.into_parts();
+    parts.extensions.remove::<ResourceVersion>();
+    let bytes = match to_bytes(body, MAX_ETAG_BODY).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::error!(error = %e, "failed to buffer response body for etag");
+            return Err(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+        }
+    };
+    let etag = HeaderValue::from_str(&digest_etag(&bytes));
+    let response = Response::from_parts(parts, Body::from(bytes));
+    match etag {
+        Ok(etag) => Ok((response, etag)),
+        Err(_) => Err(response),
+    }
+}
+
+/// 304 响应：保留 `ETag`、`Vary` 等元数据头，去掉响应体和表示相关的头
+fn not_modified(response: Response) -> Response {
+    let (mut parts, _) = response.into_parts();
+    parts.status = StatusCode::NOT_MODIFIED;
+    parts.headers.remove(CONTENT_TYPE);
+    parts.headers.remove(CONTENT_LENGTH);
+    Response::from_parts(parts, Body::empty())
+}
+
+fn strip_body(response: Response, method: &Method) -> Response {
+    if method != Method::HEAD {
+        return response;
+    }
+    let (parts, _) = response.into_parts();
+    Response::from_parts(parts, Body::empty())
+}
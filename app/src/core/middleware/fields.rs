@@ -0,0 +1,22 @@
+use axum::extract::Request;
+use axum::middleware::Next;
+use axum::response::Response;
+
+use crate::core::http::fields_param;
+use crate::core::response::{FieldSelection, invalid_fields_response, with_fields};
+
+/// 部分响应中间件
+///
+/// 解析查询字符串中的 `fields` 参数，并在处理期间设为当前字段选择，
+/// 使 `ApiResponse` 按 schema 校验所选字段并裁剪成功响应的 `data`。
+/// 参数语法错误时直接返回 400，不执行处理器；未携带参数时不做任何处理。
+pub async fn fields_middleware(request: Request, next: Next) -> Response {
+    let Some(raw) = fields_param(request.uri()) else {
+        return next.run(request).await;
+    };
+
+    match FieldSelection::parse(&raw) {
+        Ok(fields) => with_fields(fields, next.run(request)).await,
+        Err(error) => invalid_fields_response(&error),
+    }
+}
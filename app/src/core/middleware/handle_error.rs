@@ -0,0 +1,74 @@
+use std::error::Error;
+
+use axum::BoxError;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use tower::load_shed::error::Overloaded;
+use tower::timeout::error::Elapsed;
+use tower_governor::GovernorError;
+
+use crate::core::handle_rate_limit_error;
+use crate::core::response::{ApiError, ApiResponse, Domain, ErrorDetail, Reason};
+
+/// 中间件错误映射
+///
+/// 作为 `HandleErrorLayer` 的处理函数，将 tower 中间件产生的错误转换为统一格式的错误响应：
+///
+/// - 限流（`GovernorError`）→ 429 `RATE_LIMIT_EXCEEDED`，保留限流响应头
+/// - 超时（`Elapsed`）→ 504 `TIMEOUT`
+/// - 缓冲区已满（`Overloaded`）→ 503 `SERVICE_UNAVAILABLE`
+/// - 其他错误 → 500，详情只写日志
+///
+/// 会沿错误的 `source` 链查找，因此被 `Buffer` 等中间件包装过的错误同样能识别。
+pub async fn handle_middleware_error(err: BoxError) -> Response {
+    if let Some(governor) = find_source::<GovernorError>(&*err) {
+        return handle_rate_limit_error(governor.clone());
+    }
+    if find_source::<Elapsed>(&*err).is_some() {
+        tracing::warn!("request timed out");
+        return timeout_response();
+    }
+    if find_source::<Overloaded>(&*err).is_some() {
+        tracing::warn!("service overloaded");
+        return error_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            Reason::ServiceUnavailable,
+            "服务繁忙，请稍后重试",
+        );
+    }
+
+    tracing::error!(error = %err, "unhandled middleware error");
+    ApiResponse::error(ApiError::new(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "Internal server error",
+    ))
+    .into_response()
+}
+
+/// 请求超时的 504 响应（`TIMEOUT`），日志由调用方记录
+pub fn timeout_response() -> Response {
+    error_response(StatusCode::GATEWAY_TIMEOUT, Reason::Timeout, "请求处理超时")
+}
+
+/// 沿 `source` 链查找指定类型的错误
+fn find_source<'a, T: Error + 'static>(err: &'a (dyn Error + 'static)) -> Option<&'a T> {
+    let mut current = Some(err);
+    while let Some(error) = current {
+        if let Some(found) = error.downcast_ref::<T>() {
+            return Some(found);
+        }
+        current = error.source();
+    }
+    None
+}
+
+fn error_response(status: StatusCode, reason: Reason, message: &str) -> Response {
+    ApiResponse::error(
+        ApiError::new(status, message).with_detail(ErrorDetail::with_message(
+            Domain::GLOBAL,
+            reason,
+            message,
+        )),
+    )
+    .into_response()
+}
@@ -0,0 +1,348 @@
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::body::{Body, Bytes, HttpBody, to_bytes};
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::header::{AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE, DATE};
+use axum::http::{HeaderMap, HeaderName, HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use http_body_util::{BodyExt, Full, Limited};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::core::config::{IdempotencyConfig, ServerConfig};
+use crate::core::http::{RoutePrefixMap, TrustedProxies};
+use crate::error::{AppError, IdempotencyError, RejectionError};
+use crate::shared::jwt::JwtService;
+use crate::shared::{
+    IdempotencyRecord, IdempotencyStore, MemoryIdempotencyStore, RedisIdempotencyStore,
+    StoredResponse,
+};
+use crate::state::AppState;
+
+/// 幂等 key 请求头
+pub const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+
+/// 重放的响应携带的标记头
+pub const IDEMPOTENT_REPLAYED: HeaderName = HeaderName::from_static("idempotent-replayed");
+
+/// key 的最大长度
+const MAX_KEY_LEN: usize = 255;
+
+/// 不随响应保存的头：每次请求各不相同，重放时由外层中间件重新生成
+const VOLATILE_HEADERS: [HeaderName; 3] = [
+    HeaderName::from_static("x-request-id"),
+    CONTENT_LENGTH,
+    DATE,
+];
+
+/// 幂等请求设置：记录存储、请求方的识别方式与保存时间
+#[derive(Debug, Clone)]
+pub struct Idempotency {
+    store: Arc<dyn IdempotencyStore>,
+
+    /// 用于识别已登录用户，未设置时只按客户端地址区分
+    jwt_service: Option<JwtService>,
+
+    trusted_proxies: TrustedProxies,
+
+    enabled: bool,
+    ttl: Duration,
+    lock_ttl: Duration,
+    max_body_size: RoutePrefixMap<usize>,
+}
+
+impl Idempotency {
+    pub fn new(store: Arc<dyn IdempotencyStore>, config: &IdempotencyConfig) -> Self {
+        Self {
+            store,
+            jwt_service: None,
+            trusted_proxies: TrustedProxies::default(),
+            enabled: config.enabled,
+            ttl: Duration::from_secs(config.ttl),
+            lock_ttl: Duration::from_secs(config.lock_ttl),
+            max_body_size: RoutePrefixMap::new(config.max_body_size),
+        }
+    }
+
+    /// 携带有效访问令牌的请求按用户区分 key，而不是按客户端地址
+    pub fn with_jwt_service(mut self, jwt_service: JwtService) -> Self {
+        self.jwt_service = Some(jwt_service);
+        self
+    }
+
+    /// 保证该路由携带 `Idempotency-Key` 的请求体上限不低于 `size` 字节（如批量导入）
+    ///
+    /// 上限只会被提高：全局 `max_body_size` 更大时保持不变。
+    pub fn with_route_body_size(mut self, prefix: impl Into<String>, size: usize) -> Self {
+        let prefix = prefix.into();
+        if *self.max_body_size.get(&prefix) < size {
+            self.max_body_size.insert(prefix, size);
+        }
+        self
+    }
+
+    /// 经受信任代理转发的请求按 `X-Forwarded-For` 还原客户端地址
+    pub fn with_trusted_proxies(mut self, trusted_proxies: TrustedProxies) -> Self {
+        self.trusted_proxies = trusted_proxies;
+        self
+    }
+
+    /// 从应用状态和配置构建：配置了 Redis 时记录保存在 Redis，否则保存在进程内存
+    pub fn from_state(
+        state: &AppState,
+        server: &ServerConfig,
+        config: &IdempotencyConfig,
+    ) -> Result<Self, String> {
+        let store: Arc<dyn IdempotencyStore> = match &state.redis {
+            Some(pool) => Arc::new(RedisIdempotencyStore::new(pool.clone())),
+            None => Arc::new(MemoryIdempotencyStore::new()),
+        };
+        Ok(Self::new(store, config)
+            .with_jwt_service(state.jwt_service.clone())
+            .with_trusted_proxies(TrustedProxies::parse(&server.trusted_proxies)?))
+    }
+
+    /// 请求方标识：已登录用户为用户 ID，否则为客户端地址
+    fn scope(&self, request: &Request) -> String {
+        let user_id = self.jwt_service.as_ref().and_then(|jwt| {
+            let header = request.headers().get(AUTHORIZATION)?.to_str().ok()?;
+            jwt.extract_user_id(header.strip_prefix("Bearer ")?).ok()
+        });
+        if let Some(user_id) = user_id {
+            return format!("user:{user_id}");
+        }
+        let peer = request
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip());
+        match peer {
+            Some(peer) => {
+                let ip = self.trusted_proxies.client_ip(request.headers(), peer);
+                format!("ip:{}", ip.to_canonical())
+            }
+            None => "ip:unknown".to_string(),
+        }
+    }
+}
+
+/// 幂等请求中间件
+///
+/// 只处理携带 `Idempotency-Key` 的 POST 请求，key 按请求方（已登录用户或客户端地址）区分：
+/// - 首个请求成功（2xx）时保存状态码、响应头和响应体，保存期内同一 key 的重试直接重放，
+///   重放的响应带有 `Idempotent-Replayed: true`；
+/// - 同一 key 用于方法、路径或请求体不同的请求时返回 409（`IDEMPOTENCY_KEY_REUSED`）；
+/// - 首个请求仍在处理中时返回 409（`REQUEST_IN_PROGRESS`）并设置 `Retry-After`；
+/// - 首个请求失败（非 2xx）、响应体无法保存或请求被取消时释放 key，重试会重新执行；
+/// - 请求体超过 `max_body_size`（可按路由放宽）时返回 413，不会在没有幂等保护的情况下执行。
+///
+/// 需放在响应压缩之内，保存的响应体不依赖 `Accept-Encoding`。
+pub async fn idempotency_middleware(
+    State(idempotency): State<Idempotency>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !idempotency.enabled || request.method() != Method::POST {
+        return next.run(request).await;
+    }
+    let Some(key) = request.headers().get(&IDEMPOTENCY_KEY) else {
+        return next.run(request).await;
+    };
+    let Some(key) = valid_key(key) else {
+        return IdempotencyError::InvalidKey.into_response();
+    };
+    let key = format!("{}:{key}", idempotency.scope(&request));
+
+    // 读取请求体计算指纹，之后原样交给处理器
+    let max_body_size = *idempotency.max_body_size.get(request.uri().path());
+    let (parts, body) = request.into_parts();
+    let Some((bytes, body)) = buffer(body, max_body_size).await else {
+        return RejectionError::Body {
+            status: StatusCode::PAYLOAD_TOO_LARGE,
+            message: format!(
+                "携带 Idempotency-Key 的请求体不能超过 {} 字节",
+                max_body_size
+            ),
+        }
+        .into_response();
+    };
+    let fingerprint = fingerprint(&parts.method, &parts.uri, &parts.headers, &bytes);
+
+    let owned = IdempotencyRecord::InFlight {
+        fingerprint: fingerprint.clone(),
+        owner: Uuid::new_v4().to_string(),
+    };
+    match idempotency
+        .store
+        .acquire(&key, &owned, idempotency.lock_ttl)
+        .await
+    {
+        Ok(None) => {}
+        Ok(Some(existing)) if existing.fingerprint() != fingerprint => {
+            return IdempotencyError::KeyReused.into_response();
+        }
+        Ok(Some(IdempotencyRecord::InFlight { .. })) => {
+            return IdempotencyError::InProgress.into_response();
+        }
+        Ok(Some(IdempotencyRecord::Completed { response, .. })) => return replay(response),
+        Err(e) => return AppError::from(e).into_response(),
+    }
+
+    let mut lease = Lease {
+        store: idempotency.store.clone(),
+        key,
+        owned,
+        held: true,
+    };
+    let response = next.run(Request::from_parts(parts, body)).await;
+    if !response.status().is_success() || !fits_in(&response, max_body_size) {
+        // 立即释放，客户端收到响应后马上重试不会遇到处理中的占用
+        lease.release().await;
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = match to_bytes(body, max_body_size).await {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::error!(error = %e, "failed to buffer response body for idempotency");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let record = IdempotencyRecord::Completed {
+        fingerprint,
+        response: StoredResponse {
+            status: parts.status.as_u16(),
+            headers: stored_headers(&parts.headers),
+            body: body.to_vec(),
+        },
+    };
+    match idempotency
+        .store
+        .complete(&lease.key, &lease.owned, &record, idempotency.ttl)
+        .await
+    {
+        Ok(()) => lease.held = false,
+        // 请求已执行成功，保存失败只影响之后的重放，不改变本次响应
+        Err(e) => tracing::warn!(error = %e, "failed to store idempotent response"),
+    }
+    Response::from_parts(parts, Body::from(body))
+}
+
+/// 读取不超过 `limit` 字节的请求体，返回其内容和交给处理器的请求体（保留 trailers）
+async fn buffer(body: Body, limit: usize) -> Option<(Bytes, Body)> {
+    let collected = Limited::new(body, limit).collect().await.ok()?;
+    let trailers = collected.trailers().cloned();
+    let bytes = collected.to_bytes();
+    let body =
+        Full::new(bytes.clone()).with_trailers(async move { trailers.map(Ok::<_, Infallible>) });
+    Some((bytes, Body::new(body)))
+}
+
+/// 1 到 255 个可见 ASCII 字符
+fn valid_key(value: &HeaderValue) -> Option<&str> {
+    let key = value.to_str().ok()?.trim();
+    let valid = !key.is_empty()
+        && key.len() <= MAX_KEY_LEN
+        && key.bytes().all(|byte| byte.is_ascii_graphic());
+    valid.then_some(key)
+}
+
+/// 请求指纹：方法、路径与查询、`Content-Type` 和请求体的 SHA-256
+fn fingerprint(method: &Method, uri: &axum::http::Uri, headers: &HeaderMap, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_str());
+    hasher.update(b"\n");
+    hasher.update(uri.path_and_query().map_or("", |path| path.as_str()));
+    hasher.update(b"\n");
+    hasher.update(
+        headers
+            .get(CONTENT_TYPE)
+            .map_or(&b""[..], HeaderValue::as_bytes),
+    );
+    hasher.update(b"\n");
+    hasher.update(body);
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// 响应体大小已知且不超过上限（流式或过大的响应体不保存）
+pub(crate) fn fits_in(response: &Response, max_body_size: usize) -> bool {
+    response
+        .body()
+        .size_hint()
+        .upper()
+        .is_some_and(|upper| upper <= max_body_size as u64)
+}
+
+/// 需要保存的响应头（不含随请求变化的头）
+pub(crate) fn stored_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .filter(|(name, _)| !VOLATILE_HEADERS.contains(name))
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+        .collect()
+}
+
+fn replay(stored: StoredResponse) -> Response {
+    let mut response = restore(stored);
+    response
+        .headers_mut()
+        .insert(IDEMPOTENT_REPLAYED, HeaderValue::from_static("true"));
+    response
+}
+
+/// 由保存的状态码、响应头和响应体还原响应
+pub(crate) fn restore(stored: StoredResponse) -> Response {
+    let mut response = Response::new(Body::from(stored.body));
+    *response.status_mut() = StatusCode::from_u16(stored.status).unwrap_or(StatusCode::OK);
+    let headers = response.headers_mut();
+    for (name, value) in &stored.headers {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(value),
+        ) {
+            headers.append(name, value);
+        }
+    }
+    response
+}
+
+/// 处理中占用，未写入完成记录就结束（失败、无法保存或请求被取消）时释放
+struct Lease {
+    store: Arc<dyn IdempotencyStore>,
+    key: String,
+    owned: IdempotencyRecord,
+    held: bool,
+}
+
+impl Lease {
+    async fn release(&mut self) {
+        self.held = false;
+        if let Err(e) = self.store.release(&self.key, &self.owned).await {
+            tracing::warn!(error = %e, "failed to release idempotency key");
+        }
+    }
+}
+
+impl Drop for Lease {
+    fn drop(&mut self) {
+        if !self.held {
+            return;
+        }
+        let store = self.store.clone();
+        let key = std::mem::take(&mut self.key);
+        let owned = self.owned.clone();
+        tokio::spawn(async move {
+            if let Err(e) = store.release(&key, &owned).await {
+                tracing::warn!(error = %e, "failed to release idempotency key");
+            }
+        });
+    }
+}
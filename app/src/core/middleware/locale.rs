@@ -0,0 +1,37 @@
+use axum::extract::{Request, State};
+use axum::http::HeaderValue;
+use axum::http::header::{ACCEPT_LANGUAGE, CONTENT_LANGUAGE, VARY};
+use axum::middleware::Next;
+use axum::response::Response;
+
+use crate::core::config::ResponseConfig;
+use crate::core::i18n::{Locale, with_locale};
+
+/// 消息语言协商中间件
+///
+/// 按 `Accept-Language` 头（未携带或不支持时按配置默认值）选择语言，
+/// 写入请求扩展（处理器可用 `Locale` 提取器读取），并在处理期间设为当前语言，
+/// 使 `ApiResponse` 输出本地化的错误消息和 `data.lang`。
+/// 响应追加 `Content-Language` 和 `Vary: Accept-Language`。需放在错误格式协商中间件之内。
+pub async fn locale_middleware(
+    State(config): State<ResponseConfig>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let locale = Locale::negotiate(
+        request
+            .headers()
+            .get(ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok()),
+        config.default_locale,
+    );
+    request.extensions_mut().insert(locale);
+
+    let mut response = with_locale(locale, next.run(request)).await;
+    let headers = response.headers_mut();
+    if !headers.contains_key(CONTENT_LANGUAGE) {
+        headers.insert(CONTENT_LANGUAGE, HeaderValue::from_static(locale.tag()));
+    }
+    headers.append(VARY, HeaderValue::from_static("accept-language"));
+    response
+}
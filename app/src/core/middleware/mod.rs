@@ -2,10 +2,46 @@
 //!
 //! 提供 HTTP 请求的拦截和处理功能，包括认证、请求追踪等。
 
-/// JWT 认证中间件
+/// JWT 认证和管理员权限中间件
 pub mod auth;
+/// 请求体大小限制与请求体解压中间件
+pub mod body_limit;
+/// 路由级 GET 响应缓存中间件
+pub mod cache;
+/// 响应编码协商中间件（JSON / MessagePack / CBOR）
+pub mod encoding;
+/// 错误响应格式协商中间件（Google JSON / RFC 9457）
+pub mod error_format;
+/// ETag 生成与条件请求（If-None-Match）中间件
+pub mod etag;
+/// 部分响应中间件（`fields` 查询参数）
+pub mod fields;
+/// 中间件错误（限流、超时、过载）映射为统一错误响应
+pub mod handle_error;
+/// 幂等请求中间件（`Idempotency-Key`）
+pub mod idempotency;
+/// 消息语言协商中间件（Accept-Language）
+pub mod locale;
+/// 请求原始 URL 还原与分页响应头中间件
+pub mod pagination;
 /// 请求 ID 生成和追踪中间件
 pub mod request_id;
+/// 安全响应头中间件（HSTS、CSP 等）
+pub mod security_headers;
+/// 请求超时中间件（全局超时和按路由覆盖）
+pub mod timeout;
 
 pub use auth::*;
+pub use body_limit::*;
+pub use cache::*;
+pub use encoding::*;
+pub use error_format::*;
+pub use etag::*;
+pub use fields::*;
+pub use handle_error::*;
+pub use idempotency::*;
+pub use locale::*;
+pub use pagination::*;
 pub use request_id::*;
+pub use security_headers::*;
+pub use timeout::*;
@@ -0,0 +1,70 @@
+use std::net::SocketAddr;
+
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::HeaderValue;
+use axum::http::header::LINK;
+use axum::middleware::Next;
+use axum::response::Response;
+
+use crate::core::config::{ResponseConfig, ServerConfig};
+use crate::core::http::{PageLinks, RequestUrl, TrustedProxies};
+
+/// 分页链接设置：受信任代理与是否输出分页响应头
+#[derive(Debug, Clone, Default)]
+pub struct PaginationLinks {
+    trusted_proxies: TrustedProxies,
+
+    /// 是否输出 `Link` 与 `X-Total-Count` 响应头
+    headers: bool,
+}
+
+impl PaginationLinks {
+    pub fn new(trusted_proxies: TrustedProxies, headers: bool) -> Self {
+        Self {
+            trusted_proxies,
+            headers,
+        }
+    }
+
+    /// 从配置构建（`server.trusted_proxies` 与 `response.pagination_headers`）
+    pub fn from_config(server: &ServerConfig, response: &ResponseConfig) -> Result<Self, String> {
+        Ok(Self::new(
+            TrustedProxies::parse(&server.trusted_proxies)?,
+            response.pagination_headers,
+        ))
+    }
+}
+
+/// 请求原始 URL 与分页响应头中间件
+///
+/// 按直接对端地址（`ConnectInfo`）判断是否采信 `X-Forwarded-*`，将还原出的 `RequestUrl`
+/// 写入请求扩展供处理器提取；需放在 `nest` 之外，以便取得完整路径。
+/// 开启分页响应头时，为带分页链接的列表响应追加 RFC 8288 `Link` 与 `X-Total-Count`。
+pub async fn pagination_links_middleware(
+    State(links): State<PaginationLinks>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    let url = RequestUrl::from_request(
+        request.headers(),
+        request.uri(),
+        peer,
+        &links.trusted_proxies,
+    );
+    request.extensions_mut().insert(url);
+
+    let mut response = next.run(request).await;
+    if links.headers
+        && let Some(page_links) = response.extensions_mut().remove::<PageLinks>()
+        && let Ok(link) = HeaderValue::from_str(&page_links.link)
+    {
+        let headers = response.headers_mut();
+        headers.insert(LINK, link);
+        headers.insert("x-total-count", HeaderValue::from(page_links.total));
+    }
+    response
+}
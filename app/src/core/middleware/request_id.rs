@@ -2,22 +2,40 @@ use axum::extract::Request;
 use axum::http::HeaderValue;
 use axum::middleware::Next;
 use axum::response::Response;
-use uuid::Uuid;
+use tracing::Instrument;
 
+use crate::core::http::{RequestId, TraceContext, X_REQUEST_ID, with_request_id};
+
+/// 请求 ID 与追踪上下文中间件
+///
+/// 网关传入的 `X-Request-ID` 格式合法时沿用，否则生成新的 UUID，并覆盖请求头、写入响应头；
+/// 合法的 `traceparent` / `tracestate` 解析为 `TraceContext`。两者写入请求扩展，
+/// 请求 ID 和追踪 ID 记录在包裹后续处理的 tracing span 中，
+/// 请求 ID 同时在处理期间设为当前请求 ID，使错误响应体带上 `request_id`。
 pub async fn request_id_middleware(mut request: Request, next: Next) -> Response {
-    let request_id = Uuid::new_v4().to_string();
+    let request_id = RequestId::from_headers(request.headers());
+    let trace_context = TraceContext::from_headers(request.headers());
 
-    // 将请求ID添加到请求头中
-    if let Ok(header_value) = HeaderValue::from_str(&request_id) {
-        request.headers_mut().insert("x-request-id", header_value);
+    let header_value = HeaderValue::from_str(request_id.as_str()).ok();
+    if let Some(ref value) = header_value {
+        request.headers_mut().insert(X_REQUEST_ID, value.clone());
+    }
+    let span = tracing::info_span!(
+        "request_id",
+        request_id = %request_id,
+        trace_id = trace_context.as_ref().map(|context| context.trace_id.as_str()),
+    );
+    request.extensions_mut().insert(request_id.clone());
+    if let Some(trace_context) = trace_context {
+        request.extensions_mut().insert(trace_context);
     }
 
-    let mut response = next.run(request).await;
+    let mut response = with_request_id(request_id, next.run(request))
+        .instrument(span)
+        .await;
 
-    // 将请求ID添加到响应头中
-    if let Ok(header_value) = HeaderValue::from_str(&request_id) {
-        response.headers_mut().insert("x-request-id", header_value);
+    if let Some(value) = header_value {
+        response.headers_mut().insert(X_REQUEST_ID, value);
     }
-
     response
 }
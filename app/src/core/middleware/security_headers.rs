@@ -0,0 +1,98 @@
+use axum::extract::{Request, State};
+use axum::http::header::{
+    CONTENT_SECURITY_POLICY, REFERRER_POLICY, STRICT_TRANSPORT_SECURITY, X_CONTENT_TYPE_OPTIONS,
+    X_FRAME_OPTIONS,
+};
+use axum::http::{HeaderName, HeaderValue};
+use axum::middleware::Next;
+use axum::response::Response;
+
+use crate::core::config::SecurityHeadersConfig;
+use crate::core::http::RoutePrefixMap;
+
+/// `Permissions-Policy` 响应头
+pub const PERMISSIONS_POLICY: HeaderName = HeaderName::from_static("permissions-policy");
+
+/// 安全响应头设置：固定的响应头加按路径前缀覆盖的内容安全策略
+#[derive(Debug, Clone, Default)]
+pub struct SecurityHeaders {
+    headers: Vec<(HeaderName, HeaderValue)>,
+
+    /// 内容安全策略，None 表示不输出
+    csp: RoutePrefixMap<Option<HeaderValue>>,
+}
+
+impl SecurityHeaders {
+    /// 从配置构建，未启用时不添加任何响应头
+    pub fn from_config(config: &SecurityHeadersConfig) -> Result<Self, String> {
+        if !config.enabled {
+            return Ok(Self::default());
+        }
+        let mut headers = vec![(X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff"))];
+        let optional = [
+            (STRICT_TRANSPORT_SECURITY, config.hsts().unwrap_or_default()),
+            (X_FRAME_OPTIONS, config.frame_options.to_ascii_uppercase()),
+            (REFERRER_POLICY, config.referrer_policy.clone()),
+            (PERMISSIONS_POLICY, config.permissions_policy.clone()),
+        ];
+        for (name, value) in optional {
+            if let Some(value) = header_value(&value)? {
+                headers.push((name, value));
+            }
+        }
+
+        let mut security_headers = Self {
+            headers,
+            csp: RoutePrefixMap::new(header_value(&config.content_security_policy)?),
+        };
+        for (prefix, csp) in &config.route_csp {
+            security_headers = security_headers.with_route_csp(prefix.clone(), csp)?;
+        }
+        Ok(security_headers)
+    }
+
+    /// 覆盖指定路径前缀下所有路由的内容安全策略，空字符串表示不输出
+    pub fn with_route_csp(mut self, prefix: impl Into<String>, csp: &str) -> Result<Self, String> {
+        self.csp.insert(prefix, header_value(csp)?);
+        Ok(self)
+    }
+
+    /// 请求路径适用的内容安全策略
+    pub fn csp_for(&self, path: &str) -> Option<&HeaderValue> {
+        self.csp.get(path).as_ref()
+    }
+}
+
+/// 空字符串表示不输出
+fn header_value(value: &str) -> Result<Option<HeaderValue>, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    HeaderValue::from_str(value)
+        .map(Some)
+        .map_err(|_| format!("无效的响应头值：{}", value))
+}
+
+/// 安全响应头中间件
+///
+/// 为所有响应（包括错误、404 和 CORS 预检）添加 HSTS、`X-Content-Type-Options`、
+/// `X-Frame-Options`、`Referrer-Policy`、`Permissions-Policy` 和 `Content-Security-Policy`。
+/// 处理器已设置的同名响应头保持不变，HTML 页面可以据此使用自己的内容安全策略。
+pub async fn security_headers_middleware(
+    State(security_headers): State<SecurityHeaders>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let csp = security_headers.csp_for(request.uri().path()).cloned();
+    let mut response = next.run(request).await;
+
+    let headers = response.headers_mut();
+    for (name, value) in &security_headers.headers {
+        headers.entry(name).or_insert_with(|| value.clone());
+    }
+    if let Some(csp) = csp {
+        headers.entry(CONTENT_SECURITY_POLICY).or_insert(csp);
+    }
+    response
+}
@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+
+use super::timeout_response;
+use crate::core::config::ServerConfig;
+use crate::core::http::RoutePrefixMap;
+
+/// 请求超时设置：全局超时加按路径前缀的覆盖
+#[derive(Debug, Clone)]
+pub struct RequestTimeout {
+    routes: RoutePrefixMap<Duration>,
+}
+
+impl RequestTimeout {
+    pub fn new(default: Duration) -> Self {
+        Self {
+            routes: RoutePrefixMap::new(default),
+        }
+    }
+
+    /// 从服务器配置构建（`timeout` 与 `route_timeouts`）
+    pub fn from_config(config: &ServerConfig) -> Self {
+        config.route_timeouts.iter().fold(
+            Self::new(config.request_timeout()),
+            |timeout, (prefix, secs)| {
+                timeout.with_route(prefix.clone(), Duration::from_secs(*secs))
+            },
+        )
+    }
+
+    /// 覆盖指定路径前缀下所有路由的超时
+    pub fn with_route(mut self, prefix: impl Into<String>, timeout: Duration) -> Self {
+        self.routes.insert(prefix, timeout);
+        self
+    }
+
+    /// 请求路径适用的超时
+    pub fn timeout_for(&self, path: &str) -> Duration {
+        *self.routes.get(path)
+    }
+}
+
+/// 请求超时中间件
+///
+/// 处理器在限定时间内没有返回响应时丢弃其 future 并返回 504（`TIMEOUT`），
+/// 丢弃会一并取消其中未完成的数据库查询和连接获取；数据库会话的 `statement_timeout`
+/// 另由连接池按最长超时设置，防止服务端继续执行已被放弃的语句。
+/// 超时只约束响应头返回之前的处理时间，流式响应体的传输不受影响。
+pub async fn timeout_middleware(
+    State(timeout): State<RequestTimeout>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let duration = timeout.timeout_for(request.uri().path());
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+
+    match tokio::time::timeout(duration, next.run(request)).await {
+        Ok(response) => response,
+        Err(_) => {
+            tracing::warn!(%method, %path, timeout = ?duration, "request timed out");
+            timeout_response()
+        }
+    }
+}
@@ -6,6 +6,7 @@ mod bootstrap;
 pub mod config;
 mod cors;
 mod http;
+pub mod i18n;
 mod logging;
 pub mod middleware;
 mod rate_limit;
@@ -20,10 +21,16 @@ pub use bootstrap::{
 pub use config::AppConfig;
 /// CORS 跨域配置构建函数
 pub use cors::build_cors_layer;
-/// 分页请求解析和约束
+/// 分页请求解析和约束、分页链接、统一错误格式的请求提取器、条件请求、请求体与响应体编码、请求 ID 与追踪上下文、按路径前缀覆盖的设置
 pub use http::{
-    DEFAULT_PAGE, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE, MIN_PAGE_SIZE, Pagination, PaginationQuery,
+    BodyEncoding, CBOR, Conditional, DEFAULT_PAGE, DEFAULT_PAGE_SIZE, DecodeError, IfMatch,
+    IfNoneMatch, Json, MAX_PAGE_SIZE, MIN_PAGE_SIZE, MSGPACK, PAGE_PARAM, PAGE_PLACEHOLDER,
+    Pagination, PaginationQuery, Path, Query, RequestId, RequestUrl, RoutePrefixMap, TraceContext,
+    TrustedProxies, ValidatedJson, ValidatedPath, ValidatedQuery, X_REQUEST_ID, current_encoding,
+    current_request_id, digest_etag, version_etag, version_tag, with_encoding, with_request_id,
 };
+/// 错误消息本地化
+pub use i18n::{Locale, MessageCatalog};
 /// 旧日志文件清理函数
 pub use logging::cleanup_old_logs;
 /// 速率限制错误处理函数
@@ -1,46 +1,59 @@
-use axum::http::StatusCode;
+use axum::http::header::RETRY_AFTER;
+use axum::http::{HeaderValue, StatusCode};
 use axum::response::{IntoResponse, Response};
 use tower_governor::GovernorError;
 
+use crate::core::response::{ApiError, ApiResponse, Domain, ErrorDetail, Reason};
+
 /// 速率限制错误处理
 ///
-/// 处理 tower_governor 的限流错误，返回适当的 HTTP 响应
-pub async fn handle_rate_limit_error(err: GovernorError) -> Response {
+/// 作为 `GovernorLayer::error_handler` 使用，将 tower_governor 的限流错误转换为统一格式的错误响应：
+/// 超出限制返回 429（`RATE_LIMIT_EXCEEDED`），保留 `x-ratelimit-*` 响应头并设置 `Retry-After`。
+pub fn handle_rate_limit_error(err: GovernorError) -> Response {
     match err {
-        GovernorError::TooManyRequests { headers, .. } => {
-            let mut response = (
-                StatusCode::TOO_MANY_REQUESTS,
-                "请求过于频繁，请稍后重试".to_string(),
+        GovernorError::TooManyRequests { wait_time, headers } => {
+            let message = format!("请求过于频繁，请在 {} 秒后重试", wait_time);
+            let mut response = ApiResponse::error(
+                ApiError::new(StatusCode::TOO_MANY_REQUESTS, message.clone()).with_detail(
+                    ErrorDetail::with_message(
+                        Domain::RATE_LIMIT,
+                        Reason::RateLimitExceeded,
+                        message,
+                    ),
+                ),
             )
-                .into_response();
+            .into_response();
 
             // 添加速率限制相关的响应头
-            if let Some(headers_map) = headers {
-                let response_headers = response.headers_mut();
-                for (name, value) in headers_map.iter() {
-                    response_headers.insert(name.clone(), value.clone());
-                }
+            let response_headers = response.headers_mut();
+            if let Some(headers) = headers {
+                response_headers.extend(headers);
+            }
+            if !response_headers.contains_key(RETRY_AFTER) {
+                response_headers.insert(RETRY_AFTER, HeaderValue::from(wait_time));
             }
 
             response
         }
         GovernorError::UnableToExtractKey => {
             tracing::error!("无法提取速率限制的 key");
-            (
+            ApiResponse::error(ApiError::new(
                 StatusCode::INTERNAL_SERVER_ERROR,
-                "服务器内部错误".to_string(),
-            )
-                .into_response()
+                "Internal server error",
+            ))
+            .into_response()
         }
         GovernorError::Other { code, msg, headers } => {
             tracing::error!("速率限制其他错误: {:?}", msg);
-            let mut response = (code, msg.unwrap_or_default()).into_response();
+            let message = msg.unwrap_or_else(|| {
+                code.canonical_reason()
+                    .unwrap_or("Rate limit error")
+                    .to_string()
+            });
+            let mut response = ApiResponse::error(ApiError::new(code, message)).into_response();
 
-            if let Some(headers_map) = headers {
-                let response_headers = response.headers_mut();
-                for (name, value) in headers_map.iter() {
-                    response_headers.insert(name.clone(), value.clone());
-                }
+            if let Some(headers) = headers {
+                response.headers_mut().extend(headers);
             }
 
             response
@@ -5,14 +5,19 @@
 use aide::OperationOutput;
 use aide::generate::GenContext;
 use aide::openapi::Operation;
-use axum::Json;
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use indexmap::IndexMap;
-use schemars::JsonSchema;
+use schemars::{JsonSchema, schema_for};
 use serde::{Deserialize, Serialize};
 
+use super::fields::{FieldSelection, current_fields, invalid_fields_response};
 use super::{ApiError, Domain, ErrorDetail, Reason};
+use crate::core::http::{
+    PageLinks, RequestUrl, ResourceVersion, add_binary_media_types, current_request_id,
+    encoded_response,
+};
+use crate::core::i18n::{MessageCatalog, current_locale};
 
 /// API 版本号
 pub const API_VERSION: &str = "1.0";
@@ -186,10 +191,7 @@ impl<T: Serialize> ApiResponse<T> {
     /// # use crate::response::ApiResponse;
     /// let response = ApiResponse::list(users, 100, 1, 10)
     ///     .with_kind("UserList")
-    ///     .with_links(
-    ///         Some("http://api.example.com/users?page=2".to_string()),
-    ///         Some("http://api.example.com/users?page=0".to_string())
-    ///     );
+    ///     .with_page_links(&url);
     /// ```ignore
     pub fn list(items: Vec<T>, total: i64, page: i64, per_page: i64) -> Self {
         let current_count = items.len() as i64;
@@ -358,10 +360,10 @@ impl<T: Serialize> ApiResponse<T> {
     /// # Examples
     ///
     /// ```ignore
-    /// let response = ApiResponse::list(users, 100, 1, 10)
+    /// let response = ApiResponse::list(users, 100, 2, 10)
     ///     .with_links(
-    ///         Some("http://api.example.com/users?page=2".to_string()),
-    ///         Some("http://api.example.com/users?page=0".to_string())
+    ///         Some("http://api.example.com/users?page=3".to_string()),
+    ///         Some("http://api.example.com/users?page=1".to_string())
     ///     );
     /// ```ignore
     pub fn with_links(mut self, next: Option<String>, previous: Option<String>) -> Self {
@@ -408,6 +410,43 @@ impl<T: Serialize> ApiResponse<T> {
         self
     }
 
+    /// 按请求 URL 生成分页链接（仅对分页列表响应有效）
+    ///
+    /// 填充 `self_link`、`page_link_template`，第一页不含 `previous_link`，最后一页不含 `next_link`。
+    /// 链接保留请求的其余查询参数（如 `page_size`、`fields`）。
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let response = ApiResponse::list(users, 100, 1, 10).with_page_links(&url);
+    /// ```ignore
+    pub fn with_page_links(mut self, url: &RequestUrl) -> Self {
+        if let Some(ref mut data) = self.data
+            && let DataContent::List(ref mut list_data) = data.content
+            && let (Some(page), Some(total_pages)) = (list_data.page_index, list_data.total_pages)
+        {
+            let last = total_pages.max(1);
+            list_data.self_link = Some(url.page_href(page));
+            list_data.page_link_template = Some(url.page_template());
+            list_data.previous_link = (page > 1).then(|| url.page_href((page - 1).min(last)));
+            list_data.next_link = (page < total_pages).then(|| url.page_href(page + 1));
+        }
+        self
+    }
+
+    /// 分页响应头，仅对设置了分页链接模板的列表响应生成
+    fn page_links(&self) -> Option<PageLinks> {
+        let DataContent::List(ref list_data) = self.data.as_ref()?.content else {
+            return None;
+        };
+        Some(PageLinks::new(
+            list_data.page_link_template.as_deref()?,
+            list_data.page_index?,
+            list_data.total_pages?,
+            list_data.total_items?,
+        ))
+    }
+
     /// 获取 HTTP 状态码
     fn status_code(&self) -> StatusCode {
         self.error
@@ -587,10 +626,80 @@ impl ApiResponse<()> {
     }
 }
 
-impl<T: Serialize> IntoResponse for ApiResponse<T> {
-    fn into_response(self) -> Response {
+impl<T: Serialize + JsonSchema> IntoResponse for ApiResponse<T> {
+    fn into_response(mut self) -> Response {
+        // 按 locale_middleware 协商出的语言本地化错误消息并标注 data.lang
+        if let Some(locale) = current_locale() {
+            if let Some(ref mut data) = self.data
+                && data.lang.is_none()
+            {
+                data.lang = Some(locale.tag().to_string());
+            }
+            self.error = self
+                .error
+                .take()
+                .map(|error| MessageCatalog::builtin().localize(error, locale));
+        }
+        // 错误对象带上 request_id_middleware 确定的请求 ID
+        if let Some(ref mut error) = self.error
+            && error.request_id.is_none()
+        {
+            error.request_id = current_request_id();
+        }
+
+        // 保留资源版本，供 etag_middleware 生成 ETag（data.etag 可能被 fields 裁剪掉）
+        let version = self
+            .data
+            .as_ref()
+            .and_then(|data| data.etag.clone())
+            .map(ResourceVersion);
+        // 分页链接，供 pagination_links_middleware 输出 Link 与 X-Total-Count 响应头
+        let page_links = self.page_links();
+
+        // 按 fields_middleware 解析出的 fields 参数裁剪成功响应的 data
+        let mut response = match current_fields() {
+            Some(fields) if self.error.is_none() => self.into_partial_response(&fields),
+            _ => {
+                let status = self.status_code();
+                // 保留错误对象，供 error_format_middleware 按 Accept 改写为 Problem Details
+                let error = self.error.clone();
+                let mut response = encoded_response(status, &self);
+                if let Some(error) = error {
+                    response.extensions_mut().insert(error);
+                }
+                response
+            }
+        };
+        if let Some(version) = version {
+            response.extensions_mut().insert(version);
+        }
+        if let Some(page_links) = page_links {
+            response.extensions_mut().insert(page_links);
+        }
+        response
+    }
+}
+
+impl<T: Serialize + JsonSchema> ApiResponse<T> {
+    /// 按字段选择输出部分响应，选择了 schema 中不存在的字段时返回 400
+    fn into_partial_response(self, fields: &FieldSelection) -> Response {
+        let schema = schema_for!(DataWrapper<T>);
+        if let Err(error) = fields.validate(schema.as_value(), schema.as_value()) {
+            return invalid_fields_response(&error);
+        }
+
         let status = self.status_code();
-        (status, Json(self)).into_response()
+        let mut body = match serde_json::to_value(&self) {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::error!(error = %e, "failed to serialize api response");
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+        };
+        if let Some(data) = body.get_mut("data") {
+            *data = fields.apply(data.take());
+        }
+        encoded_response(status, &body)
     }
 }
 
@@ -616,6 +725,7 @@ impl<T: Serialize + JsonSchema> OperationOutput for ApiResponse<T> {
                 ..Default::default()
             },
         );
+        add_binary_media_types(&mut content);
 
         Some(aide::openapi::Response {
             description: "API 响应".to_string(),
@@ -2,11 +2,14 @@
 //!
 //! 遵循 Google JSON Style Guide 的 error 对象结构。
 
+use std::collections::BTreeMap;
+
 use axum::http::StatusCode;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use super::{Domain, Reason};
+use super::{Domain, Reason, Status};
+use crate::core::http::RequestId;
 
 /// 错误详情（errors 数组中的元素）
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -20,13 +23,18 @@ pub struct ErrorDetail {
     /// 错误消息
     pub message: String,
 
-    /// 错误位置（如字段名）
+    /// 错误位置（如字段的 JSON Pointer `/items/0/name`）
     #[serde(skip_serializing_if = "Option::is_none")]
     pub location: Option<String>,
 
-    /// 位置类型（parameter, header, body）
+    /// 位置类型（body, query, path, header）
     #[serde(skip_serializing_if = "Option::is_none")]
     pub location_type: Option<String>,
+
+    /// 消息参数（如长度约束的 `min` / `max`、解析错误的 `line` / `column`），
+    /// 本地化时填入目录消息中的 `{name}` 占位符，不输出到响应
+    #[serde(skip)]
+    pub params: BTreeMap<String, String>,
 }
 
 impl ErrorDetail {
@@ -38,6 +46,7 @@ impl ErrorDetail {
             message: reason.to_string(),
             location: None,
             location_type: None,
+            params: BTreeMap::new(),
         }
     }
 
@@ -49,6 +58,7 @@ impl ErrorDetail {
             message: message.into(),
             location: None,
             location_type: None,
+            params: BTreeMap::new(),
         }
     }
 
@@ -58,6 +68,12 @@ impl ErrorDetail {
         self.location_type = Some(location_type.into());
         self
     }
+
+    /// 设置消息参数
+    pub fn with_param(mut self, name: impl Into<String>, value: impl ToString) -> Self {
+        self.params.insert(name.into(), value.to_string());
+        self
+    }
 }
 
 /// API 错误对象
@@ -71,9 +87,17 @@ pub struct ApiError {
     /// 主要错误消息
     pub message: String,
 
+    /// 标准状态码语义（如 `FAILED_PRECONDITION`、`ABORTED`），供客户端判断处理方式
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub status: Option<Status>,
+
     /// 错误详情列表
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub errors: Vec<ErrorDetail>,
+
+    /// 本次请求的 ID（与 `X-Request-ID` 响应头一致），用户联系支持时可据此定位日志
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub request_id: Option<RequestId>,
 }
 
 impl ApiError {
@@ -82,7 +106,9 @@ impl ApiError {
         Self {
             code: status.as_u16(),
             message: message.into(),
+            status: None,
             errors: Vec::new(),
+            request_id: None,
         }
     }
 
@@ -91,10 +117,18 @@ impl ApiError {
         Self {
             code: status.as_u16(),
             message: reason.to_string(),
+            status: None,
             errors: vec![ErrorDetail::new(domain, reason)],
+            request_id: None,
         }
     }
 
+    /// 设置标准状态码语义
+    pub fn with_status(mut self, status: Status) -> Self {
+        self.status = Some(status);
+        self
+    }
+
     /// 添加错误详情
     pub fn with_detail(mut self, detail: ErrorDetail) -> Self {
         self.errors.push(detail);
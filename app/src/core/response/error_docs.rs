@@ -0,0 +1,219 @@
+//! 错误响应的 OpenAPI 文档
+//!
+//! 每个错误枚举通过 [`ErrorCases`] 声明自己可能产生的 `(状态码, domain, reason)`，
+//! 据此为接口生成按状态码分组的错误响应：同时列出 Google JSON 与 Problem Details 两种格式，
+//! 每个 `(domain, reason)` 一个示例。
+//!
+//! `Json`、`Query`、`Path` 等提取器的拒绝响应自动写入文档；开启 aide 响应推断时，
+//! 直接返回具体错误类型的处理器也会自动推断。返回 `AppError` 的处理器在文档函数中用
+//! [`ErrorDocs::errors`] 声明实际可能出现的错误类型，例如：
+//!
+//! ```ignore
+//! op.description("用户登录")
+//!     .response::<200, ApiResponse<LoginResponse>>()
+//!     .errors::<AuthError>()
+//! ```
+
+use std::collections::BTreeMap;
+
+use aide::generate::{GenContext, in_context};
+use aide::openapi::{Example, Operation, ReferenceOr, Response};
+use aide::transform::TransformOperation;
+use axum::http::StatusCode;
+
+use super::{
+    ApiError, ApiResponse, Domain, ErrorDetail, PROBLEM_JSON, ProblemDetails, Reason,
+    error_response_docs,
+};
+use crate::core::i18n::{Locale, MessageCatalog};
+
+/// 示例中 Problem Details `type` 使用的前缀（与默认配置一致）
+const EXAMPLE_PROBLEM_TYPE_BASE: &str = "/problems";
+
+/// 错误类型可能产生的一种错误
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorCase {
+    /// HTTP 状态码
+    pub status: StatusCode,
+
+    /// 错误域
+    pub domain: Domain,
+
+    /// 错误原因
+    pub reason: Reason,
+}
+
+impl ErrorCase {
+    pub const fn new(status: StatusCode, domain: Domain, reason: Reason) -> Self {
+        Self {
+            status,
+            domain,
+            reason,
+        }
+    }
+
+    /// 内部错误（详情只进日志，响应中没有错误详情）
+    pub const INTERNAL: Self = Self::new(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Domain::GLOBAL,
+        Reason::InternalError,
+    );
+}
+
+/// 声明错误类型可能产生的全部错误
+pub trait ErrorCases {
+    fn error_cases() -> Vec<ErrorCase>;
+}
+
+macro_rules! impl_error_cases_for_tuple {
+    ($($ty:ident),+) => {
+        impl<$($ty: ErrorCases),+> ErrorCases for ($($ty,)+) {
+            fn error_cases() -> Vec<ErrorCase> {
+                let mut cases = Vec::new();
+                $(
+                    for case in $ty::error_cases() {
+                        if !cases.contains(&case) {
+                            cases.push(case);
+                        }
+                    }
+                )+
+                cases
+            }
+        }
+    };
+}
+
+impl_error_cases_for_tuple!(A);
+impl_error_cases_for_tuple!(A, B);
+impl_error_cases_for_tuple!(A, B, C);
+impl_error_cases_for_tuple!(A, B, C, D);
+impl_error_cases_for_tuple!(A, B, C, D, E);
+impl_error_cases_for_tuple!(A, B, C, D, E, F);
+impl_error_cases_for_tuple!(A, B, C, D, E, F, G);
+impl_error_cases_for_tuple!(A, B, C, D, E, F, G, H);
+
+/// 按状态码分组生成错误响应文档
+pub fn error_responses(ctx: &mut GenContext, cases: &[ErrorCase]) -> Vec<(Option<u16>, Response)> {
+    let mut by_status: BTreeMap<u16, Vec<&ErrorCase>> = BTreeMap::new();
+    for case in cases {
+        by_status
+            .entry(case.status.as_u16())
+            .or_default()
+            .push(case);
+    }
+
+    by_status
+        .into_iter()
+        .map(|(status, cases)| {
+            let mut response = error_response_docs(ctx);
+            response.description = cases[0]
+                .status
+                .canonical_reason()
+                .unwrap_or("Error")
+                .to_string();
+            for case in cases {
+                add_examples(&mut response, case);
+            }
+            (Some(status), response)
+        })
+        .collect()
+}
+
+/// 为响应的两种媒体类型各追加一个 `(domain, reason)` 示例
+fn add_examples(response: &mut Response, case: &ErrorCase) {
+    let message = MessageCatalog::builtin()
+        .message(
+            Locale::SOURCE,
+            case.domain.as_str(),
+            &case.reason.to_string(),
+        )
+        .map(str::to_string)
+        .unwrap_or_else(|| case.reason.to_string());
+    let error = if *case == ErrorCase::INTERNAL {
+        ApiError::new(case.status, message)
+    } else {
+        ApiError::new(case.status, message.clone()).with_detail(ErrorDetail::with_message(
+            case.domain,
+            case.reason,
+            message,
+        ))
+    };
+    let key = format!("{}.{}", case.domain, case.reason);
+
+    for (media_type, content) in response.content.iter_mut() {
+        let value = if media_type == PROBLEM_JSON {
+            serde_json::to_value(ProblemDetails::from_api_error(
+                &error,
+                EXAMPLE_PROBLEM_TYPE_BASE,
+                None,
+            ))
+        } else {
+            serde_json::to_value(ApiResponse::error(error.clone()))
+        };
+        let Ok(value) = value else {
+            continue;
+        };
+        content.examples.entry(key.clone()).or_insert_with(|| {
+            ReferenceOr::Item(Example {
+                summary: Some(key.clone()),
+                value: Some(value),
+                ..Default::default()
+            })
+        });
+    }
+}
+
+/// 将错误响应合并进接口文档
+///
+/// 已有相同状态码的响应时合并示例，不覆盖已有的描述和 schema。
+pub fn merge_error_responses(operation: &mut Operation, responses: Vec<(Option<u16>, Response)>) {
+    let operation_responses = operation.responses.get_or_insert_with(Default::default);
+    for (status, response) in responses {
+        let Some(status) = status else {
+            if operation_responses.default.is_none() {
+                operation_responses.default = Some(ReferenceOr::Item(response));
+            }
+            continue;
+        };
+        let entry = operation_responses
+            .responses
+            .entry(aide::openapi::StatusCode::Code(status));
+        match entry {
+            indexmap::map::Entry::Vacant(vacant) => {
+                vacant.insert(ReferenceOr::Item(response));
+            }
+            indexmap::map::Entry::Occupied(mut occupied) => {
+                let ReferenceOr::Item(existing) = occupied.get_mut() else {
+                    continue;
+                };
+                for (media_type, content) in response.content {
+                    match existing.content.get_mut(&media_type) {
+                        Some(existing_content) => {
+                            for (key, example) in content.examples {
+                                existing_content.examples.entry(key).or_insert(example);
+                            }
+                        }
+                        None => {
+                            existing.content.insert(media_type, content);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 在接口文档中声明错误响应
+pub trait ErrorDocs {
+    /// 追加错误类型 `E`（可以是元组）可能产生的错误响应
+    fn errors<E: ErrorCases>(self) -> Self;
+}
+
+impl ErrorDocs for TransformOperation<'_> {
+    fn errors<E: ErrorCases>(mut self) -> Self {
+        let cases = E::error_cases();
+        let responses = in_context(|ctx| error_responses(ctx, &cases));
+        merge_error_responses(self.inner_mut(), responses);
+        self
+    }
+}
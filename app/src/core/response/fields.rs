@@ -0,0 +1,331 @@
+//! 部分响应（`fields` 查询参数）
+//!
+//! 按 Google API 的 partial response 语法裁剪成功响应的 `data` 对象：
+//!
+//! - `a,b`：选择多个字段
+//! - `a/b`：选择嵌套字段（等价于 `a(b)`）
+//! - `a(b,c)`：选择嵌套对象或数组元素的子字段
+//! - `*`：选择当前层级的全部字段
+//!
+//! 例如 `fields=items(id,username),total_items` 只返回列表项的 `id`、`username` 和总数。
+//! 选择对数组透明：作用于数组时逐个裁剪其元素。
+
+use std::collections::BTreeMap;
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use serde_json::{Map, Value};
+
+use super::{ApiError, ApiResponse, Domain, ErrorDetail, Reason};
+
+/// 查询参数名
+pub const FIELDS_PARAM: &str = "fields";
+
+/// 通配符，选择当前层级的全部字段
+const WILDCARD: &str = "*";
+
+/// 解析后的字段选择
+///
+/// 每个键是一个字段名，值是该字段的子选择；子选择为空表示返回整个字段。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldSelection {
+    fields: BTreeMap<String, FieldSelection>,
+}
+
+/// 字段选择的解析或校验错误
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{0}")]
+pub struct FieldSelectionError(String);
+
+impl FieldSelection {
+    /// 解析 `fields` 参数
+    pub fn parse(input: &str) -> Result<Self, FieldSelectionError> {
+        let mut parser = Parser {
+            input: input.as_bytes(),
+            position: 0,
+        };
+        let selection = parser.parse_list()?;
+        match parser.peek() {
+            None => {}
+            Some(b')') => return Err(parser.error("多余的右括号")),
+            Some(_) => return Err(parser.error("无法识别的字符")),
+        }
+        if selection.is_empty() {
+            return Err(FieldSelectionError("fields 不能为空".to_string()));
+        }
+        Ok(selection)
+    }
+
+    /// 是否没有选择任何字段（即返回整个值）
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// 合并另一个选择（同名字段的子选择取并集，任一方选择整个字段时结果为整个字段）
+    fn merge(&mut self, name: String, sub: FieldSelection) {
+        match self.fields.get_mut(&name) {
+            Some(existing) if existing.is_empty() => {}
+            Some(_) if sub.is_empty() => {
+                self.fields.insert(name, sub);
+            }
+            Some(existing) => {
+                for (name, sub) in sub.fields {
+                    existing.merge(name, sub);
+                }
+            }
+            None => {
+                self.fields.insert(name, sub);
+            }
+        }
+    }
+
+    /// 按 JSON Schema 校验所选字段是否存在
+    ///
+    /// `root` 为根 schema（用于解析 `$defs` 引用），`schema` 为当前层级的 schema。
+    /// 没有声明属性的开放对象（如 `serde_json::Value`）不做限制。
+    pub fn validate(&self, root: &Value, schema: &Value) -> Result<(), FieldSelectionError> {
+        self.validate_at(root, schema, "")
+    }
+
+    fn validate_at(
+        &self,
+        root: &Value,
+        schema: &Value,
+        path: &str,
+    ) -> Result<(), FieldSelectionError> {
+        let properties = match shape_of(root, schema) {
+            Shape::Open => return Ok(()),
+            Shape::Object(properties) => properties,
+            Shape::Leaf => {
+                return Err(FieldSelectionError(format!(
+                    "字段 {} 不是对象，不支持子字段选择",
+                    path.trim_end_matches('/')
+                )));
+            }
+        };
+        for (name, sub) in &self.fields {
+            let field_path = format!("{path}{name}");
+            if name == WILDCARD {
+                if !sub.is_empty() {
+                    return Err(FieldSelectionError(format!(
+                        "通配符 {field_path} 不支持子字段选择"
+                    )));
+                }
+                continue;
+            }
+            let Some(property) = properties.get(name.as_str()) else {
+                return Err(FieldSelectionError(format!("未知字段 {field_path}")));
+            };
+            if !sub.is_empty() {
+                sub.validate_at(root, property, &format!("{field_path}/"))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 按选择裁剪值：对象只保留所选字段，数组逐个裁剪元素，其他值原样返回
+    pub fn apply(&self, value: Value) -> Value {
+        if self.is_empty() {
+            return value;
+        }
+        match value {
+            Value::Object(object) => {
+                let keep_all = self.fields.contains_key(WILDCARD);
+                let filtered: Map<String, Value> = object
+                    .into_iter()
+                    .filter_map(|(key, value)| match self.fields.get(&key) {
+                        Some(sub) => Some((key, sub.apply(value))),
+                        None if keep_all => Some((key, value)),
+                        None => None,
+                    })
+                    .collect();
+                Value::Object(filtered)
+            }
+            Value::Array(items) => {
+                Value::Array(items.into_iter().map(|item| self.apply(item)).collect())
+            }
+            other => other,
+        }
+    }
+}
+
+/// schema 在某一层级的形状
+enum Shape<'a> {
+    /// 开放对象（如 `serde_json::Value`、`HashMap`），任何字段都允许
+    Open,
+
+    /// 声明了属性的对象
+    Object(BTreeMap<&'a str, &'a Value>),
+
+    /// 标量等不能再选择子字段的值
+    Leaf,
+}
+
+/// 限定值形状的 schema 关键字
+const CONSTRAINT_KEYWORDS: &[&str] = &[
+    "type",
+    "$ref",
+    "properties",
+    "items",
+    "allOf",
+    "anyOf",
+    "oneOf",
+    "enum",
+    "const",
+];
+
+/// 收集 schema 在当前层级可用的属性（解析 `$ref`、组合关键字与数组元素）
+fn shape_of<'a>(root: &'a Value, schema: &'a Value) -> Shape<'a> {
+    let mut properties = BTreeMap::new();
+    let mut has_properties = false;
+    let mut stack = vec![schema];
+
+    while let Some(schema) = stack.pop() {
+        let Some(object) = schema.as_object() else {
+            // 布尔 schema：`true` 接受任意值
+            if schema.as_bool() == Some(true) {
+                return Shape::Open;
+            }
+            continue;
+        };
+        // 没有任何约束关键字（只有描述等注解）的 schema 接受任意值
+        if !CONSTRAINT_KEYWORDS
+            .iter()
+            .any(|keyword| object.contains_key(*keyword))
+        {
+            return Shape::Open;
+        }
+        if let Some(reference) = object.get("$ref").and_then(Value::as_str) {
+            match resolve_ref(root, reference) {
+                Some(target) => stack.push(target),
+                None => return Shape::Open,
+            }
+        }
+        if let Some(items) = object.get("items") {
+            stack.push(items);
+        }
+        for keyword in ["allOf", "anyOf", "oneOf"] {
+            if let Some(variants) = object.get(keyword).and_then(Value::as_array) {
+                stack.extend(variants);
+            }
+        }
+        if matches!(object.get("additionalProperties"), Some(additional) if additional != &Value::Bool(false))
+        {
+            return Shape::Open;
+        }
+        if let Some(props) = object.get("properties").and_then(Value::as_object) {
+            has_properties = true;
+            properties.extend(props.iter().map(|(name, schema)| (name.as_str(), schema)));
+        }
+    }
+
+    if has_properties {
+        Shape::Object(properties)
+    } else {
+        Shape::Leaf
+    }
+}
+
+/// 解析形如 `#/$defs/Name` 的本地引用
+fn resolve_ref<'a>(root: &'a Value, reference: &str) -> Option<&'a Value> {
+    root.pointer(reference.strip_prefix('#')?)
+}
+
+/// `fields` 参数无效时的 400 响应
+pub fn invalid_fields_response(error: &FieldSelectionError) -> Response {
+    let message = format!("fields 参数无效：{error}");
+    ApiResponse::error(
+        ApiError::new(StatusCode::BAD_REQUEST, message.clone()).with_detail(
+            ErrorDetail::with_message(Domain::VALIDATION, Reason::InvalidFormat, message)
+                .at(FIELDS_PARAM, "query"),
+        ),
+    )
+    .into_response()
+}
+
+/// `fields` 语法的递归下降解析器
+struct Parser<'a> {
+    input: &'a [u8],
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> FieldSelectionError {
+        FieldSelectionError(format!("{message}（位置 {}）", self.position))
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.position).copied()
+    }
+
+    /// list := term (',' term)*
+    fn parse_list(&mut self) -> Result<FieldSelection, FieldSelectionError> {
+        let mut selection = FieldSelection::default();
+        loop {
+            let (name, sub) = self.parse_term()?;
+            selection.merge(name, sub);
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                _ => return Ok(selection),
+            }
+        }
+    }
+
+    /// term := name ('/' term | '(' list ')')?
+    fn parse_term(&mut self) -> Result<(String, FieldSelection), FieldSelectionError> {
+        let name = self.parse_name()?;
+        let sub = match self.peek() {
+            Some(b'/') => {
+                self.position += 1;
+                let (child, sub) = self.parse_term()?;
+                let mut selection = FieldSelection::default();
+                selection.merge(child, sub);
+                selection
+            }
+            Some(b'(') => {
+                self.position += 1;
+                let selection = self.parse_list()?;
+                if self.peek() != Some(b')') {
+                    return Err(self.error("缺少右括号"));
+                }
+                self.position += 1;
+                selection
+            }
+            _ => FieldSelection::default(),
+        };
+        Ok((name, sub))
+    }
+
+    /// name := [A-Za-z0-9_]+ | '*'
+    fn parse_name(&mut self) -> Result<String, FieldSelectionError> {
+        let start = self.position;
+        if self.peek() == Some(b'*') {
+            self.position += 1;
+            return Ok(WILDCARD.to_string());
+        }
+        while self
+            .peek()
+            .is_some_and(|byte| byte.is_ascii_alphanumeric() || byte == b'_')
+        {
+            self.position += 1;
+        }
+        if start == self.position {
+            return Err(self.error("缺少字段名"));
+        }
+        Ok(String::from_utf8_lossy(&self.input[start..self.position]).into_owned())
+    }
+}
+
+tokio::task_local! {
+    static CURRENT_FIELDS: FieldSelection;
+}
+
+/// 在指定字段选择下执行 future（请求处理期间 `current_fields` 返回该选择）
+pub async fn with_fields<F: Future>(fields: FieldSelection, future: F) -> F::Output {
+    CURRENT_FIELDS.scope(fields, future).await
+}
+
+/// 当前请求的字段选择，不在 `with_fields` 范围内时返回 `None`
+pub fn current_fields() -> Option<FieldSelection> {
+    CURRENT_FIELDS.try_with(Clone::clone).ok()
+}
@@ -38,8 +38,9 @@
 //!     "start_index": 0,
 //!     "page_index": 1,
 //!     "total_pages": 10,
-//!     "next_link": "http://api.example.com/users?page=2",
-//!     "previous_link": "http://api.example.com/users?page=0"
+//!     "page_link_template": "http://api.example.com/users?page_size=10&page={page}",
+//!     "self_link": "http://api.example.com/users?page_size=10&page=1",
+//!     "next_link": "http://api.example.com/users?page_size=10&page=2"
 //!   }
 //! }
 //! ```
@@ -72,6 +73,11 @@
 //! - [`ErrorDetail`] - 错误详情
 //! - [`Domain`] - 错误域枚举
 //! - [`Reason`] - 错误原因枚举
+//! - [`Status`] - 标准状态码语义（如并发冲突 `ABORTED`），写入 `error.status`
+//! - [`ProblemDetails`] - RFC 9457 格式的错误对象（按 `Accept` 协商）
+//! - [`ErrorCases`] / [`ErrorDocs`] - 错误类型声明可能产生的错误，并写入 OpenAPI 文档
+//! - [`FieldSelection`] - `fields` 查询参数（部分响应），按 schema 校验并裁剪 `data`
+//! - [`StreamingList`] - 流式列表（NDJSON / CSV，按 `Accept` 协商），用于导出完整数据集
 //!
 //! ## 使用示例
 //!
@@ -84,13 +90,10 @@
 //!     .with_kind("User")
 //!     .with_id("user123");
 //!
-//! // 分页列表响应
+//! // 分页列表响应（url 为 RequestUrl 提取器，按请求原始 URL 生成分页链接）
 //! let response = ApiResponse::list(users, 100, 1, 10)
 //!     .with_kind("UserList")
-//!     .with_links(
-//!         Some("http://api.example.com/users?page=2".to_string()),
-//!         Some("http://api.example.com/users?page=0".to_string())
-//!     );
+//!     .with_page_links(&url);
 //!
 //! // 简单列表响应（无分页信息）
 //! let response = ApiResponse::simple_list(tags)
@@ -112,9 +115,28 @@
 mod api_response;
 mod domain;
 mod error;
+mod error_docs;
+mod fields;
+mod problem;
 mod reason;
+mod status;
+mod streaming;
 
 pub use api_response::{API_VERSION, ApiResponse, DataContent, DataWrapper};
 pub use domain::Domain;
 pub use error::{ApiError, ErrorDetail};
+pub use error_docs::{ErrorCase, ErrorCases, ErrorDocs, error_responses, merge_error_responses};
+pub use fields::{
+    FIELDS_PARAM, FieldSelection, FieldSelectionError, current_fields, invalid_fields_response,
+    with_fields,
+};
+pub use problem::{
+    ErrorFormat, PROBLEM_JSON, ProblemDetails, error_response_docs, problem_type_uri,
+};
 pub use reason::Reason;
+pub use status::Status;
+pub use streaming::{
+    CSV, CsvRecord, NDJSON, StreamFormat, StreamingList, encode_csv_line, escape_formula,
+};
+
+pub(crate) use problem::media_quality;
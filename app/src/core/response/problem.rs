@@ -0,0 +1,195 @@
+//! RFC 9457 Problem Details
+//!
+//! 错误响应的另一种格式（`application/problem+json`），供只认 RFC 9457 的网关和客户端使用。
+//! 与 Google JSON 格式承载相同的信息：`type` 由首个错误详情的 `(domain, reason)` 派生，
+//! `instance` 为请求 ID，`errors` 扩展成员沿用 [`ErrorDetail`]。
+//!
+//! 响应格式由 `error_format_middleware` 按 `Accept` 头协商，未明确偏好时使用配置的默认格式。
+
+use aide::generate::GenContext;
+use aide::openapi::{MediaType, SchemaObject};
+use axum::http::header::CONTENT_TYPE;
+use axum::http::{HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use indexmap::IndexMap;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::{ApiError, ApiResponse, ErrorDetail};
+
+/// Problem Details 的媒体类型
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// 错误响应格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorFormat {
+    /// Google JSON Style Guide 的 `error` 对象（`application/json`）
+    #[default]
+    Google,
+
+    /// RFC 9457 Problem Details（`application/problem+json`）
+    Problem,
+}
+
+impl ErrorFormat {
+    /// 从配置值解析（`google` 或 `problem`）
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "google" => Some(Self::Google),
+            "problem" => Some(Self::Problem),
+            _ => None,
+        }
+    }
+
+    /// 按 `Accept` 头协商错误格式
+    ///
+    /// 比较 `application/problem+json` 与 `application/json` 的 q 值，取较高者；
+    /// 两者都未显式出现或 q 值相同（包括只有 `*/*` 的情况）时使用 `default`。
+    pub fn negotiate(accept: Option<&str>, default: Self) -> Self {
+        let Some(accept) = accept else {
+            return default;
+        };
+        let problem = media_quality(accept, PROBLEM_JSON);
+        let json = media_quality(accept, "application/json");
+        match problem.partial_cmp(&json) {
+            Some(std::cmp::Ordering::Greater) => Self::Problem,
+            Some(std::cmp::Ordering::Less) => Self::Google,
+            _ => default,
+        }
+    }
+}
+
+/// `Accept` 中显式列出的某个媒体类型的 q 值，未列出时为 0
+pub(crate) fn media_quality(accept: &str, media_type: &str) -> f32 {
+    accept
+        .split(',')
+        .filter_map(|range| {
+            let mut params = range.split(';');
+            let essence = params.next()?.trim();
+            if !essence.eq_ignore_ascii_case(media_type) {
+                return None;
+            }
+            let quality = params
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            Some(quality.clamp(0.0, 1.0))
+        })
+        .fold(0.0, f32::max)
+}
+
+/// RFC 9457 Problem Details 文档
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ProblemDetails {
+    /// 问题类型 URI，由 `(domain, reason)` 派生；无错误详情时为 `about:blank`
+    #[serde(rename = "type")]
+    pub problem_type: String,
+
+    /// 问题类型的简短描述（HTTP 状态码的标准短语）
+    pub title: String,
+
+    /// HTTP 状态码
+    pub status: u16,
+
+    /// 本次错误的具体说明
+    pub detail: String,
+
+    /// 本次请求的标识（请求 ID）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+
+    /// 错误详情列表（扩展成员）
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub errors: Vec<ErrorDetail>,
+}
+
+impl ProblemDetails {
+    /// 从 `ApiError` 转换
+    ///
+    /// # 参数
+    /// * `error` - 错误对象
+    /// * `type_base` - 问题类型 URI 前缀
+    /// * `instance` - 请求 ID
+    pub fn from_api_error(error: &ApiError, type_base: &str, instance: Option<String>) -> Self {
+        let status = error.status_code();
+        let problem_type = error
+            .errors
+            .first()
+            .map(|detail| problem_type_uri(type_base, &detail.domain, &detail.reason))
+            .unwrap_or_else(|| "about:blank".to_string());
+
+        Self {
+            problem_type,
+            title: status.canonical_reason().unwrap_or("Unknown").to_string(),
+            status: status.as_u16(),
+            detail: error.message.clone(),
+            instance,
+            errors: error.errors.clone(),
+        }
+    }
+}
+
+/// 问题类型 URI：`{type_base}/{domain}/{reason}`，reason 转为小写短横线形式
+///
+/// 例如 `/problems/validation/invalid-format`。
+pub fn problem_type_uri(type_base: &str, domain: &str, reason: &str) -> String {
+    format!(
+        "{}/{}/{}",
+        type_base.trim_end_matches('/'),
+        domain,
+        reason.to_ascii_lowercase().replace('_', "-")
+    )
+}
+
+impl IntoResponse for ProblemDetails {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        match serde_json::to_vec(&self) {
+            Ok(body) => (
+                status,
+                [(CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON))],
+                body,
+            )
+                .into_response(),
+            Err(e) => {
+                tracing::error!(error = %e, "failed to serialize problem details");
+                StatusCode::INTERNAL_SERVER_ERROR.into_response()
+            }
+        }
+    }
+}
+
+/// 错误响应的 OpenAPI 描述，同时列出两种格式
+pub fn error_response_docs(ctx: &mut GenContext) -> aide::openapi::Response {
+    let mut content = IndexMap::new();
+    content.insert(
+        "application/json".to_string(),
+        MediaType {
+            schema: Some(SchemaObject {
+                json_schema: ctx.schema.subschema_for::<ApiResponse<()>>(),
+                external_docs: None,
+                example: None,
+            }),
+            ..Default::default()
+        },
+    );
+    content.insert(
+        PROBLEM_JSON.to_string(),
+        MediaType {
+            schema: Some(SchemaObject {
+                json_schema: ctx.schema.subschema_for::<ProblemDetails>(),
+                external_docs: None,
+                example: None,
+            }),
+            ..Default::default()
+        },
+    );
+
+    aide::openapi::Response {
+        description: "错误响应（按 Accept 协商为 Google JSON 或 RFC 9457 Problem Details）"
+            .to_string(),
+        content,
+        ..Default::default()
+    }
+}
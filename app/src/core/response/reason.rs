@@ -50,6 +50,10 @@ pub enum Reason {
     WeakPassword,
     /// 两次密码不匹配
     PasswordMismatch,
+    /// 请求体媒体类型不受支持
+    UnsupportedMediaType,
+    /// 请求体（解压后）超出大小限制
+    PayloadTooLarge,
 
     // ==================== 资源通用 ====================
     /// 资源未找到
@@ -60,6 +64,14 @@ pub enum Reason {
     Conflict,
     /// 使用次数/变更次数已达上限
     UsageLimitReached,
+    /// 资源已被修改，`If-Match` 与当前版本不一致
+    PreconditionFailed,
+    /// 修改资源时缺少 `If-Match` 请求头
+    PreconditionRequired,
+    /// 同一 `Idempotency-Key` 被用于内容不同的请求
+    IdempotencyKeyReused,
+    /// 同一 `Idempotency-Key` 的请求仍在处理中
+    RequestInProgress,
 
     // ==================== 权限 ====================
     /// 权限不足
@@ -84,6 +96,8 @@ pub enum Reason {
     ServiceUnavailable,
     /// 功能未实现
     NotImplemented,
+    /// 请求方法不被该路径支持
+    MethodNotAllowed,
     /// 请求超时
     Timeout,
     /// 未知错误
@@ -91,6 +105,47 @@ pub enum Reason {
     Unknown,
 }
 
+impl Reason {
+    /// 全部错误原因（用于校验消息目录等需要枚举所有 reason 的场景）
+    pub const ALL: [Self; 35] = [
+        Self::UserNotFound,
+        Self::InvalidPassword,
+        Self::InvalidToken,
+        Self::TokenExpired,
+        Self::MissingCredentials,
+        Self::AuthenticationFailed,
+        Self::InvalidFormat,
+        Self::RequiredFieldMissing,
+        Self::ValueOutOfRange,
+        Self::InvalidLength,
+        Self::InvalidEmail,
+        Self::InvalidUsername,
+        Self::WeakPassword,
+        Self::PasswordMismatch,
+        Self::UnsupportedMediaType,
+        Self::PayloadTooLarge,
+        Self::NotFound,
+        Self::AlreadyExists,
+        Self::Conflict,
+        Self::UsageLimitReached,
+        Self::PreconditionFailed,
+        Self::PreconditionRequired,
+        Self::IdempotencyKeyReused,
+        Self::RequestInProgress,
+        Self::PermissionDenied,
+        Self::FileTooLarge,
+        Self::FileTypeNotAllowed,
+        Self::UploadFailed,
+        Self::RateLimitExceeded,
+        Self::InternalError,
+        Self::ServiceUnavailable,
+        Self::NotImplemented,
+        Self::MethodNotAllowed,
+        Self::Timeout,
+        Self::Unknown,
+    ];
+}
+
 impl std::fmt::Display for Reason {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let s = match self {
@@ -108,10 +163,16 @@ impl std::fmt::Display for Reason {
             Self::InvalidUsername => "INVALID_USERNAME",
             Self::WeakPassword => "WEAK_PASSWORD",
             Self::PasswordMismatch => "PASSWORD_MISMATCH",
+            Self::UnsupportedMediaType => "UNSUPPORTED_MEDIA_TYPE",
+            Self::PayloadTooLarge => "PAYLOAD_TOO_LARGE",
             Self::NotFound => "NOT_FOUND",
             Self::AlreadyExists => "ALREADY_EXISTS",
             Self::Conflict => "CONFLICT",
             Self::UsageLimitReached => "USAGE_LIMIT_REACHED",
+            Self::PreconditionFailed => "PRECONDITION_FAILED",
+            Self::PreconditionRequired => "PRECONDITION_REQUIRED",
+            Self::IdempotencyKeyReused => "IDEMPOTENCY_KEY_REUSED",
+            Self::RequestInProgress => "REQUEST_IN_PROGRESS",
             Self::PermissionDenied => "PERMISSION_DENIED",
             Self::FileTooLarge => "FILE_TOO_LARGE",
             Self::FileTypeNotAllowed => "FILE_TYPE_NOT_ALLOWED",
@@ -120,6 +181,7 @@ impl std::fmt::Display for Reason {
             Self::InternalError => "INTERNAL_ERROR",
             Self::ServiceUnavailable => "SERVICE_UNAVAILABLE",
             Self::NotImplemented => "NOT_IMPLEMENTED",
+            Self::MethodNotAllowed => "METHOD_NOT_ALLOWED",
             Self::Timeout => "TIMEOUT",
             Self::Unknown => "UNKNOWN",
         };
@@ -12,8 +12,10 @@ use std::fmt;
 /// 借鉴 Google gRPC 的 17 个标准状态码语义，用于 REST API。
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[derive(Default)]
 pub enum Status {
     /// 成功
+    #[default]
     Ok,
     /// 操作被取消
     Cancelled,
@@ -113,12 +115,6 @@ impl Status {
     }
 }
 
-impl Default for Status {
-    fn default() -> Self {
-        Status::Ok
-    }
-}
-
 impl fmt::Display for Status {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.as_str())
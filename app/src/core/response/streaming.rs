@@ -0,0 +1,334 @@
+//! 流式列表响应（NDJSON / CSV）
+//!
+//! `ApiResponse::list` 需要把整页数据载入内存，且每页最多 `MAX_PAGE_SIZE` 条，不适合导出完整数据集。
+//! `StreamingList` 逐行编码并随写随发，格式按 `Accept` 协商：
+//!
+//! ```ignore
+//! pub async fn export_users(State(state): State<Arc<AppState>>, headers: HeaderMap) -> StreamingList<ExportUserRow> {
+//!     let format = StreamFormat::from_headers(&headers, StreamFormat::Csv);
+//!     let select = user::Entity::find().order_by_asc(user::Column::Id);
+//!     StreamingList::from_select(format, state.db.clone(), select, ExportUserRow::from)
+//!         .with_filename("users")
+//! }
+//! ```
+
+use std::io;
+
+use aide::OperationOutput;
+use aide::generate::GenContext;
+use aide::openapi::{MediaType, Operation, SchemaObject};
+use axum::body::{Body, Bytes};
+use axum::http::HeaderMap;
+use axum::http::header::{ACCEPT, CONTENT_DISPOSITION, CONTENT_TYPE};
+use axum::response::{IntoResponse, Response};
+use futures_util::stream::BoxStream;
+use futures_util::{Stream, StreamExt, TryStreamExt};
+use indexmap::IndexMap;
+use schemars::JsonSchema;
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, DatabaseTransaction, DbErr, EntityTrait, Select,
+    TransactionTrait,
+};
+use serde::Serialize;
+use tokio::sync::mpsc;
+
+use super::problem::media_quality;
+
+/// NDJSON 的媒体类型
+pub const NDJSON: &str = "application/x-ndjson";
+
+/// CSV 的媒体类型
+pub const CSV: &str = "text/csv";
+
+/// 查询结果与响应体之间的通道容量（行数）
+///
+/// 客户端读取较慢时通道写满，数据库游标随之暂停，内存占用不随数据集增长。
+const STREAM_CHANNEL_CAPACITY: usize = 256;
+
+/// 流式响应格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StreamFormat {
+    /// 每行一个 JSON 对象（`application/x-ndjson`）
+    #[default]
+    Ndjson,
+
+    /// 逗号分隔，首行为表头（`text/csv`）
+    Csv,
+}
+
+impl StreamFormat {
+    /// 媒体类型
+    pub fn media_type(self) -> &'static str {
+        match self {
+            Self::Ndjson => NDJSON,
+            Self::Csv => CSV,
+        }
+    }
+
+    /// 下载文件的扩展名
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Ndjson => "ndjson",
+            Self::Csv => "csv",
+        }
+    }
+
+    /// 按 `Accept` 头协商流式格式
+    ///
+    /// 比较 `application/x-ndjson` 与 `text/csv` 的 q 值，取较高者；
+    /// 两者都未显式出现或 q 值相同（包括只有 `*/*` 的情况）时使用 `default`。
+    pub fn negotiate(accept: Option<&str>, default: Self) -> Self {
+        let Some(accept) = accept else {
+            return default;
+        };
+        let ndjson = media_quality(accept, NDJSON);
+        let csv = media_quality(accept, CSV);
+        match ndjson.partial_cmp(&csv) {
+            Some(std::cmp::Ordering::Greater) => Self::Ndjson,
+            Some(std::cmp::Ordering::Less) => Self::Csv,
+            _ => default,
+        }
+    }
+
+    /// 按请求头中的 `Accept` 协商
+    pub fn from_headers(headers: &HeaderMap, default: Self) -> Self {
+        Self::negotiate(
+            headers.get(ACCEPT).and_then(|value| value.to_str().ok()),
+            default,
+        )
+    }
+}
+
+/// 可编码为 CSV 行的记录
+///
+/// 列顺序由实现显式给出，不依赖序列化字段顺序。
+pub trait CsvRecord {
+    /// 表头
+    fn csv_header() -> Vec<&'static str>;
+
+    /// 一行的各列，用户可控的文本应经 [`escape_formula`] 处理
+    fn csv_record(&self) -> Vec<String>;
+}
+
+/// 以 `=`、`+`、`-`、`@` 等开头的单元格前加单引号，使电子表格按文本处理
+pub fn escape_formula(value: &str) -> String {
+    if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{value}")
+    } else {
+        value.to_string()
+    }
+}
+
+/// 将各列编码为一个 CSV 行（含换行符）
+pub fn encode_csv_line<S: AsRef<[u8]>>(fields: &[S]) -> Vec<u8> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    // 写入内存缓冲区不会失败
+    let _ = writer.write_record(fields);
+    writer.into_inner().unwrap_or_default()
+}
+
+/// 流式列表响应
+///
+/// 逐行拉取数据并编码为 NDJSON 或 CSV，响应体按客户端读取速度推进（背压）。
+/// 中途出错时记录日志并中断响应体，客户端会看到连接异常结束而不是截断的完整响应。
+pub struct StreamingList<T> {
+    format: StreamFormat,
+    rows: BoxStream<'static, Result<T, io::Error>>,
+    filename: Option<String>,
+}
+
+impl<T> StreamingList<T>
+where
+    T: Serialize + CsvRecord + Send + 'static,
+{
+    /// 由任意行流创建
+    pub fn new<S, E>(format: StreamFormat, rows: S) -> Self
+    where
+        S: Stream<Item = Result<T, E>> + Send + 'static,
+        E: Into<Box<dyn std::error::Error + Send + Sync>> + 'static,
+    {
+        Self {
+            format,
+            rows: rows.map_err(io::Error::other).boxed(),
+            filename: None,
+        }
+    }
+
+    /// 由 SeaORM 查询创建，逐行读取数据库游标并经 `map` 转换为输出行
+    ///
+    /// 游标在独立任务中读取，经有界通道交给响应体；客户端断开后任务随之结束。
+    /// 查询的执行时间取决于客户端读取速度，因此在只读事务内解除连接池的语句超时，
+    /// 客户端断开时事务随任务回滚，查询随之中止。
+    pub fn from_select<E, F>(
+        format: StreamFormat,
+        db: DatabaseConnection,
+        select: Select<E>,
+        mut map: F,
+    ) -> Self
+    where
+        E: EntityTrait,
+        E::Model: Send + Sync,
+        F: FnMut(E::Model) -> T + Send + 'static,
+    {
+        let (tx, mut rx) = mpsc::channel::<Result<T, io::Error>>(STREAM_CHANNEL_CAPACITY);
+
+        tokio::spawn(async move {
+            let txn = match begin_unbounded(&db).await {
+                Ok(txn) => txn,
+                Err(error) => {
+                    let _ = tx.send(Err(io::Error::other(error))).await;
+                    return;
+                }
+            };
+            let mut stream = match select.stream(&txn).await {
+                Ok(stream) => stream,
+                Err(error) => {
+                    let _ = tx.send(Err(io::Error::other(error))).await;
+                    return;
+                }
+            };
+
+            while let Some(item) = stream.next().await {
+                let row = item.map(&mut map).map_err(io::Error::other);
+                let failed = row.is_err();
+                if tx.send(row).await.is_err() || failed {
+                    return;
+                }
+            }
+        });
+
+        Self::new(
+            format,
+            futures_util::stream::poll_fn(move |cx| rx.poll_recv(cx)),
+        )
+    }
+
+    /// 作为附件下载，文件名为 `{name}.{扩展名}`
+    pub fn with_filename(mut self, name: impl Into<String>) -> Self {
+        self.filename = Some(name.into());
+        self
+    }
+
+    /// 响应格式
+    pub fn format(&self) -> StreamFormat {
+        self.format
+    }
+
+    /// 编码后的响应体分块
+    fn into_chunks(self) -> BoxStream<'static, Result<Bytes, io::Error>> {
+        let format = self.format;
+        let header = match format {
+            StreamFormat::Csv => Some(Ok(Bytes::from(encode_csv_line(&T::csv_header())))),
+            StreamFormat::Ndjson => None,
+        };
+        let rows = self.rows.map(move |row| {
+            row.and_then(|row| encode_row(format, &row))
+                .inspect_err(|error| tracing::error!(error = %error, "流式响应输出失败"))
+        });
+        futures_util::stream::iter(header).chain(rows).boxed()
+    }
+}
+
+/// 开启不受语句超时限制的只读事务，供流式游标使用
+async fn begin_unbounded(db: &DatabaseConnection) -> Result<DatabaseTransaction, DbErr> {
+    let txn = db.begin().await?;
+    txn.execute_unprepared("SET TRANSACTION READ ONLY").await?;
+    txn.execute_unprepared("SET LOCAL statement_timeout = 0")
+        .await?;
+    Ok(txn)
+}
+
+/// 将一行编码为对应格式（含换行符）
+fn encode_row<T: Serialize + CsvRecord>(format: StreamFormat, row: &T) -> Result<Bytes, io::Error> {
+    match format {
+        StreamFormat::Ndjson => {
+            let mut line = serde_json::to_vec(row)?;
+            line.push(b'\n');
+            Ok(Bytes::from(line))
+        }
+        StreamFormat::Csv => Ok(Bytes::from(encode_csv_line(&row.csv_record()))),
+    }
+}
+
+impl<T> IntoResponse for StreamingList<T>
+where
+    T: Serialize + CsvRecord + Send + 'static,
+{
+    fn into_response(self) -> Response {
+        let format = self.format;
+        let disposition = self.filename.as_ref().map(|name| {
+            format!(
+                "attachment; filename=\"{}.{}\"",
+                name.replace(['"', '\\'], ""),
+                format.extension()
+            )
+        });
+        let content_type = match format {
+            StreamFormat::Ndjson => NDJSON.to_string(),
+            StreamFormat::Csv => format!("{CSV}; charset=utf-8"),
+        };
+
+        let mut response = Body::from_stream(self.into_chunks()).into_response();
+        let headers = response.headers_mut();
+        if let Ok(value) = content_type.parse() {
+            headers.insert(CONTENT_TYPE, value);
+        }
+        if let Some(value) = disposition.and_then(|value| value.parse().ok()) {
+            headers.insert(CONTENT_DISPOSITION, value);
+        }
+        response
+    }
+}
+
+impl<T: JsonSchema> OperationOutput for StreamingList<T> {
+    type Inner = T;
+
+    fn operation_response(
+        ctx: &mut GenContext,
+        _operation: &mut Operation,
+    ) -> Option<aide::openapi::Response> {
+        let row = ctx.schema.subschema_for::<T>();
+        let text = ctx.schema.subschema_for::<String>();
+
+        let mut content = IndexMap::new();
+        content.insert(
+            NDJSON.to_string(),
+            MediaType {
+                schema: Some(SchemaObject {
+                    json_schema: row,
+                    external_docs: None,
+                    example: None,
+                }),
+                ..Default::default()
+            },
+        );
+        content.insert(
+            CSV.to_string(),
+            MediaType {
+                schema: Some(SchemaObject {
+                    json_schema: text,
+                    external_docs: None,
+                    example: None,
+                }),
+                ..Default::default()
+            },
+        );
+
+        Some(aide::openapi::Response {
+            description:
+                "流式列表：NDJSON 每行一条记录（结构如 schema），CSV 首行为表头；按 Accept 选择"
+                    .to_string(),
+            content,
+            ..Default::default()
+        })
+    }
+
+    fn inferred_responses(
+        ctx: &mut GenContext,
+        operation: &mut Operation,
+    ) -> Vec<(Option<u16>, aide::openapi::Response)> {
+        Self::operation_response(ctx, operation)
+            .map(|response| vec![(Some(200), response)])
+            .unwrap_or_default()
+    }
+}
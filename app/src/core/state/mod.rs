@@ -2,9 +2,17 @@ mod runtime;
 
 pub use runtime::AppStateConfig;
 
-use crate::{AppConfig, AppError, ValidationError, shared::jwt::JwtService};
+use crate::{
+    AppConfig, AppError, ConfigError, ValidationError,
+    shared::{
+        Cache, IdGenerator, LocalStorage, PublicId, PublicIdCodec, Storage,
+        id::{hostname_machine_id, lease_machine_id},
+        jwt::JwtService,
+    },
+};
 use deadpool_redis::Pool as RedisPool;
 use sea_orm::{ConnectOptions, Database, DatabaseConnection};
+use std::sync::Arc;
 use std::time::Duration;
 
 /// 应用程序运行时状态
@@ -19,9 +27,21 @@ pub struct AppState {
     /// Redis 连接池（可选）
     pub redis: Option<RedisPool>,
 
+    /// 缓存（配置了 Redis 时缓存在 Redis，否则缓存在进程内存）
+    pub cache: Cache,
+
     /// JWT 服务
     pub jwt_service: JwtService,
 
+    /// 分布式 ID 生成器
+    pub id_generator: IdGenerator,
+
+    /// 文件存储（公开，通过 /static 对外提供）
+    pub storage: Arc<dyn Storage>,
+
+    /// 私有文件存储（如个人数据导出文件，仅能经鉴权接口读取）
+    pub private_storage: Arc<dyn Storage>,
+
     /// 应用状态配置
     pub config: AppStateConfig,
 }
@@ -29,7 +49,7 @@ pub struct AppState {
 impl AppState {
     /// 初始化应用状态
     ///
-    /// 根据应用配置创建数据库连接池和运行时状态。
+    /// 根据应用配置创建数据库连接池和运行时状态，并安装全局对外 ID 编码器。
     ///
     /// # 参数
     ///
@@ -45,14 +65,29 @@ impl AppState {
     pub async fn init(app_config: &AppConfig) -> Result<Self, AppError> {
         let db = Self::create_db_connection(app_config).await?;
         let redis = Self::create_redis_pool(app_config).await?;
+        let cache = Cache::from_config(redis.as_ref(), &app_config.cache);
         let jwt_service = JwtService::new(app_config.clone().secrets.jwt_secret.clone());
+        PublicId::install(PublicIdCodec::new(&app_config.public_id)?)?;
+        let id_generator = Self::create_id_generator(app_config, redis.as_ref()).await?;
+        let storage = Arc::new(LocalStorage::new(&app_config.storage));
+        let private_storage = Arc::new(LocalStorage::private(&app_config.storage));
 
         Ok(AppState {
             db,
             redis,
+            cache,
             jwt_service,
+            id_generator,
+            storage,
+            private_storage,
             config: AppStateConfig {
                 jwt_secret: app_config.clone().secrets.jwt_secret,
+                avatar_max_size: app_config.storage.avatar_max_size,
+                import_max_size: app_config.storage.import_max_size,
+                deletion_grace_days: app_config.privacy.deletion_grace_days,
+                export_retention_days: app_config.privacy.export_retention_days,
+                export_timeout_minutes: app_config.privacy.export_timeout_minutes,
+                require_if_match: app_config.server.require_if_match,
             },
         })
     }
@@ -84,6 +119,13 @@ impl AppState {
                 )))
             })?);
 
+        // 单条语句最多执行全局请求超时：请求超时后被放弃的查询不会在数据库中长时间继续执行。
+        // 超时更长的路由（如批量导入）按批执行多条语句，流式导出在事务内单独解除限制
+        let statement_timeout = app_config.server.request_timeout().as_millis();
+        opt.map_sqlx_postgres_opts(move |pg_opts| {
+            pg_opts.options([("statement_timeout", statement_timeout)])
+        });
+
         Database::connect(opt).await.map_err(AppError::Database)
     }
 
@@ -114,4 +156,51 @@ impl AppState {
             }
         }
     }
+
+    /// 创建分布式 ID 生成器
+    ///
+    /// 机器 ID 的确定顺序：
+    /// 1. 配置或 `MACHINE_ID` 环境变量显式指定
+    /// 2. 配置了 Redis 时，以主机名派生值为起点通过 Redis 租约抢占，保证副本间不冲突
+    /// 3. 仅由主机名派生（单副本或主机名唯一的部署）
+    ///
+    /// # 参数
+    ///
+    /// * `app_config` - 应用配置对象
+    /// * `redis` - Redis 连接池（可选）
+    ///
+    /// # 返回值
+    ///
+    /// 成功返回 ID 生成器，无法确定机器 ID 或初始化失败时返回应用错误
+    async fn create_id_generator(
+        app_config: &AppConfig,
+        redis: Option<&RedisPool>,
+    ) -> Result<IdGenerator, AppError> {
+        let config = &app_config.id_generator;
+        let derived = hostname_machine_id();
+
+        let start_time = config.start_time().map_err(ConfigError::Invalid)?;
+        let generator = match (config.machine_id, redis) {
+            (Some(machine_id), _) => IdGenerator::new(machine_id, start_time),
+            (None, Some(pool)) => {
+                let lease =
+                    lease_machine_id(pool, derived.unwrap_or_default(), config.lease_ttl).await?;
+                IdGenerator::leased(&lease, start_time)
+            }
+            (None, None) => {
+                let machine_id = derived.ok_or_else(|| {
+                    ConfigError::Invalid("无法获取主机名，请通过 MACHINE_ID 指定机器 ID".to_string())
+                })?;
+                tracing::warn!(
+                    machine_id,
+                    "机器 ID 由主机名派生，不同主机可能冲突，仅适用于单副本部署；多副本请配置 Redis 或 MACHINE_ID"
+                );
+                IdGenerator::new(machine_id, start_time)
+            }
+        }
+        .map_err(|e| ConfigError::Invalid(format!("ID 生成器初始化失败：{}", e)))?;
+
+        tracing::info!(machine_id = generator.machine_id(), "ID 生成器已初始化");
+        Ok(generator)
+    }
 }
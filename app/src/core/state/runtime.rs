@@ -2,9 +2,27 @@ use serde::{Deserialize, Serialize};
 
 /// 应用状态运行时配置
 ///
-/// 存储应用在运行时需要的敏感配置信息和秘密。
+/// 存储应用在运行时需要的敏感配置信息、秘密和处理器用到的限制参数。
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppStateConfig {
     /// JWT 签名密钥，用于生成和验证令牌
     pub jwt_secret: String,
+
+    /// 头像上传大小上限，单位字节
+    pub avatar_max_size: usize,
+
+    /// 批量导入文件大小上限，单位字节
+    pub import_max_size: usize,
+
+    /// 注销后到匿名化个人信息的宽限期，单位天
+    pub deletion_grace_days: u64,
+
+    /// 数据导出文件保留时间，单位天
+    pub export_retention_days: u64,
+
+    /// 导出任务超时时间，单位分钟
+    pub export_timeout_minutes: u64,
+
+    /// 修改资源时是否必须携带 `If-Match`
+    pub require_if_match: bool,
 }
@@ -4,7 +4,7 @@ use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use thiserror::Error;
 
-use crate::response::{ApiError, ApiResponse, Domain, ErrorDetail, Reason};
+use crate::response::{ApiError, ApiResponse, Domain, ErrorCase, ErrorCases, ErrorDetail, Reason};
 
 #[derive(Debug, Error)]
 pub enum AuthError {
@@ -32,6 +32,9 @@ pub enum AuthError {
     #[error("无效的访问令牌")]
     InvalidToken,
 
+    #[error("没有执行此操作的权限")]
+    PermissionDenied,
+
     #[error("内部错误: {0}")]
     Internal(String),
 }
@@ -63,6 +66,9 @@ impl IntoResponse for AuthError {
             Self::InvalidToken => ApiError::new(StatusCode::UNAUTHORIZED, self.to_string())
                 .with_detail(ErrorDetail::new(Domain::AUTH, Reason::InvalidToken)),
 
+            Self::PermissionDenied => ApiError::new(StatusCode::FORBIDDEN, self.to_string())
+                .with_detail(ErrorDetail::new(Domain::AUTH, Reason::PermissionDenied)),
+
             Self::Internal(ref msg) => {
                 tracing::error!(error = %msg, "auth internal error");
                 ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
@@ -71,3 +77,40 @@ impl IntoResponse for AuthError {
         ApiResponse::error(api_error).into_response()
     }
 }
+
+impl ErrorCases for AuthError {
+    fn error_cases() -> Vec<ErrorCase> {
+        vec![
+            ErrorCase::new(StatusCode::CONFLICT, Domain::AUTH, Reason::AlreadyExists),
+            ErrorCase::new(StatusCode::NOT_FOUND, Domain::AUTH, Reason::UserNotFound),
+            ErrorCase::new(
+                StatusCode::UNAUTHORIZED,
+                Domain::AUTH,
+                Reason::InvalidPassword,
+            ),
+            ErrorCase::new(
+                StatusCode::BAD_REQUEST,
+                Domain::AUTH,
+                Reason::InvalidUsername,
+            ),
+            ErrorCase::new(StatusCode::BAD_REQUEST, Domain::AUTH, Reason::WeakPassword),
+            ErrorCase::new(
+                StatusCode::BAD_REQUEST,
+                Domain::AUTH,
+                Reason::PasswordMismatch,
+            ),
+            ErrorCase::new(
+                StatusCode::FORBIDDEN,
+                Domain::AUTH,
+                Reason::AuthenticationFailed,
+            ),
+            ErrorCase::new(StatusCode::UNAUTHORIZED, Domain::AUTH, Reason::InvalidToken),
+            ErrorCase::new(
+                StatusCode::FORBIDDEN,
+                Domain::AUTH,
+                Reason::PermissionDenied,
+            ),
+            ErrorCase::INTERNAL,
+        ]
+    }
+}
@@ -0,0 +1,75 @@
+//! 用户批量导入相关错误
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use thiserror::Error;
+
+use crate::response::{ApiError, ApiResponse, Domain, ErrorCase, ErrorCases, ErrorDetail, Reason};
+
+#[derive(Debug, Error)]
+pub enum BulkImportError {
+    #[error("不支持的导入格式，仅支持 CSV 和 NDJSON")]
+    UnsupportedFormat,
+
+    #[error("导入文件无法解析: {0}")]
+    InvalidFile(String),
+
+    #[error("导入行数超出限制: 最多 {0} 行")]
+    TooManyRows(usize),
+
+    /// 逐行校验失败，每个元素对应一行的一个错误
+    #[error("导入数据校验失败，共 {} 处错误", .0.len())]
+    InvalidRows(Vec<ErrorDetail>),
+}
+
+impl IntoResponse for BulkImportError {
+    fn into_response(self) -> Response {
+        let api_error = match self {
+            Self::UnsupportedFormat => {
+                ApiError::new(StatusCode::UNSUPPORTED_MEDIA_TYPE, self.to_string())
+                    .with_detail(ErrorDetail::new(Domain::FILE, Reason::FileTypeNotAllowed))
+            }
+
+            Self::InvalidFile(_) => ApiError::new(StatusCode::BAD_REQUEST, self.to_string())
+                .with_detail(ErrorDetail::new(Domain::FILE, Reason::InvalidFormat)),
+
+            Self::TooManyRows(_) => ApiError::new(StatusCode::BAD_REQUEST, self.to_string())
+                .with_detail(ErrorDetail::new(Domain::FILE, Reason::ValueOutOfRange)),
+
+            Self::InvalidRows(ref details) => {
+                ApiError::new(StatusCode::UNPROCESSABLE_ENTITY, self.to_string())
+                    .with_details(details.clone())
+            }
+        };
+        ApiResponse::error(api_error).into_response()
+    }
+}
+
+impl ErrorCases for BulkImportError {
+    /// `InvalidRows` 的详情按行给出，这里列出导入校验会产生的典型原因
+    fn error_cases() -> Vec<ErrorCase> {
+        vec![
+            ErrorCase::new(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                Domain::FILE,
+                Reason::FileTypeNotAllowed,
+            ),
+            ErrorCase::new(StatusCode::BAD_REQUEST, Domain::FILE, Reason::InvalidFormat),
+            ErrorCase::new(
+                StatusCode::BAD_REQUEST,
+                Domain::FILE,
+                Reason::ValueOutOfRange,
+            ),
+            ErrorCase::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Domain::USER,
+                Reason::InvalidFormat,
+            ),
+            ErrorCase::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Domain::USER,
+                Reason::AlreadyExists,
+            ),
+        ]
+    }
+}
@@ -4,7 +4,7 @@ use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use thiserror::Error;
 
-use crate::response::{ApiError, ApiResponse};
+use crate::response::{ApiError, ApiResponse, ErrorCase, ErrorCases};
 
 #[derive(Debug, Error)]
 pub enum ConfigError {
@@ -43,3 +43,9 @@ impl From<std::num::ParseIntError> for ConfigError {
         Self::Parse(e.to_string())
     }
 }
+
+impl ErrorCases for ConfigError {
+    fn error_cases() -> Vec<ErrorCase> {
+        vec![ErrorCase::INTERNAL]
+    }
+}
@@ -4,7 +4,7 @@ use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use thiserror::Error;
 
-use crate::response::{ApiError, ApiResponse, Domain, ErrorDetail, Reason};
+use crate::response::{ApiError, ApiResponse, Domain, ErrorCase, ErrorCases, ErrorDetail, Reason};
 
 #[derive(Debug, Error)]
 pub enum FileUploadError {
@@ -17,6 +17,9 @@ pub enum FileUploadError {
     #[error("文件类型不允许: {0}")]
     TypeNotAllowed(String),
 
+    #[error("图片无法解析: {0}")]
+    InvalidImage(String),
+
     #[error("上传失败: {0}")]
     Failed(String),
 
@@ -38,6 +41,9 @@ impl IntoResponse for FileUploadError {
                     .with_detail(ErrorDetail::new(Domain::FILE, Reason::FileTypeNotAllowed))
             }
 
+            Self::InvalidImage(_) => ApiError::new(StatusCode::BAD_REQUEST, self.to_string())
+                .with_detail(ErrorDetail::new(Domain::FILE, Reason::InvalidFormat)),
+
             Self::Failed(ref msg) => {
                 tracing::error!(error = %msg, "file upload failed");
                 ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
@@ -49,3 +55,27 @@ impl IntoResponse for FileUploadError {
         ApiResponse::error(api_error).into_response()
     }
 }
+
+impl ErrorCases for FileUploadError {
+    fn error_cases() -> Vec<ErrorCase> {
+        vec![
+            ErrorCase::new(StatusCode::BAD_REQUEST, Domain::FILE, Reason::InvalidFormat),
+            ErrorCase::new(
+                StatusCode::BAD_REQUEST,
+                Domain::FILE,
+                Reason::RequiredFieldMissing,
+            ),
+            ErrorCase::new(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                Domain::FILE,
+                Reason::FileTooLarge,
+            ),
+            ErrorCase::new(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                Domain::FILE,
+                Reason::FileTypeNotAllowed,
+            ),
+            ErrorCase::INTERNAL,
+        ]
+    }
+}
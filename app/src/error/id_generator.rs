@@ -0,0 +1,13 @@
+//! 分布式 ID 生成器相关错误
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum IdGeneratorError {
+    #[error("ID 生成失败: {0}")]
+    Generate(#[from] sonyflake::Error),
+
+    /// Redis 中的机器 ID 租约已丢失，继续生成可能与接手该机器 ID 的副本冲突
+    #[error("机器 ID {0} 的租约已丢失，重新获取租约前暂停生成 ID")]
+    LeaseLost(u16),
+}
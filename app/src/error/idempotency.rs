@@ -0,0 +1,92 @@
+//! 幂等请求（`Idempotency-Key`）相关错误
+
+use axum::http::header::RETRY_AFTER;
+use axum::http::{HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use thiserror::Error;
+
+use crate::response::{
+    ApiError, ApiResponse, Domain, ErrorCase, ErrorCases, ErrorDetail, Reason, Status,
+};
+
+/// 处理中冲突时建议客户端等待的秒数
+const IN_PROGRESS_RETRY_AFTER: u32 = 1;
+
+#[derive(Debug, Error)]
+pub enum IdempotencyError {
+    /// key 为空、过长或包含不可见字符
+    #[error("Idempotency-Key 必须为 1 到 255 个可见 ASCII 字符")]
+    InvalidKey,
+
+    /// 同一 key 已用于方法、路径或请求体不同的请求
+    ///
+    /// 对应 `FAILED_PRECONDITION`：客户端应为新请求生成新的 key，不应原样重试。
+    #[error("Idempotency-Key 已用于内容不同的请求")]
+    KeyReused,
+
+    /// 同一 key 的首个请求仍在处理中
+    ///
+    /// 对应 `ABORTED`：客户端可按 `Retry-After` 稍后重试，届时将得到首个请求的响应。
+    #[error("相同 Idempotency-Key 的请求仍在处理中，请稍后重试")]
+    InProgress,
+}
+
+impl IntoResponse for IdempotencyError {
+    fn into_response(self) -> Response {
+        let api_error = match self {
+            Self::InvalidKey => ApiError::new(StatusCode::BAD_REQUEST, self.to_string())
+                .with_detail(
+                    ErrorDetail::with_message(
+                        Domain::VALIDATION,
+                        Reason::InvalidFormat,
+                        self.to_string(),
+                    )
+                    .at("Idempotency-Key", "header"),
+                ),
+
+            Self::KeyReused => ApiError::new(StatusCode::CONFLICT, self.to_string())
+                .with_status(Status::FailedPrecondition)
+                .with_detail(
+                    ErrorDetail::new(Domain::GLOBAL, Reason::IdempotencyKeyReused)
+                        .at("Idempotency-Key", "header"),
+                ),
+
+            Self::InProgress => ApiError::new(StatusCode::CONFLICT, self.to_string())
+                .with_status(Status::Aborted)
+                .with_detail(
+                    ErrorDetail::new(Domain::GLOBAL, Reason::RequestInProgress)
+                        .at("Idempotency-Key", "header"),
+                ),
+        };
+
+        let mut response = ApiResponse::error(api_error).into_response();
+        if matches!(self, Self::InProgress) {
+            response
+                .headers_mut()
+                .insert(RETRY_AFTER, HeaderValue::from(IN_PROGRESS_RETRY_AFTER));
+        }
+        response
+    }
+}
+
+impl ErrorCases for IdempotencyError {
+    fn error_cases() -> Vec<ErrorCase> {
+        vec![
+            ErrorCase::new(
+                StatusCode::BAD_REQUEST,
+                Domain::VALIDATION,
+                Reason::InvalidFormat,
+            ),
+            ErrorCase::new(
+                StatusCode::CONFLICT,
+                Domain::GLOBAL,
+                Reason::IdempotencyKeyReused,
+            ),
+            ErrorCase::new(
+                StatusCode::CONFLICT,
+                Domain::GLOBAL,
+                Reason::RequestInProgress,
+            ),
+        ]
+    }
+}
@@ -3,23 +3,39 @@
 //! 所有错误类型统一转换为 Google JSON Style Guide 格式的响应。
 
 mod auth;
+mod bulk_import;
 mod config;
 mod file_upload;
+mod id_generator;
+mod idempotency;
+mod precondition;
+mod privacy;
 mod redis;
+mod rejection;
 mod validation;
 
 use aide::OperationOutput;
+use aide::generate::GenContext;
+use aide::openapi::Operation;
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use thiserror::Error;
 
-use crate::response::{ApiError, ApiResponse};
+use crate::response::{
+    ApiError, ApiResponse, ErrorCase, ErrorCases, error_response_docs, error_responses,
+};
 
 pub use auth::AuthError;
+pub use bulk_import::BulkImportError;
 pub use config::ConfigError;
 pub use file_upload::FileUploadError;
+pub use id_generator::IdGeneratorError;
+pub use idempotency::IdempotencyError;
+pub use precondition::PreconditionError;
+pub use privacy::PrivacyError;
 pub use redis::RedisError;
-pub use validation::ValidationError;
+pub use rejection::{ParsePosition, RejectionError};
+pub use validation::{FieldLocation, ValidationError};
 
 /// 应用程序错误
 #[derive(Debug, Error)]
@@ -30,12 +46,27 @@ pub enum AppError {
     #[error(transparent)]
     Validation(#[from] ValidationError),
 
+    #[error(transparent)]
+    Rejection(#[from] RejectionError),
+
+    #[error(transparent)]
+    BulkImport(#[from] BulkImportError),
+
     #[error(transparent)]
     Config(#[from] ConfigError),
 
     #[error(transparent)]
     FileUpload(#[from] FileUploadError),
 
+    #[error(transparent)]
+    Privacy(#[from] PrivacyError),
+
+    #[error(transparent)]
+    Precondition(#[from] PreconditionError),
+
+    #[error(transparent)]
+    Idempotency(#[from] IdempotencyError),
+
     #[error(transparent)]
     Redis(#[from] RedisError),
 
@@ -58,8 +89,13 @@ impl IntoResponse for AppError {
             // 委托给具体错误类型
             Self::Auth(e) => e.into_response(),
             Self::Validation(e) => e.into_response(),
+            Self::Rejection(e) => e.into_response(),
+            Self::BulkImport(e) => e.into_response(),
             Self::Config(e) => e.into_response(),
             Self::FileUpload(e) => e.into_response(),
+            Self::Privacy(e) => e.into_response(),
+            Self::Precondition(e) => e.into_response(),
+            Self::Idempotency(e) => e.into_response(),
             Self::Redis(e) => e.into_response(),
 
             Self::Database(e) => {
@@ -121,4 +157,72 @@ impl From<validator::ValidationErrors> for AppError {
 
 impl OperationOutput for AppError {
     type Inner = ();
+
+    fn operation_response(
+        ctx: &mut GenContext,
+        _operation: &mut Operation,
+    ) -> Option<aide::openapi::Response> {
+        Some(error_response_docs(ctx))
+    }
+
+    /// 只推断通用的 500，具体错误由文档函数通过 `ErrorDocs::errors` 声明
+    fn inferred_responses(
+        ctx: &mut GenContext,
+        _operation: &mut Operation,
+    ) -> Vec<(Option<u16>, aide::openapi::Response)> {
+        error_responses(ctx, &[ErrorCase::INTERNAL])
+    }
+}
+
+impl ErrorCases for AppError {
+    fn error_cases() -> Vec<ErrorCase> {
+        <(
+            AuthError,
+            ValidationError,
+            RejectionError,
+            BulkImportError,
+            FileUploadError,
+            PrivacyError,
+            PreconditionError,
+            IdempotencyError,
+        )>::error_cases()
+    }
 }
+
+/// 具体错误类型直接作为处理器错误时，按声明的错误生成响应文档
+macro_rules! impl_error_output {
+    ($($error:ty),+ $(,)?) => {
+        $(
+            impl OperationOutput for $error {
+                type Inner = ();
+
+                fn operation_response(
+                    ctx: &mut GenContext,
+                    _operation: &mut Operation,
+                ) -> Option<aide::openapi::Response> {
+                    Some(error_response_docs(ctx))
+                }
+
+                fn inferred_responses(
+                    ctx: &mut GenContext,
+                    _operation: &mut Operation,
+                ) -> Vec<(Option<u16>, aide::openapi::Response)> {
+                    error_responses(ctx, &Self::error_cases())
+                }
+            }
+        )+
+    };
+}
+
+impl_error_output!(
+    AuthError,
+    ValidationError,
+    RejectionError,
+    BulkImportError,
+    ConfigError,
+    FileUploadError,
+    PrivacyError,
+    PreconditionError,
+    IdempotencyError,
+    RedisError,
+);
@@ -0,0 +1,59 @@
+//! 条件请求（乐观并发控制）相关错误
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use thiserror::Error;
+
+use crate::response::{
+    ApiError, ApiResponse, Domain, ErrorCase, ErrorCases, ErrorDetail, Reason, Status,
+};
+
+#[derive(Debug, Error)]
+pub enum PreconditionError {
+    /// `If-Match` 与资源当前版本不一致，或写入时版本已被并发修改
+    ///
+    /// 对应 `ABORTED`：客户端应重新读取资源，在最新版本上重做修改。
+    #[error("资源已被修改，请重新获取后再提交")]
+    Failed,
+
+    /// 配置要求修改资源时携带 `If-Match`，但请求未携带
+    ///
+    /// 对应 `FAILED_PRECONDITION`：客户端需要先读取资源拿到 ETag，不应原样重试。
+    #[error("修改资源时必须携带 If-Match 请求头")]
+    Required,
+}
+
+impl IntoResponse for PreconditionError {
+    fn into_response(self) -> Response {
+        let api_error = match self {
+            Self::Failed => ApiError::new(StatusCode::PRECONDITION_FAILED, self.to_string())
+                .with_status(Status::Aborted)
+                .with_detail(ErrorDetail::new(Domain::GLOBAL, Reason::PreconditionFailed)),
+
+            Self::Required => ApiError::new(StatusCode::PRECONDITION_REQUIRED, self.to_string())
+                .with_status(Status::FailedPrecondition)
+                .with_detail(
+                    ErrorDetail::new(Domain::GLOBAL, Reason::PreconditionRequired)
+                        .at("If-Match", "header"),
+                ),
+        };
+        ApiResponse::error(api_error).into_response()
+    }
+}
+
+impl ErrorCases for PreconditionError {
+    fn error_cases() -> Vec<ErrorCase> {
+        vec![
+            ErrorCase::new(
+                StatusCode::PRECONDITION_FAILED,
+                Domain::GLOBAL,
+                Reason::PreconditionFailed,
+            ),
+            ErrorCase::new(
+                StatusCode::PRECONDITION_REQUIRED,
+                Domain::GLOBAL,
+                Reason::PreconditionRequired,
+            ),
+        ]
+    }
+}
@@ -0,0 +1,47 @@
+//! 个人数据导出和账号注销相关错误
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use thiserror::Error;
+
+use crate::response::{ApiError, ApiResponse, Domain, ErrorCase, ErrorCases, ErrorDetail, Reason};
+
+#[derive(Debug, Error)]
+pub enum PrivacyError {
+    #[error("导出任务不存在")]
+    ExportNotFound,
+
+    #[error("导出文件尚未生成")]
+    ExportNotReady,
+
+    #[error("内部错误: {0}")]
+    Internal(String),
+}
+
+impl IntoResponse for PrivacyError {
+    fn into_response(self) -> Response {
+        let api_error = match self {
+            Self::ExportNotFound => ApiError::new(StatusCode::NOT_FOUND, self.to_string())
+                .with_detail(ErrorDetail::new(Domain::USER, Reason::NotFound)),
+
+            Self::ExportNotReady => ApiError::new(StatusCode::CONFLICT, self.to_string())
+                .with_detail(ErrorDetail::new(Domain::USER, Reason::Conflict)),
+
+            Self::Internal(ref msg) => {
+                tracing::error!(error = %msg, "privacy operation failed");
+                ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+        };
+        ApiResponse::error(api_error).into_response()
+    }
+}
+
+impl ErrorCases for PrivacyError {
+    fn error_cases() -> Vec<ErrorCase> {
+        vec![
+            ErrorCase::new(StatusCode::NOT_FOUND, Domain::USER, Reason::NotFound),
+            ErrorCase::new(StatusCode::CONFLICT, Domain::USER, Reason::Conflict),
+            ErrorCase::INTERNAL,
+        ]
+    }
+}
@@ -4,7 +4,7 @@ use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use thiserror::Error;
 
-use crate::response::{ApiError, ApiResponse};
+use crate::response::{ApiError, ApiResponse, ErrorCase, ErrorCases};
 
 #[derive(Debug, Error)]
 pub enum RedisError {
@@ -28,3 +28,9 @@ impl IntoResponse for RedisError {
         .into_response()
     }
 }
+
+impl ErrorCases for RedisError {
+    fn error_cases() -> Vec<ErrorCase> {
+        vec![ErrorCase::INTERNAL]
+    }
+}
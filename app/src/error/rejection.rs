@@ -0,0 +1,418 @@
+//! 请求解析（提取器拒绝）相关错误
+//!
+//! 请求体、查询字符串和路径参数无法解析时返回的错误，统一使用 `validation` 域。
+//! JSON、MessagePack、CBOR 请求体无法解码返回 400，结构合法但字段类型或取值不符返回 422，
+//! 媒体类型或请求体编码不受支持返回 415，请求体超出大小限制返回 413；解析错误的行列号和字节偏移写入错误消息，
+//! JSON 解析错误的原因和行列号同时作为消息参数，供其他语言的译文使用。
+
+use axum::extract::path::ErrorKind;
+use axum::extract::rejection::{BytesRejection, PathRejection};
+use axum::http::header::ACCEPT_ENCODING;
+use axum::http::{HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use serde_path_to_error::Segment;
+use thiserror::Error;
+
+use super::validation::escape_pointer_token;
+use crate::core::{BodyEncoding, DecodeError};
+use crate::response::{ApiError, ApiResponse, Domain, ErrorCase, ErrorCases, ErrorDetail, Reason};
+
+/// 解析错误在请求体中的位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParsePosition {
+    /// 行号（从 1 开始）
+    pub line: usize,
+
+    /// 列号（从 1 开始，按字节计）
+    pub column: usize,
+
+    /// 字节偏移（从 0 开始）
+    pub offset: usize,
+}
+
+impl std::fmt::Display for ParsePosition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "第 {} 行第 {} 列，字节偏移 {}",
+            self.line, self.column, self.offset
+        )
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum RejectionError {
+    #[error(
+        "不支持的媒体类型，请求体必须为 JSON、MessagePack 或 CBOR\
+         （Content-Type: application/json、application/msgpack 或 application/cbor）"
+    )]
+    UnsupportedMediaType,
+
+    /// 请求体的 `Content-Encoding` 不受支持（或未启用请求体解压）
+    #[error("不支持的请求体编码 {encoding}，支持的编码：{supported}")]
+    UnsupportedEncoding { encoding: String, supported: String },
+
+    /// JSON 语法错误或请求体不完整
+    #[error("请求体不是合法的 JSON: {message}（{position}）")]
+    JsonSyntax {
+        message: String,
+        position: ParsePosition,
+    },
+
+    /// JSON 语法正确，但字段缺失、类型或取值不符
+    #[error("请求体字段无效: {message}（{position}）")]
+    JsonData {
+        pointer: String,
+        message: String,
+        position: ParsePosition,
+    },
+
+    /// MessagePack / CBOR 请求体无法解码
+    #[error("请求体不是合法的 {media_type}: {message}")]
+    BinarySyntax {
+        media_type: &'static str,
+        message: String,
+    },
+
+    /// MessagePack / CBOR 请求体解码成功，但字段缺失、类型或取值不符
+    #[error("请求体字段无效: {message}")]
+    BinaryData { pointer: String, message: String },
+
+    #[error("查询参数无效: {message}")]
+    Query { pointer: String, message: String },
+
+    #[error("路径参数无效: {message}")]
+    Path {
+        key: Option<String>,
+        message: String,
+    },
+
+    /// 读取请求体失败（如超出大小限制），沿用底层提取器的状态码
+    #[error("{message}")]
+    Body { status: StatusCode, message: String },
+
+    /// 路由与提取器定义不匹配等编程错误
+    #[error("请求解析内部错误: {0}")]
+    Internal(String),
+}
+
+impl RejectionError {
+    /// 从 serde_json 的反序列化错误创建
+    ///
+    /// # 参数
+    /// * `error` - 带字段路径的反序列化错误
+    /// * `body` - 原始请求体，用于由行列号计算字节偏移
+    pub fn from_json(error: serde_path_to_error::Error<serde_json::Error>, body: &[u8]) -> Self {
+        Self::json_at(path_to_pointer(error.path()), error.inner(), body)
+    }
+
+    /// 从不带字段路径的 serde_json 错误创建（如 JSON 值之后的多余内容）
+    pub fn from_json_error(error: &serde_json::Error, body: &[u8]) -> Self {
+        Self::json_at(String::new(), error, body)
+    }
+
+    fn json_at(mut pointer: String, inner: &serde_json::Error, body: &[u8]) -> Self {
+        let position = ParsePosition {
+            line: inner.line(),
+            column: inner.column(),
+            offset: byte_offset(body, inner.line(), inner.column()),
+        };
+        let message = strip_position(&inner.to_string(), inner.line(), inner.column());
+
+        match inner.classify() {
+            serde_json::error::Category::Data => {
+                // 缺失字段时 serde 报告的路径是外层对象，补上字段名指向缺失的字段本身
+                if let Some(field) = missing_field(&message) {
+                    pointer = format!("{}/{}", pointer, escape_pointer_token(field));
+                }
+                Self::JsonData {
+                    pointer,
+                    message,
+                    position,
+                }
+            }
+            _ => Self::JsonSyntax { message, position },
+        }
+    }
+
+    /// 从 MessagePack / CBOR 请求体的解码错误创建
+    pub fn from_decode(encoding: BodyEncoding, error: DecodeError) -> Self {
+        match error {
+            DecodeError::Syntax(message) => Self::BinarySyntax {
+                media_type: encoding.media_type(),
+                message,
+            },
+            DecodeError::Data { path, message } => {
+                let mut pointer = path_to_pointer(&path);
+                if let Some(field) = missing_field(&message) {
+                    pointer = format!("{}/{}", pointer, escape_pointer_token(field));
+                }
+                Self::BinaryData { pointer, message }
+            }
+        }
+    }
+
+    /// 从查询字符串的反序列化错误创建
+    pub fn from_query(error: serde_path_to_error::Error<serde_urlencoded::de::Error>) -> Self {
+        let mut pointer = path_to_pointer(error.path());
+        let message = error.inner().to_string();
+        if let Some(field) = missing_field(&message) {
+            pointer = format!("{}/{}", pointer, escape_pointer_token(field));
+        }
+        Self::Query { pointer, message }
+    }
+}
+
+impl From<PathRejection> for RejectionError {
+    fn from(rejection: PathRejection) -> Self {
+        match rejection {
+            PathRejection::FailedToDeserializePathParams(error) => {
+                if error.status().is_server_error() {
+                    return Self::Internal(error.body_text());
+                }
+                let key = match error.kind() {
+                    ErrorKind::ParseErrorAtKey { key, .. }
+                    | ErrorKind::DeserializeError { key, .. }
+                    | ErrorKind::InvalidUtf8InPathParam { key } => Some(key.clone()),
+                    _ => None,
+                };
+                Self::Path {
+                    key,
+                    message: error.body_text(),
+                }
+            }
+            other => Self::Internal(other.body_text()),
+        }
+    }
+}
+
+impl From<BytesRejection> for RejectionError {
+    fn from(rejection: BytesRejection) -> Self {
+        Self::Body {
+            status: rejection.status(),
+            message: rejection.body_text(),
+        }
+    }
+}
+
+/// 将 serde 报告的字段路径转换为 JSON Pointer（根路径为空字符串）
+fn path_to_pointer(path: &serde_path_to_error::Path) -> String {
+    path.iter()
+        .filter_map(|segment| match segment {
+            Segment::Seq { index } => Some(index.to_string()),
+            Segment::Map { key } => Some(escape_pointer_token(key)),
+            Segment::Enum { variant } => Some(escape_pointer_token(variant)),
+            Segment::Unknown => None,
+        })
+        .map(|token| format!("/{token}"))
+        .collect()
+}
+
+/// 从 `missing field `name`` 形式的消息中取出字段名
+fn missing_field(message: &str) -> Option<&str> {
+    message
+        .strip_prefix("missing field `")?
+        .split_once('`')
+        .map(|(field, _)| field)
+}
+
+/// 去掉 serde_json 附加在消息末尾的 ` at line X column Y`
+fn strip_position(message: &str, line: usize, column: usize) -> String {
+    let suffix = format!(" at line {line} column {column}");
+    message
+        .strip_suffix(suffix.as_str())
+        .unwrap_or(message)
+        .to_string()
+}
+
+/// 由行列号计算字节偏移
+///
+/// serde_json 的列号按字节计、从 1 开始；列号为 0 表示错误位于换行符处。
+fn byte_offset(body: &[u8], line: usize, column: usize) -> usize {
+    let line_start = match line {
+        0 | 1 => 0,
+        _ => body
+            .iter()
+            .enumerate()
+            .filter(|(_, byte)| **byte == b'\n')
+            .nth(line - 2)
+            .map_or(body.len(), |(index, _)| index + 1),
+    };
+    (line_start + column.saturating_sub(1)).min(body.len())
+}
+
+impl IntoResponse for RejectionError {
+    fn into_response(self) -> Response {
+        let api_error = match self {
+            Self::UnsupportedMediaType => {
+                ApiError::new(StatusCode::UNSUPPORTED_MEDIA_TYPE, self.to_string()).with_detail(
+                    ErrorDetail::with_message(
+                        Domain::VALIDATION,
+                        Reason::UnsupportedMediaType,
+                        self.to_string(),
+                    )
+                    .at("Content-Type", "header"),
+                )
+            }
+
+            Self::UnsupportedEncoding { ref supported, .. } => {
+                let api_error = ApiError::new(StatusCode::UNSUPPORTED_MEDIA_TYPE, self.to_string())
+                    .with_detail(
+                        ErrorDetail::with_message(
+                            Domain::VALIDATION,
+                            Reason::UnsupportedMediaType,
+                            self.to_string(),
+                        )
+                        .at("Content-Encoding", "header"),
+                    );
+                // RFC 7694：415 响应通过 Accept-Encoding 告知客户端可用的请求体编码
+                let mut response = ApiResponse::error(api_error).into_response();
+                if let Ok(value) = HeaderValue::from_str(supported) {
+                    response.headers_mut().insert(ACCEPT_ENCODING, value);
+                }
+                return response;
+            }
+
+            Self::JsonSyntax {
+                ref message,
+                position,
+            } => ApiError::new(StatusCode::BAD_REQUEST, self.to_string()).with_detail(
+                ErrorDetail::with_message(
+                    Domain::VALIDATION,
+                    Reason::InvalidFormat,
+                    self.to_string(),
+                )
+                .at("", "body")
+                .with_param("cause", message)
+                .with_param("line", position.line)
+                .with_param("column", position.column),
+            ),
+
+            Self::BinarySyntax { .. } => ApiError::new(StatusCode::BAD_REQUEST, self.to_string())
+                .with_detail(
+                    ErrorDetail::with_message(
+                        Domain::VALIDATION,
+                        Reason::InvalidFormat,
+                        self.to_string(),
+                    )
+                    .at("", "body"),
+                ),
+
+            Self::JsonData {
+                ref pointer,
+                ref message,
+                position,
+            } => {
+                let reason = data_reason(message);
+                ApiError::new(StatusCode::UNPROCESSABLE_ENTITY, self.to_string()).with_detail(
+                    ErrorDetail::with_message(Domain::VALIDATION, reason, self.to_string())
+                        .at(pointer.clone(), "body")
+                        .with_param("cause", message)
+                        .with_param("line", position.line)
+                        .with_param("column", position.column),
+                )
+            }
+
+            Self::BinaryData {
+                ref pointer,
+                ref message,
+            } => {
+                let reason = data_reason(message);
+                ApiError::new(StatusCode::UNPROCESSABLE_ENTITY, self.to_string()).with_detail(
+                    ErrorDetail::with_message(Domain::VALIDATION, reason, self.to_string())
+                        .at(pointer.clone(), "body"),
+                )
+            }
+
+            Self::Query {
+                ref pointer,
+                ref message,
+            } => {
+                let reason = data_reason(message);
+                ApiError::new(StatusCode::BAD_REQUEST, self.to_string()).with_detail(
+                    ErrorDetail::with_message(Domain::VALIDATION, reason, self.to_string())
+                        .at(pointer.clone(), "query"),
+                )
+            }
+
+            Self::Path { ref key, .. } => {
+                let pointer = key
+                    .as_deref()
+                    .map(|key| format!("/{}", escape_pointer_token(key)))
+                    .unwrap_or_default();
+                ApiError::new(StatusCode::BAD_REQUEST, self.to_string()).with_detail(
+                    ErrorDetail::with_message(
+                        Domain::VALIDATION,
+                        Reason::InvalidFormat,
+                        self.to_string(),
+                    )
+                    .at(pointer, "path"),
+                )
+            }
+
+            Self::Body { status, .. } => {
+                let reason = if status == StatusCode::PAYLOAD_TOO_LARGE {
+                    Reason::PayloadTooLarge
+                } else {
+                    Reason::InvalidFormat
+                };
+                ApiError::new(status, self.to_string()).with_detail(
+                    ErrorDetail::with_message(Domain::VALIDATION, reason, self.to_string())
+                        .at("", "body"),
+                )
+            }
+
+            Self::Internal(ref message) => {
+                tracing::error!(error = %message, "request extractor misconfigured");
+                ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            }
+        };
+        ApiResponse::error(api_error).into_response()
+    }
+}
+
+fn data_reason(message: &str) -> Reason {
+    if missing_field(message).is_some() {
+        Reason::RequiredFieldMissing
+    } else {
+        Reason::InvalidFormat
+    }
+}
+
+impl ErrorCases for RejectionError {
+    fn error_cases() -> Vec<ErrorCase> {
+        vec![
+            ErrorCase::new(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                Domain::VALIDATION,
+                Reason::UnsupportedMediaType,
+            ),
+            ErrorCase::new(
+                StatusCode::BAD_REQUEST,
+                Domain::VALIDATION,
+                Reason::InvalidFormat,
+            ),
+            ErrorCase::new(
+                StatusCode::BAD_REQUEST,
+                Domain::VALIDATION,
+                Reason::RequiredFieldMissing,
+            ),
+            ErrorCase::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Domain::VALIDATION,
+                Reason::InvalidFormat,
+            ),
+            ErrorCase::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Domain::VALIDATION,
+                Reason::RequiredFieldMissing,
+            ),
+            ErrorCase::new(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                Domain::VALIDATION,
+                Reason::PayloadTooLarge,
+            ),
+            ErrorCase::INTERNAL,
+        ]
+    }
+}
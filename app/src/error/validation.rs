@@ -3,55 +3,188 @@
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use thiserror::Error;
+use validator::ValidationErrorsKind;
 
-use crate::response::{ApiError, ApiResponse, Domain, ErrorDetail, Reason};
+use crate::response::{ApiError, ApiResponse, Domain, ErrorCase, ErrorCases, ErrorDetail, Reason};
 
 #[derive(Debug, Error)]
 pub enum ValidationError {
-    #[error("{0}")]
-    Fields(String),
+    /// 字段级校验失败，每个字段一个错误详情
+    #[error("{}", summarize(.0))]
+    Fields(Vec<ErrorDetail>),
 
     #[error("{0}")]
     Custom(String),
 }
 
+/// 校验失败字段所在的位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldLocation {
+    /// 请求体
+    Body,
+
+    /// 查询字符串
+    Query,
+
+    /// 路径参数
+    Path,
+}
+
+impl FieldLocation {
+    /// `location_type` 字段的取值
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Body => "body",
+            Self::Query => "query",
+            Self::Path => "path",
+        }
+    }
+}
+
 impl ValidationError {
-    /// 从 validator::ValidationErrors 创建
+    /// 从 validator::ValidationErrors 创建，字段位置视为请求体
     pub fn from_validator(errors: validator::ValidationErrors) -> Self {
-        let messages: Vec<String> = errors
-            .field_errors()
-            .iter()
-            .map(|(field, errs)| {
-                let msgs = errs
-                    .iter()
-                    .filter_map(|e| e.message.as_ref())
-                    .map(|m| m.to_string())
-                    .collect::<Vec<_>>()
-                    .join(", ");
-                format!(
-                    "{}: {}",
-                    field,
-                    if msgs.is_empty() {
-                        "验证失败"
-                    } else {
-                        &msgs
-                    }
-                )
-            })
-            .collect();
-        Self::Fields(messages.join("; "))
+        Self::from_validator_at(errors, FieldLocation::Body)
+    }
+
+    /// 从 validator::ValidationErrors 创建，并指定字段所在位置
+    ///
+    /// 每个失败的字段、每条失败的约束各生成一个错误详情，`location` 为 JSON Pointer
+    /// （如 `/address/city`、`/items/0/name`），嵌套结构体和列表元素递归展开。
+    ///
+    /// # 参数
+    /// * `errors` - validator 的校验结果
+    /// * `location` - 字段所在位置（请求体、查询字符串或路径参数）
+    pub fn from_validator_at(errors: validator::ValidationErrors, location: FieldLocation) -> Self {
+        let mut details = Vec::new();
+        collect_details(&errors, "", location, &mut details);
+        // HashMap 迭代顺序不固定，按位置排序保证响应稳定（同一字段内保持约束声明顺序）
+        details.sort_by(|a, b| a.location.cmp(&b.location));
+        Self::Fields(details)
     }
 
     pub fn custom(msg: impl Into<String>) -> Self {
         Self::Custom(msg.into())
     }
+
+    /// 字段级错误详情（自定义错误返回空切片）
+    pub fn details(&self) -> &[ErrorDetail] {
+        match self {
+            Self::Fields(details) => details,
+            Self::Custom(_) => &[],
+        }
+    }
+}
+
+/// 递归展开 validator 的错误树
+fn collect_details(
+    errors: &validator::ValidationErrors,
+    pointer: &str,
+    location: FieldLocation,
+    details: &mut Vec<ErrorDetail>,
+) {
+    for (field, kind) in errors.errors() {
+        let pointer = format!("{}/{}", pointer, escape_pointer_token(field));
+        match kind {
+            ValidationErrorsKind::Field(errs) => {
+                details.extend(errs.iter().map(|error| {
+                    let reason = reason_for_code(&error.code);
+                    let message = error
+                        .message
+                        .as_ref()
+                        .map(|m| m.to_string())
+                        .unwrap_or_else(|| default_message(reason).to_string());
+                    let detail = ErrorDetail::with_message(Domain::VALIDATION, reason, message)
+                        .at(pointer.clone(), location.as_str());
+                    // 约束参数（如 min、max）用于本地化消息，字段取值可能是密码等敏感数据，不保留
+                    error
+                        .params
+                        .iter()
+                        .filter(|(name, _)| *name != "value")
+                        .fold(detail, |detail, (name, value)| match value {
+                            serde_json::Value::String(value) => {
+                                detail.with_param(name.as_ref(), value)
+                            }
+                            value => detail.with_param(name.as_ref(), value),
+                        })
+                }));
+            }
+            ValidationErrorsKind::Struct(nested) => {
+                collect_details(nested, &pointer, location, details);
+            }
+            ValidationErrorsKind::List(items) => {
+                for (index, nested) in items {
+                    collect_details(nested, &format!("{pointer}/{index}"), location, details);
+                }
+            }
+        }
+    }
+}
+
+/// 将 validator 的错误码映射为错误原因
+pub fn reason_for_code(code: &str) -> Reason {
+    match code {
+        "length" => Reason::InvalidLength,
+        "email" => Reason::InvalidEmail,
+        "range" => Reason::ValueOutOfRange,
+        "required" => Reason::RequiredFieldMissing,
+        _ => Reason::InvalidFormat,
+    }
+}
+
+fn default_message(reason: Reason) -> &'static str {
+    match reason {
+        Reason::InvalidLength => "长度不符合要求",
+        Reason::InvalidEmail => "邮箱格式无效",
+        Reason::ValueOutOfRange => "取值超出允许范围",
+        Reason::RequiredFieldMissing => "缺少必需字段",
+        _ => "格式无效",
+    }
+}
+
+/// 按 RFC 6901 转义 JSON Pointer 中的 `~` 和 `/`
+pub(super) fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+fn summarize(details: &[ErrorDetail]) -> String {
+    details
+        .iter()
+        .map(|detail| match &detail.location {
+            Some(location) => format!("{}: {}", location, detail.message),
+            None => detail.message.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
 }
 
 impl IntoResponse for ValidationError {
     fn into_response(self) -> Response {
-        let api_error = ApiError::new(StatusCode::BAD_REQUEST, self.to_string()).with_detail(
-            ErrorDetail::with_message(Domain::VALIDATION, Reason::InvalidFormat, self.to_string()),
-        );
+        let api_error = match self {
+            Self::Fields(ref details) => ApiError::new(StatusCode::BAD_REQUEST, self.to_string())
+                .with_details(details.clone()),
+            Self::Custom(_) => ApiError::new(StatusCode::BAD_REQUEST, self.to_string())
+                .with_detail(ErrorDetail::with_message(
+                    Domain::VALIDATION,
+                    Reason::InvalidFormat,
+                    self.to_string(),
+                )),
+        };
         ApiResponse::error(api_error).into_response()
     }
 }
+
+impl ErrorCases for ValidationError {
+    fn error_cases() -> Vec<ErrorCase> {
+        [
+            Reason::InvalidLength,
+            Reason::InvalidEmail,
+            Reason::ValueOutOfRange,
+            Reason::RequiredFieldMissing,
+            Reason::InvalidFormat,
+        ]
+        .into_iter()
+        .map(|reason| ErrorCase::new(StatusCode::BAD_REQUEST, Domain::VALIDATION, reason))
+        .collect()
+    }
+}
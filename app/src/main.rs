@@ -4,21 +4,22 @@ use aide::transform::TransformOpenApi;
 use app::*;
 use axum::body::Body;
 use axum::error_handling::HandleErrorLayer;
+use axum::extract::DefaultBodyLimit;
 use axum::http::Request;
-use axum::http::StatusCode;
 use axum::http::header::CONTENT_TYPE;
-use axum::{BoxError, Extension, routing::get};
+use axum::{Extension, routing::get};
 use migration::{Migrator, MigratorTrait};
 use serde_json::{Value, json};
 use std::sync::Arc;
 use tokio::signal;
 use tower::ServiceBuilder;
 use tower::buffer::BufferLayer;
+use tower::load_shed::LoadShedLayer;
 use tower_governor::{GovernorLayer, governor::GovernorConfigBuilder};
 use tower_http::compression::CompressionLayer;
 use tower_http::services::ServeDir;
 use tower_http::trace::TraceLayer;
-use tracing::{Level, error, info, instrument};
+use tracing::{Level, error, info, instrument, warn};
 
 /// 健康检查端点
 ///
@@ -107,12 +108,32 @@ async fn main() -> Result<(), AppError> {
         }
     }
 
+    // 启动个人数据清理任务（匿名化宽限期已过的注销账号、删除过期导出文件）
+    let purge_state = app_state.clone();
+    let purge_interval = config.privacy.purge_interval;
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(tokio::time::Duration::from_secs(purge_interval * 3600));
+        loop {
+            interval.tick().await;
+            match user::purge_expired_personal_data(&purge_state).await {
+                Ok(0) => {}
+                Ok(purged) => info!("已匿名化 {} 个注销账号", purged),
+                Err(e) => tracing::error!("个人数据清理任务出错: {}", e),
+            }
+        }
+    });
+
     // 构建路由
     let mut api = OpenApi::default();
 
     // 构建基础路由
+    // /static 优先提供内置静态资源，未命中时回退到上传文件的本地存储目录
     let mut app = ApiRouter::new()
-        .nest_service("/static", ServeDir::new("app/assets"))
+        .nest_service(
+            "/static",
+            ServeDir::new("app/assets").fallback(ServeDir::new(&config.storage.local_dir)),
+        )
         .route("/health", get(health_check))
         .route("/", get(hello_world))
         .route("/favicon.ico", get(favicon))
@@ -130,6 +151,10 @@ async fn main() -> Result<(), AppError> {
         config.cors.allow_origins, config.cors.allow_credentials
     );
 
+    // 安全响应头，/docs 等页面按路径前缀使用各自的内容安全策略
+    let security_headers = middleware::SecurityHeaders::from_config(&config.security_headers)
+        .map_err(ConfigError::Invalid)?;
+
     // 配置速率限制
     // 注意：在本地开发环境中，SmartIpKeyExtractor 可能无法正确提取 IP 地址
     // 生产环境中，确保配置了正确的 ConnectInfo 中间件
@@ -142,6 +167,33 @@ async fn main() -> Result<(), AppError> {
             .unwrap(),
     );
     info!("⚡ 速率限制已启用: 每秒10个请求，突发20个请求");
+    info!(
+        "⏱️ 请求超时 {} 秒，按路由覆盖 {:?}",
+        config.server.timeout, config.server.route_timeouts
+    );
+
+    // 分页链接（受信任代理与分页响应头）
+    let pagination_links =
+        middleware::PaginationLinks::from_config(&config.server, &config.response)
+            .map_err(ConfigError::Invalid)?;
+
+    // 请求体大小限制，上传路由的上限由存储配置允许的文件大小得出
+    let body_limit = middleware::BodyLimit::from_config(&config.body_limit)
+        .with_upload_route("/v1/user/me/avatar", config.storage.avatar_max_size)
+        .with_upload_route("/v1/user/import", config.storage.import_max_size);
+
+    // 幂等请求（Idempotency-Key），配置了 Redis 时多副本共享记录；
+    // 批量导入携带 Idempotency-Key 时按导入文件上限读取请求体计算指纹
+    let idempotency =
+        middleware::Idempotency::from_state(&app_state, &config.server, &config.idempotency)
+            .map_err(ConfigError::Invalid)?
+            .with_route_body_size("/v1/user/import", body_limit.limit_for("/v1/user/import"));
+    if config.idempotency.enabled && app_state.redis.is_none() {
+        warn!("未配置 Redis，幂等记录保存在进程内存中，多副本部署时无法跨副本重放");
+    }
+    if config.cache.enabled && app_state.redis.is_none() {
+        warn!("未配置 Redis，缓存保存在进程内存中，多副本部署时失效不会同步到其他副本");
+    }
 
     // 应用所有中间件
     let app = app
@@ -149,38 +201,75 @@ async fn main() -> Result<(), AppError> {
         .fallback(handle_404)
         .layer(
             ServiceBuilder::new()
+                // 安全响应头（HSTS、CSP 等），最外层使错误、404 和 CORS 预检响应同样带上
+                .layer(axum::middleware::from_fn_with_state(
+                    security_headers,
+                    middleware::security_headers_middleware,
+                ))
                 // CORS 跨域配置
                 .layer(cors_layer)
-                // 基于 IP 的速率限制
-                .layer(GovernorLayer::new(general_limiter))
-                // 错误处理层（处理 GovernorLayer 和其他中间件的错误）
-                .layer(HandleErrorLayer::new(|err: BoxError| async move {
-                    (
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        format!("Unhandled error: {}", err),
-                    )
-                }))
+                // 请求 ID（沿用网关传入的合法 X-Request-ID）与 W3C traceparent 追踪上下文
+                .layer(axum::middleware::from_fn(middleware::request_id_middleware))
+                // 错误响应格式协商（Google JSON / RFC 9457 Problem Details）
+                .layer(axum::middleware::from_fn_with_state(
+                    config.response.clone(),
+                    middleware::error_format_middleware,
+                ))
+                // 消息语言协商（Accept-Language），本地化错误消息
+                .layer(axum::middleware::from_fn_with_state(
+                    config.response.clone(),
+                    middleware::locale_middleware,
+                ))
+                // 响应编码协商（JSON / MessagePack / CBOR）
+                .layer(axum::middleware::from_fn(middleware::encoding_middleware))
+                // 还原请求原始 URL（受信任代理的 X-Forwarded-*），分页列表追加 Link 与 X-Total-Count
+                .layer(axum::middleware::from_fn_with_state(
+                    pagination_links,
+                    middleware::pagination_links_middleware,
+                ))
+                // 部分响应（fields 查询参数），按 schema 校验并裁剪 data
+                .layer(axum::middleware::from_fn(middleware::fields_middleware))
+                // 405 响应改写为 JSON 错误（保留 Allow 头）
+                .layer(axum::middleware::from_fn(method_not_allowed_middleware))
+                // 基于 IP 的速率限制（超限返回 429 JSON 并保留限流响应头）
+                .layer(GovernorLayer::new(general_limiter).error_handler(handle_rate_limit_error))
+                // 请求体大小限制（全局 body_limit.max_size，route_limits 按路径前缀覆盖），超出返回 413；
+                // gzip / deflate / br 编码的请求体边读边解压，上限按解压后的大小计算
+                .layer(axum::middleware::from_fn_with_state(
+                    body_limit,
+                    middleware::body_limit_middleware,
+                ))
+                // 大小已由上一层限制，关闭提取器默认的 2 MiB 上限
+                .layer(DefaultBodyLimit::disable())
+                // 请求超时（全局 server.timeout，server.route_timeouts 按路径前缀覆盖），超时返回 504
+                .layer(axum::middleware::from_fn_with_state(
+                    middleware::RequestTimeout::from_config(&config.server),
+                    middleware::timeout_middleware,
+                ))
+                // 错误处理层（将超时、过载等中间件错误映射为统一格式的错误响应）
+                .layer(HandleErrorLayer::new(middleware::handle_middleware_error))
+                // 缓冲区已满时立即拒绝（503），而不是无限排队
+                .layer(LoadShedLayer::new())
                 // 缓冲层
                 .layer(BufferLayer::new(1024))
                 // HTTP 响应压缩（gzip/deflate/brotli）
                 .layer(CompressionLayer::new())
-                // 请求 ID 中间件（用于追踪）
-                .layer(axum::middleware::from_fn(middleware::request_id_middleware))
-                // 请求追踪和日志
+                // 幂等请求：重放携带相同 Idempotency-Key 的 POST 的首次成功响应，基于未压缩的响应体保存
+                .layer(axum::middleware::from_fn_with_state(
+                    idempotency,
+                    middleware::idempotency_middleware,
+                ))
+                // ETag 与条件请求（If-None-Match 命中返回 304），基于未压缩的响应体计算
+                .layer(axum::middleware::from_fn(middleware::etag_middleware))
+                // 请求追踪和日志（请求 ID 与追踪 ID 由外层 request_id_middleware 的 span 记录）
                 .layer(
                     TraceLayer::new_for_http().make_span_with(|request: &Request<Body>| {
-                        let request_id = request
-                            .headers()
-                            .get("x-request-id")
-                            .and_then(|v| v.to_str().ok())
-                            .unwrap_or("unknown");
                         tracing::span!(
                             Level::DEBUG,
                             "request",
                             method = display(request.method()),
                             uri = display(request.uri()),
                             version = debug(request.version()),
-                            request_id = request_id
                         )
                     }),
                 ),
@@ -265,6 +354,10 @@ async fn favicon() -> impl IntoApiResponse {
 fn api_docs(api: TransformOpenApi) -> TransformOpenApi {
     api.title("DropBuddy API Documentation")
         .summary("API for the DropBuddy platform")
+        // 所有接口的错误响应：application/json 或 application/problem+json
+        .default_response_with::<AppError, _>(|res| {
+            res.description("错误响应，格式按 Accept 头协商（Google JSON 或 RFC 9457）")
+        })
         // .description(include_str!("README.md")) 
         .tag(Tag {
             name: "❤️💕".into(),
@@ -1,11 +1,25 @@
 use askama::Template;
 use axum::{
     extract::Request,
-    http::StatusCode,
+    http::{
+        HeaderMap, StatusCode,
+        header::{ACCEPT, ALLOW, CONTENT_SECURITY_POLICY, CONTENT_TYPE},
+    },
+    middleware::Next,
     response::{Html, IntoResponse, Response},
 };
 use chrono::Utc;
 
+use crate::core::response::{ApiError, ApiResponse, Domain, ErrorDetail, Reason, media_quality};
+
+/// 总是返回 JSON 错误的 API 路径前缀
+pub const API_PREFIXES: &[&str] = &["/v1"];
+
+/// 404 页面的内容安全策略：页面只有内联样式和按钮的内联事件处理器
+///
+/// 由页面自己设置，安全响应头中间件不会再用 API 的默认策略覆盖。
+pub const NOT_FOUND_CSP: &str = "default-src 'none'; style-src 'unsafe-inline'; script-src 'unsafe-inline'; frame-ancestors 'none'";
+
 #[derive(Template)]
 #[template(path = "404.html")]
 pub struct NotFoundTemplate {
@@ -26,11 +40,43 @@ impl NotFoundTemplate {
     }
 }
 
-// 404 错误处理器
+/// 是否按浏览器页面导航响应 HTML
+///
+/// API 前缀下的路径总是返回 JSON；其他路径只有 `Accept` 显式包含 `text/html`
+/// 且优先级不低于 JSON 时才返回 HTML，未携带 `Accept` 或只有 `*/*` 时返回 JSON。
+pub fn prefers_html(path: &str, headers: &HeaderMap) -> bool {
+    let is_api = API_PREFIXES.iter().any(|prefix| {
+        path.strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    });
+    if is_api {
+        return false;
+    }
+    let Some(accept) = headers.get(ACCEPT).and_then(|v| v.to_str().ok()) else {
+        return false;
+    };
+    let html = media_quality(accept, "text/html");
+    let json = media_quality(accept, "application/json")
+        .max(media_quality(accept, "application/problem+json"));
+    html > 0.0 && html >= json
+}
+
+/// 404 错误处理器
+///
+/// 浏览器页面导航渲染 404 页面，其他请求（API 路径或偏好 JSON）返回统一格式的 JSON 错误。
 pub async fn handle_404(request: Request) -> Response {
     let path = request.uri().path().to_string();
 
     let headers = request.headers();
+    if !prefers_html(&path, headers) {
+        let message = format!("路径 {} 不存在", path);
+        return ApiResponse::error(
+            ApiError::new(StatusCode::NOT_FOUND, message.clone()).with_detail(
+                ErrorDetail::with_message(Domain::GLOBAL, Reason::NotFound, message),
+            ),
+        )
+        .into_response();
+    }
 
     let request_id = headers
         .get("x-request-id")
@@ -49,10 +95,41 @@ pub async fn handle_404(request: Request) -> Response {
     let template = NotFoundTemplate::new(path, request_id);
 
     match template.render() {
-        Ok(html) => (StatusCode::NOT_FOUND, Html(html)).into_response(),
+        Ok(html) => (
+            StatusCode::NOT_FOUND,
+            [(CONTENT_SECURITY_POLICY, NOT_FOUND_CSP)],
+            Html(html),
+        )
+            .into_response(),
         Err(err) => {
             tracing::error!("Failed to render 404 template: {}", err);
             (StatusCode::INTERNAL_SERVER_ERROR, "Internal Server Error").into_response()
         }
     }
 }
+
+/// 405 错误处理中间件
+///
+/// 将路由返回的空 405 响应改写为统一格式的 JSON 错误，保留 `Allow` 响应头。
+/// 以中间件实现，嵌套路由（如 `/v1`）中的 405 同样生效。
+pub async fn method_not_allowed_middleware(request: Request, next: Next) -> Response {
+    let method = request.method().clone();
+    let response = next.run(request).await;
+    if response.status() != StatusCode::METHOD_NOT_ALLOWED
+        || response.headers().contains_key(CONTENT_TYPE)
+    {
+        return response;
+    }
+
+    let message = format!("不支持 {} 请求方法", method);
+    let mut json = ApiResponse::error(
+        ApiError::new(StatusCode::METHOD_NOT_ALLOWED, message.clone()).with_detail(
+            ErrorDetail::with_message(Domain::GLOBAL, Reason::MethodNotAllowed, message),
+        ),
+    )
+    .into_response();
+    if let Some(allow) = response.headers().get(ALLOW) {
+        json.headers_mut().insert(ALLOW, allow.clone());
+    }
+    json
+}
@@ -0,0 +1,107 @@
+//! 头像图片处理
+//!
+//! 按文件头魔数识别图片格式（不信任客户端声明的 Content-Type），
+//! 解码后统一重新编码为 PNG，丢弃 EXIF 等元数据，并生成固定尺寸的缩略图。
+
+use std::io::Cursor;
+
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat, ImageReader, Limits};
+
+use crate::error::FileUploadError;
+
+/// 头像主图边长（像素）
+pub const AVATAR_SIZE: u32 = 512;
+
+/// 缩略图边长（像素）
+pub const THUMBNAIL_SIZES: [u32; 2] = [128, 64];
+
+/// 解码时允许的最大宽高，防止像素炸弹
+const MAX_DIMENSION: u32 = 8192;
+
+/// 解码时允许的最大内存分配
+const MAX_DECODE_ALLOC: u64 = 256 * 1024 * 1024;
+
+/// 处理后的头像（均为 PNG 编码）
+#[derive(Debug)]
+pub struct ProcessedAvatar {
+    /// 头像主图
+    pub avatar: Vec<u8>,
+
+    /// 缩略图列表（边长, PNG 数据）
+    pub thumbnails: Vec<(u32, Vec<u8>)>,
+}
+
+/// 根据文件头魔数识别图片格式
+///
+/// 仅识别允许上传的格式（PNG、JPEG、GIF、WebP），其他格式返回 None。
+pub fn sniff_image_format(bytes: &[u8]) -> Option<ImageFormat> {
+    match bytes {
+        [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, ..] => Some(ImageFormat::Png),
+        [0xFF, 0xD8, 0xFF, ..] => Some(ImageFormat::Jpeg),
+        [b'G', b'I', b'F', b'8', b'7' | b'9', b'a', ..] => Some(ImageFormat::Gif),
+        [b'R', b'I', b'F', b'F', _, _, _, _, rest @ ..] if rest.starts_with(b"WEBP") => {
+            Some(ImageFormat::WebP)
+        }
+        _ => None,
+    }
+}
+
+/// 处理上传的头像
+///
+/// CPU 密集，在异步上下文中应通过 `spawn_blocking` 调用。
+///
+/// # 参数
+/// * `bytes` - 上传的原始文件内容
+///
+/// # 返回
+/// 成功返回重新编码后的主图和缩略图；格式不支持返回 `TypeNotAllowed`，
+/// 解码失败（文件损坏、尺寸超限）返回 `InvalidImage`
+pub fn process_avatar(bytes: &[u8]) -> Result<ProcessedAvatar, FileUploadError> {
+    let format = sniff_image_format(bytes).ok_or_else(|| {
+        FileUploadError::TypeNotAllowed("仅支持 PNG、JPEG、GIF、WebP 图片".to_string())
+    })?;
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_DIMENSION);
+    limits.max_image_height = Some(MAX_DIMENSION);
+    limits.max_alloc = Some(MAX_DECODE_ALLOC);
+
+    let mut reader = ImageReader::with_format(Cursor::new(bytes), format);
+    reader.limits(limits);
+    let image = reader
+        .decode()
+        .map_err(|e| FileUploadError::InvalidImage(e.to_string()))?;
+
+    let avatar = image.resize_to_fill(AVATAR_SIZE, AVATAR_SIZE, FilterType::Lanczos3);
+    let thumbnails = THUMBNAIL_SIZES
+        .iter()
+        .map(|&size| {
+            let thumbnail = avatar.resize_to_fill(size, size, FilterType::Lanczos3);
+            encode_png(&thumbnail).map(|data| (size, data))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ProcessedAvatar {
+        avatar: encode_png(&avatar)?,
+        thumbnails,
+    })
+}
+
+/// 头像主图的存储 key
+pub fn avatar_key(prefix: &str) -> String {
+    format!("{prefix}/avatar.png")
+}
+
+/// 缩略图的存储 key
+pub fn thumbnail_key(prefix: &str, size: u32) -> String {
+    format!("{prefix}/{size}.png")
+}
+
+fn encode_png(image: &DynamicImage) -> Result<Vec<u8>, FileUploadError> {
+    let mut buffer = Cursor::new(Vec::new());
+    image
+        .write_to(&mut buffer, ImageFormat::Png)
+        .map_err(|e| FileUploadError::Failed(format!("图片编码失败：{}", e)))?;
+    Ok(buffer.into_inner())
+}
@@ -0,0 +1,218 @@
+//! 用户批量导入导出
+//!
+//! 导入支持 CSV（首行为表头）和 NDJSON（每行一个 JSON 对象）两种格式，
+//! 字段为 `username`、`email`、`password`；解析按行进行，单行出错不影响其他行，
+//! 以便在预检（dry-run）模式下一次性报告所有问题。
+//! 导出为 CSV 或 NDJSON（按 `Accept` 协商），逐行编码后流式输出。
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, FixedOffset};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub use crate::core::response::escape_formula;
+use crate::core::response::{CsvRecord, encode_csv_line};
+
+/// 单次导入允许的最大行数
+pub const MAX_IMPORT_ROWS: usize = 10_000;
+
+/// 导出 CSV 的表头
+pub const EXPORT_CSV_HEADER: [&str; 6] =
+    ["id", "username", "email", "status", "role", "created_at"];
+
+/// 导入文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// 逗号分隔，首行为表头
+    Csv,
+
+    /// 每行一个 JSON 对象
+    Ndjson,
+}
+
+impl ImportFormat {
+    /// 根据上传字段的 Content-Type 或文件扩展名识别格式
+    pub fn detect(content_type: Option<&str>, file_name: Option<&str>) -> Option<Self> {
+        let mime = content_type
+            .and_then(|value| value.split(';').next())
+            .map(|value| value.trim().to_ascii_lowercase());
+        match mime.as_deref() {
+            Some("text/csv") => return Some(Self::Csv),
+            Some("application/x-ndjson" | "application/jsonl" | "application/jsonlines") => {
+                return Some(Self::Ndjson);
+            }
+            _ => {}
+        }
+
+        let extension = file_name?.rsplit_once('.')?.1.to_ascii_lowercase();
+        match extension.as_str() {
+            "csv" => Some(Self::Csv),
+            "ndjson" | "jsonl" => Some(Self::Ndjson),
+            _ => None,
+        }
+    }
+}
+
+/// 导入文件中的一行用户数据
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImportRow {
+    /// 用户名
+    pub username: String,
+
+    /// 邮箱
+    pub email: String,
+
+    /// 明文密码（导入时哈希，不落盘）
+    pub password: String,
+}
+
+/// 带行号的解析结果
+///
+/// 行号从 1 开始，对应原始文件中的行（CSV 表头为第 1 行）。
+#[derive(Debug)]
+pub struct ParsedRow {
+    /// 原始文件中的行号
+    pub line: usize,
+
+    /// 解析结果，失败时为错误描述
+    pub row: Result<ImportRow, String>,
+}
+
+/// 导入文件中已出现的用户名和邮箱
+///
+/// 邮箱按小写比较，`A@x.com` 与 `a@x.com` 视为重复。
+#[derive(Debug, Default)]
+pub struct ImportKeys {
+    usernames: HashSet<String>,
+    emails: HashSet<String>,
+}
+
+impl ImportKeys {
+    /// 用户名和邮箱都未出现过时记录该行并返回 true
+    ///
+    /// 任一重复时两者都不记录，避免被拒绝的行让之后的行误报重复。
+    pub fn insert(&mut self, row: &ImportRow) -> bool {
+        let email = row.email.to_lowercase();
+        if self.usernames.contains(&row.username) || self.emails.contains(&email) {
+            return false;
+        }
+        self.usernames.insert(row.username.clone());
+        self.emails.insert(email);
+        true
+    }
+
+    /// 拆分为用户名和小写邮箱，用于查询数据库中的冲突
+    pub fn into_parts(self) -> (HashSet<String>, HashSet<String>) {
+        (self.usernames, self.emails)
+    }
+}
+
+/// 解析导入文件
+///
+/// 空行会被跳过；结构错误（缺列、JSON 非法等）记录在对应行的结果中。
+/// 整个文件无法解析（如 CSV 表头缺少必需列）时返回错误。
+pub fn parse_import(bytes: &[u8], format: ImportFormat) -> Result<Vec<ParsedRow>, String> {
+    match format {
+        ImportFormat::Csv => parse_csv(bytes),
+        ImportFormat::Ndjson => parse_ndjson(bytes),
+    }
+}
+
+fn parse_csv(bytes: &[u8]) -> Result<Vec<ParsedRow>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(bytes);
+
+    let headers = reader
+        .headers()
+        .map_err(|e| format!("无法读取 CSV 表头：{}", e))?
+        .clone();
+    for required in ["username", "email", "password"] {
+        if !headers.iter().any(|header| header == required) {
+            return Err(format!("CSV 表头缺少必需列：{}", required));
+        }
+    }
+
+    let rows = reader
+        .records()
+        .map(|record| match record {
+            Ok(record) => ParsedRow {
+                line: record.position().map_or(0, |p| p.line() as usize),
+                row: record
+                    .deserialize::<ImportRow>(Some(&headers))
+                    .map_err(|e| e.to_string()),
+            },
+            Err(error) => ParsedRow {
+                line: error.position().map_or(0, |p| p.line() as usize),
+                row: Err(error.to_string()),
+            },
+        })
+        .collect();
+    Ok(rows)
+}
+
+fn parse_ndjson(bytes: &[u8]) -> Result<Vec<ParsedRow>, String> {
+    let text = std::str::from_utf8(bytes).map_err(|e| format!("文件不是有效的 UTF-8：{}", e))?;
+    let rows = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| ParsedRow {
+            line: index + 1,
+            row: serde_json::from_str(line).map_err(|e| e.to_string()),
+        })
+        .collect();
+    Ok(rows)
+}
+
+/// 导出的一行用户数据
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ExportUserRow {
+    /// 对外用户 ID
+    pub id: String,
+
+    /// 用户名
+    pub username: String,
+
+    /// 邮箱
+    pub email: String,
+
+    /// 用户状态
+    pub status: String,
+
+    /// 用户角色
+    pub role: String,
+
+    /// 注册时间
+    pub created_at: DateTime<FixedOffset>,
+}
+
+/// 将表头编码为 CSV 行
+pub fn csv_header() -> Vec<u8> {
+    encode_csv_line(&EXPORT_CSV_HEADER)
+}
+
+/// 将一行用户数据编码为 CSV 行（含换行符）
+pub fn csv_line(row: &ExportUserRow) -> Vec<u8> {
+    encode_csv_line(&row.csv_record())
+}
+
+impl CsvRecord for ExportUserRow {
+    fn csv_header() -> Vec<&'static str> {
+        EXPORT_CSV_HEADER.to_vec()
+    }
+
+    /// 用户可控的文本字段会做公式注入防护，避免在电子表格中打开时被当作公式执行。
+    fn csv_record(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            escape_formula(&self.username),
+            escape_formula(&self.email),
+            self.status.clone(),
+            self.role.clone(),
+            self.created_at.to_rfc3339(),
+        ]
+    }
+}
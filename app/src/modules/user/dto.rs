@@ -1,11 +1,16 @@
+use chrono::{DateTime, FixedOffset};
+use entity::enums::{ExportFormat, ExportStatus};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::shared::PublicId;
 
 /// 用户列表项
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct UserListItem {
     /// 用户ID
-    pub id: i32,
+    pub id: PublicId,
 
     /// 用户名
     pub username: String,
@@ -18,18 +23,24 @@ pub struct UserListItem {
 }
 
 /// 用户注册请求
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+///
+/// 校验规则同时用于注册接口和批量导入。
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Validate)]
 pub struct RegisterRequest {
     /// 用户名（3-20字符）
+    #[validate(length(min = 3, max = 20, message = "用户名长度必须在3-20个字符之间"))]
     pub username: String,
 
     /// 邮箱地址
+    #[validate(email(message = "邮箱格式无效"))]
     pub email: String,
 
     /// 密码（8字符以上）
+    #[validate(length(min = 8, message = "密码长度至少8个字符"))]
     pub password: String,
 
     /// 确认密码
+    #[validate(must_match(other = "password", message = "两次输入的密码不一致"))]
     pub password_confirm: String,
 }
 
@@ -37,13 +48,17 @@ pub struct RegisterRequest {
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RegisterResponse {
     /// 用户ID
-    pub id: i32,
+    pub id: PublicId,
 
     /// 用户名
     pub username: String,
 
     /// 邮箱
     pub email: String,
+
+    /// 头像 URL（未上传时为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
 }
 
 /// 用户登录请求
@@ -60,7 +75,7 @@ pub struct LoginRequest {
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct LoginResponse {
     /// 用户ID
-    pub id: i32,
+    pub id: PublicId,
 
     /// 用户名
     pub username: String,
@@ -74,3 +89,109 @@ pub struct LoginResponse {
     /// Token 过期时间（秒）
     pub expires_in: i64,
 }
+
+/// 头像缩略图
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AvatarThumbnail {
+    /// 边长（像素）
+    pub size: u32,
+
+    /// 访问 URL
+    pub url: String,
+}
+
+/// 头像上传响应
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AvatarResponse {
+    /// 头像主图 URL
+    pub url: String,
+
+    /// 缩略图列表
+    pub thumbnails: Vec<AvatarThumbnail>,
+}
+
+/// 个人数据导出请求
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct DataExportRequest {
+    /// 导出格式（json 或 zip，默认 json）
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+/// 个人数据导出任务
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DataExportResponse {
+    /// 导出任务 ID
+    pub id: String,
+
+    /// 导出格式
+    pub format: ExportFormat,
+
+    /// 任务状态
+    pub status: ExportStatus,
+
+    /// 创建时间
+    pub created_at: DateTime<FixedOffset>,
+
+    /// 完成时间（未完成时为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<FixedOffset>>,
+
+    /// 下载地址（完成后提供）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download_url: Option<String>,
+}
+
+/// 账号注销响应
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AccountDeletionResponse {
+    /// 注销时间
+    pub deleted_at: DateTime<FixedOffset>,
+
+    /// 预计匿名化个人信息的时间，此后数据不可恢复
+    pub purge_after: DateTime<FixedOffset>,
+}
+
+/// 批量导入查询参数
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ImportQuery {
+    /// 仅校验不写入，返回逐行校验结果（默认 false）
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// 批量导入的单行错误
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ImportRowError {
+    /// 原始文件中的行号（从 1 开始）
+    pub line: usize,
+
+    /// 出错的字段（整行错误时为空）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+
+    /// 错误原因
+    pub reason: String,
+
+    /// 错误消息
+    pub message: String,
+}
+
+/// 批量导入结果
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ImportReport {
+    /// 是否为预检模式
+    pub dry_run: bool,
+
+    /// 数据行总数
+    pub total: usize,
+
+    /// 校验通过的行数
+    pub valid: usize,
+
+    /// 实际创建的用户数（预检模式恒为 0）
+    pub imported: usize,
+
+    /// 逐行错误列表
+    pub errors: Vec<ImportRowError>,
+}
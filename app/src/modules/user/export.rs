@@ -0,0 +1,194 @@
+//! 个人数据导出
+//!
+//! 将用户的个人数据汇总为单个 JSON 文档或 ZIP 压缩包，供用户行使数据访问与可携带权。
+//! 目前导出账号资料、账号状态和历史导出记录；会话、审计日志等数据落库后在
+//! [`UserDataExport`] 中追加对应部分，并在 [`EXPORT_SECTIONS`] 中登记。
+
+use std::io::{Cursor, Write};
+
+use chrono::{DateTime, FixedOffset};
+use entity::enums::{ExportFormat, ExportStatus, UserStatus};
+use serde::Serialize;
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+/// 导出文件结构版本，结构发生不兼容变化时递增
+pub const EXPORT_SCHEMA_VERSION: u32 = 1;
+
+/// 导出文件包含的数据部分
+pub const EXPORT_SECTIONS: [&str; 3] = ["profile", "account", "exports"];
+
+/// 账号资料
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportProfile {
+    /// 对外用户 ID
+    pub id: String,
+
+    /// 用户名
+    pub username: String,
+
+    /// 邮箱
+    pub email: String,
+
+    /// 头像 URL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
+}
+
+/// 账号状态
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportAccount {
+    /// 账号状态
+    pub status: Option<UserStatus>,
+
+    /// 注册时间
+    pub created_at: DateTime<FixedOffset>,
+
+    /// 最后更新时间
+    pub updated_at: DateTime<FixedOffset>,
+
+    /// 注销时间
+    pub deleted_at: Option<DateTime<FixedOffset>>,
+
+    /// 最近一次吊销全部令牌的时间
+    pub tokens_valid_after: Option<DateTime<FixedOffset>>,
+}
+
+/// 历史导出记录
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportRecord {
+    /// 导出任务 ID
+    pub id: String,
+
+    /// 导出格式
+    pub format: Option<ExportFormat>,
+
+    /// 任务状态
+    pub status: Option<ExportStatus>,
+
+    /// 创建时间
+    pub created_at: DateTime<FixedOffset>,
+
+    /// 完成时间
+    pub completed_at: Option<DateTime<FixedOffset>>,
+}
+
+/// 用户个人数据
+///
+/// 包含系统为该用户保存的全部数据。不含会话和审计记录：登录令牌是无状态 JWT，
+/// 服务端不保存会话，唯一与会话相关的数据是 `account.tokens_valid_after`（令牌生效起点）；
+/// 系统也没有持久化的审计日志，请求日志不与用户关联保存。
+/// 以后新增会话或审计表时，需要在这里加入对应字段并提升 `schema_version`。
+#[derive(Debug, Clone, Serialize)]
+pub struct UserDataExport {
+    /// 导出文件结构版本
+    pub schema_version: u32,
+
+    /// 生成时间
+    pub generated_at: DateTime<FixedOffset>,
+
+    /// 账号资料
+    pub profile: ExportProfile,
+
+    /// 账号状态
+    pub account: ExportAccount,
+
+    /// 历史导出记录
+    pub exports: Vec<ExportRecord>,
+}
+
+/// 随 ZIP 一起打包的附件（如头像原图）
+#[derive(Debug)]
+pub struct ExportAttachment {
+    /// 压缩包内的文件路径
+    pub path: String,
+
+    /// 文件内容
+    pub bytes: Vec<u8>,
+}
+
+/// ZIP 压缩包中的清单文件
+#[derive(Serialize)]
+struct Manifest<'a> {
+    schema_version: u32,
+    generated_at: DateTime<FixedOffset>,
+    sections: &'a [&'a str],
+    attachments: Vec<&'a str>,
+}
+
+/// 导出文件在私有存储中的 key
+pub fn export_file_key(user_id: i32, export_id: i64, format: ExportFormat) -> String {
+    format!("exports/{user_id}/{export_id}.{}", file_extension(format))
+}
+
+/// 导出文件的扩展名
+pub fn file_extension(format: ExportFormat) -> &'static str {
+    match format {
+        ExportFormat::Json => "json",
+        ExportFormat::Zip => "zip",
+    }
+}
+
+/// 导出文件的 Content-Type
+pub fn content_type(format: ExportFormat) -> &'static str {
+    match format {
+        ExportFormat::Json => "application/json",
+        ExportFormat::Zip => "application/zip",
+    }
+}
+
+/// 生成 JSON 格式的导出文件
+pub fn build_json(data: &UserDataExport) -> Result<Vec<u8>, serde_json::Error> {
+    serde_json::to_vec_pretty(data)
+}
+
+/// 生成 ZIP 格式的导出文件
+///
+/// 压缩包内包含 `manifest.json`、每个数据部分一个 JSON 文件，以及附件。
+/// CPU 密集，在异步上下文中应通过 `spawn_blocking` 调用。
+pub fn build_zip(
+    data: &UserDataExport,
+    attachments: &[ExportAttachment],
+) -> Result<Vec<u8>, String> {
+    let manifest = Manifest {
+        schema_version: data.schema_version,
+        generated_at: data.generated_at,
+        sections: &EXPORT_SECTIONS,
+        attachments: attachments.iter().map(|a| a.path.as_str()).collect(),
+    };
+
+    let files = [
+        ("manifest.json", to_json(&manifest)?),
+        ("profile.json", to_json(&data.profile)?),
+        ("account.json", to_json(&data.account)?),
+        ("exports.json", to_json(&data.exports)?),
+    ];
+
+    let options = SimpleFileOptions::default();
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    let entries = files
+        .iter()
+        .map(|(path, bytes)| (*path, bytes.as_slice()))
+        .chain(
+            attachments
+                .iter()
+                .map(|a| (a.path.as_str(), a.bytes.as_slice())),
+        );
+    for (path, bytes) in entries {
+        writer
+            .start_file(path, options)
+            .map_err(|e| format!("写入压缩包失败：{}", e))?;
+        writer
+            .write_all(bytes)
+            .map_err(|e| format!("写入压缩包失败：{}", e))?;
+    }
+
+    let cursor = writer
+        .finish()
+        .map_err(|e| format!("写入压缩包失败：{}", e))?;
+    Ok(cursor.into_inner())
+}
+
+fn to_json<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, String> {
+    serde_json::to_vec_pretty(value).map_err(|e| format!("序列化导出数据失败：{}", e))
+}
@@ -1,14 +1,28 @@
 use crate::{
-    ApiResponse, AppError, AppState, Pagination, PaginationQuery, core::middleware::CurrentUser,
+    ApiResponse, AppError, AppState, IfMatch, Json, Pagination, PaginationQuery, Path, Query,
+    RequestUrl, ValidatedJson,
+    core::middleware::CurrentUser,
+    core::response::{ErrorDocs, StreamFormat, StreamingList},
+    error::{
+        AuthError, BulkImportError, FileUploadError, IdempotencyError, PreconditionError,
+        PrivacyError, ValidationError,
+    },
     shared::FromState,
+    version_tag,
 };
 use aide::transform::TransformOperation;
-use axum::Json;
-use axum::extract::{Extension, Query, State};
+use axum::extract::{Extension, Multipart, State};
+use axum::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE, HeaderValue};
+use axum::http::{HeaderMap, StatusCode};
 use std::sync::Arc;
 use tracing::{info, instrument};
 
-use super::dto::{LoginRequest, LoginResponse, RegisterRequest, RegisterResponse, UserListItem};
+use super::bulk::{self, ExportUserRow, ImportFormat};
+use super::dto::{
+    AccountDeletionResponse, AvatarResponse, DataExportRequest, DataExportResponse, ImportQuery,
+    ImportReport, LoginRequest, LoginResponse, RegisterRequest, RegisterResponse, UserListItem,
+};
+use super::export;
 use super::service::UserService;
 
 /// 获取用户列表处理器
@@ -17,13 +31,15 @@ use super::service::UserService;
 ///
 /// # 参数
 /// * `state` - 应用状态（包含数据库连接）
+/// * `url` - 请求原始 URL，用于生成分页链接
 /// * `query` - 分页查询参数（page、page_size）
 ///
 /// # 返回
-/// 成功返回带分页元数据的用户列表，失败返回错误
+/// 成功返回带分页元数据和分页链接的用户列表，失败返回错误
 #[instrument(skip(state))]
 pub async fn list_users(
     State(state): State<Arc<AppState>>,
+    url: RequestUrl,
     Query(query): Query<PaginationQuery>,
 ) -> Result<ApiResponse<UserListItem>, AppError> {
     let pagination = Pagination::from_query(&query)?;
@@ -41,7 +57,8 @@ pub async fn list_users(
         pagination.page as i64,
         pagination.page_size as i64,
     )
-    .with_kind("UserList"))
+    .with_kind("UserList")
+    .with_page_links(&url))
 }
 
 /// 获取用户列表 API 文档
@@ -49,11 +66,13 @@ pub fn list_users_docs(op: TransformOperation) -> TransformOperation {
     op.description("分页获取用户列表")
         .tag("用户")
         .response::<200, ApiResponse<UserListItem>>()
+        .errors::<(AuthError, ValidationError)>()
 }
 
 /// 用户注册处理器
 ///
-/// 处理用户注册请求，验证输入数据、哈希密码并创建新用户。
+/// 处理用户注册请求，按 `RegisterRequest` 的声明式规则校验输入、哈希密码并创建新用户。
+/// 客户端可携带 `Idempotency-Key`，网络重试时由幂等中间件重放首次成功的响应，不会重复注册。
 ///
 /// # 参数
 /// * `state` - 应用状态（包含数据库连接）
@@ -64,7 +83,7 @@ pub fn list_users_docs(op: TransformOperation) -> TransformOperation {
 #[instrument(skip(state))]
 pub async fn register(
     State(state): State<Arc<AppState>>,
-    Json(req): Json<RegisterRequest>,
+    ValidatedJson(req): ValidatedJson<RegisterRequest>,
 ) -> Result<ApiResponse<RegisterResponse>, AppError> {
     info!("处理用户注册请求: {}", req.username);
 
@@ -77,9 +96,10 @@ pub async fn register(
 
 /// 用户注册 API 文档
 pub fn register_docs(op: TransformOperation) -> TransformOperation {
-    op.description("用户注册")
+    op.description("用户注册（可携带 Idempotency-Key 请求头，重试时重放首次成功的响应）")
         .tag("认证")
         .response::<201, ApiResponse<RegisterResponse>>()
+        .errors::<(AuthError, IdempotencyError)>()
 }
 
 /// 用户登录处理器
@@ -111,6 +131,7 @@ pub fn login_docs(op: TransformOperation) -> TransformOperation {
     op.description("用户登录")
         .tag("认证")
         .response::<200, ApiResponse<LoginResponse>>()
+        .errors::<AuthError>()
 }
 
 /// 获取当前用户处理器
@@ -122,7 +143,8 @@ pub fn login_docs(op: TransformOperation) -> TransformOperation {
 /// * `current_user` - 当前登录用户（由认证中间件注入）
 ///
 /// # 返回
-/// 返回当前用户信息（ID、用户名、邮箱），如果用户不存在返回错误
+/// 返回当前用户信息（ID、用户名、邮箱），`data.etag` 为用户版本（修改时作为 `If-Match`），
+/// 如果用户不存在返回错误
 #[instrument(skip(state, current_user))]
 pub async fn me(
     State(state): State<Arc<AppState>>,
@@ -131,9 +153,9 @@ pub async fn me(
     info!("获取当前用户信息，用户ID: {}", current_user.user_id);
 
     let user_service = UserService::from_state(&state);
-    let response = user_service.get_user(current_user.user_id).await?;
+    let (response, version) = user_service.get_user(current_user.user_id).await?;
 
-    Ok(ApiResponse::success(response))
+    Ok(ApiResponse::success(response).with_etag(version_tag(version)))
 }
 
 /// 获取当前用户 API 文档
@@ -141,4 +163,336 @@ pub fn me_docs(op: TransformOperation) -> TransformOperation {
     op.description("获取当前登录用户信息")
         .tag("用户")
         .response::<200, ApiResponse<RegisterResponse>>()
+        .errors::<AuthError>()
+}
+
+/// 上传头像处理器
+///
+/// 接收 `multipart/form-data` 中名为 `avatar` 的文件字段，边读取边检查大小，
+/// 超过上限立即中止。需要在 Authorization header 中提供有效的 JWT 令牌。
+/// 携带 `If-Match` 时只在用户版本一致时修改（配置要求时必须携带）。
+///
+/// # 参数
+/// * `state` - 应用状态（包含数据库连接和文件存储）
+/// * `current_user` - 当前登录用户（由认证中间件注入）
+/// * `if_match` - 期望的用户版本
+/// * `multipart` - multipart 请求体
+///
+/// # 返回
+/// 成功返回头像和缩略图的访问 URL（`data.etag` 为新版本），失败返回错误
+#[instrument(skip(state, current_user, multipart))]
+pub async fn upload_avatar(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    if_match: IfMatch,
+    mut multipart: Multipart,
+) -> Result<ApiResponse<AvatarResponse>, AppError> {
+    info!("上传头像，用户ID: {}", current_user.user_id);
+
+    let expected_version = if_match.version(state.config.require_if_match)?;
+    let bytes = read_avatar_field(&mut multipart, state.config.avatar_max_size).await?;
+
+    let user_service = UserService::from_state(&state);
+    let (response, version) = user_service
+        .update_avatar(current_user.user_id, expected_version, bytes)
+        .await?;
+
+    Ok(ApiResponse::success(response).with_etag(version_tag(version)))
+}
+
+/// 上传头像 API 文档
+pub fn upload_avatar_docs(op: TransformOperation) -> TransformOperation {
+    op.description("上传当前用户头像（multipart 字段名 avatar，支持 PNG/JPEG/GIF/WebP）")
+        .tag("用户")
+        .response::<200, ApiResponse<AvatarResponse>>()
+        .errors::<(AuthError, FileUploadError, PreconditionError)>()
+}
+
+/// 注销当前账号处理器
+///
+/// 软删除当前账号并立即吊销已签发的全部令牌，宽限期结束后匿名化个人信息。
+/// 携带 `If-Match` 时只在用户版本一致时注销（配置要求时必须携带）。
+///
+/// # 参数
+/// * `state` - 应用状态（包含数据库连接）
+/// * `current_user` - 当前登录用户（由认证中间件注入）
+/// * `if_match` - 期望的用户版本
+///
+/// # 返回
+/// 成功返回注销时间和预计匿名化时间，失败返回错误
+#[instrument(skip(state, current_user))]
+pub async fn delete_me(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    if_match: IfMatch,
+) -> Result<ApiResponse<AccountDeletionResponse>, AppError> {
+    info!("注销账号，用户ID: {}", current_user.user_id);
+
+    let expected_version = if_match.version(state.config.require_if_match)?;
+    let user_service = UserService::from_state(&state);
+    let response = user_service
+        .delete_account(current_user.user_id, expected_version)
+        .await?;
+
+    Ok(ApiResponse::success(response))
+}
+
+/// 注销当前账号 API 文档
+pub fn delete_me_docs(op: TransformOperation) -> TransformOperation {
+    op.description("注销当前账号（软删除，吊销全部令牌，宽限期后匿名化个人信息）")
+        .tag("用户")
+        .response::<200, ApiResponse<AccountDeletionResponse>>()
+        .errors::<(AuthError, PreconditionError)>()
+}
+
+/// 创建个人数据导出任务处理器
+///
+/// 异步生成当前用户的个人数据导出文件，立即返回任务信息。
+///
+/// # 参数
+/// * `state` - 应用状态（包含数据库连接和文件存储）
+/// * `current_user` - 当前登录用户（由认证中间件注入）
+/// * `req` - 导出请求（导出格式）
+///
+/// # 返回
+/// 成功返回 202 和导出任务信息，失败返回错误
+#[instrument(skip(state, current_user))]
+pub async fn request_export(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Json(req): Json<DataExportRequest>,
+) -> Result<(StatusCode, ApiResponse<DataExportResponse>), AppError> {
+    info!(
+        "创建个人数据导出，用户ID: {}，格式: {}",
+        current_user.user_id, req.format
+    );
+
+    let user_service = UserService::from_state(&state);
+    let response = user_service
+        .request_export(current_user.user_id, req.format)
+        .await?;
+
+    Ok((StatusCode::ACCEPTED, ApiResponse::success(response)))
+}
+
+/// 创建个人数据导出任务 API 文档
+pub fn request_export_docs(op: TransformOperation) -> TransformOperation {
+    op.description("创建个人数据导出任务（JSON 或 ZIP），通过查询接口轮询状态")
+        .tag("用户")
+        .response::<202, ApiResponse<DataExportResponse>>()
+        .errors::<(AuthError, PrivacyError)>()
+}
+
+/// 查询个人数据导出任务处理器
+///
+/// # 参数
+/// * `state` - 应用状态（包含数据库连接）
+/// * `current_user` - 当前登录用户（由认证中间件注入）
+/// * `id` - 导出任务 ID
+///
+/// # 返回
+/// 返回导出任务状态，任务不存在或不属于当前用户时返回 404
+#[instrument(skip(state, current_user))]
+pub async fn get_export(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(id): Path<i64>,
+) -> Result<ApiResponse<DataExportResponse>, AppError> {
+    let user_service = UserService::from_state(&state);
+    let response = user_service.get_export(current_user.user_id, id).await?;
+
+    Ok(ApiResponse::success(response))
+}
+
+/// 查询个人数据导出任务 API 文档
+pub fn get_export_docs(op: TransformOperation) -> TransformOperation {
+    op.description("查询个人数据导出任务状态")
+        .tag("用户")
+        .response::<200, ApiResponse<DataExportResponse>>()
+        .errors::<(AuthError, PrivacyError)>()
+}
+
+/// 下载个人数据导出文件处理器
+///
+/// # 参数
+/// * `state` - 应用状态（包含数据库连接和私有存储）
+/// * `current_user` - 当前登录用户（由认证中间件注入）
+/// * `id` - 导出任务 ID
+///
+/// # 返回
+/// 成功返回导出文件（附件形式），任务未完成时返回 409
+#[instrument(skip(state, current_user))]
+pub async fn download_export(
+    State(state): State<Arc<AppState>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(id): Path<i64>,
+) -> Result<(HeaderMap, Vec<u8>), AppError> {
+    info!(
+        "下载个人数据导出，用户ID: {}，任务ID: {}",
+        current_user.user_id, id
+    );
+
+    let user_service = UserService::from_state(&state);
+    let (format, bytes) = user_service
+        .download_export(current_user.user_id, id)
+        .await?;
+
+    let disposition = format!(
+        "attachment; filename=\"personal-data-{}.{}\"",
+        id,
+        export::file_extension(format)
+    );
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        CONTENT_TYPE,
+        HeaderValue::from_static(export::content_type(format)),
+    );
+    headers.insert(
+        CONTENT_DISPOSITION,
+        HeaderValue::try_from(disposition).map_err(|e| PrivacyError::Internal(e.to_string()))?,
+    );
+    Ok((headers, bytes))
+}
+
+/// 下载个人数据导出文件 API 文档
+pub fn download_export_docs(op: TransformOperation) -> TransformOperation {
+    op.description("下载已完成的个人数据导出文件")
+        .tag("用户")
+        .response::<200, Vec<u8>>()
+        .errors::<(AuthError, PrivacyError)>()
+}
+
+/// 批量导入用户处理器（管理员）
+///
+/// 接收 `multipart/form-data` 中名为 `file` 的 CSV 或 NDJSON 文件，
+/// 按字段 Content-Type 或文件扩展名识别格式。`dry_run=true` 时只返回逐行校验结果。
+///
+/// # 参数
+/// * `state` - 应用状态（包含数据库连接）
+/// * `query` - 导入参数（是否预检）
+/// * `multipart` - multipart 请求体
+///
+/// # 返回
+/// 成功返回导入结果，正式导入存在错误行时返回 422 和逐行错误
+#[instrument(skip(state, multipart))]
+pub async fn import_users(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ImportQuery>,
+    mut multipart: Multipart,
+) -> Result<ApiResponse<ImportReport>, AppError> {
+    let (format, bytes) = read_import_field(&mut multipart, state.config.import_max_size).await?;
+    let rows = tokio::task::spawn_blocking(move || bulk::parse_import(&bytes, format))
+        .await
+        .map_err(|e| FileUploadError::Failed(format!("导入文件解析任务失败：{}", e)))?
+        .map_err(BulkImportError::InvalidFile)?;
+    info!(
+        "批量导入用户，格式: {:?}，行数: {}，预检: {}",
+        format,
+        rows.len(),
+        query.dry_run
+    );
+
+    let user_service = UserService::from_state(&state);
+    let report = user_service.import_users(rows, query.dry_run).await?;
+
+    info!(
+        "批量导入完成，有效: {}，导入: {}，错误: {}",
+        report.valid,
+        report.imported,
+        report.errors.len()
+    );
+    Ok(ApiResponse::success(report))
+}
+
+/// 批量导入用户 API 文档
+pub fn import_users_docs(op: TransformOperation) -> TransformOperation {
+    op.description(
+        "批量导入用户（管理员，multipart 字段名 file，支持 CSV/NDJSON，列为 username、email、password）",
+    )
+    .tag("用户")
+    .response::<200, ApiResponse<ImportReport>>()
+    .errors::<(AuthError, FileUploadError, BulkImportError)>()
+}
+
+/// 导出全部用户处理器（管理员）
+///
+/// 按 `Accept` 以 CSV（默认）或 NDJSON 流式输出全部未注销用户，不在内存中缓存完整结果。
+///
+/// # 参数
+/// * `state` - 应用状态（包含数据库连接）
+/// * `headers` - 请求头，按其中的 `Accept` 选择格式
+///
+/// # 返回
+/// CSV 或 NDJSON 文件（附件形式）
+#[instrument(skip(state, headers))]
+pub async fn export_users(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> StreamingList<ExportUserRow> {
+    let format = StreamFormat::from_headers(&headers, StreamFormat::Csv);
+    info!("导出全部用户，format={:?}", format);
+
+    UserService::from_state(&state)
+        .export_users(format)
+        .with_filename("users")
+}
+
+/// 导出全部用户 API 文档
+pub fn export_users_docs(op: TransformOperation) -> TransformOperation {
+    op.description("以 CSV 或 NDJSON 流式导出全部用户（管理员），按 Accept 选择，默认 CSV")
+        .tag("用户")
+        .response_with::<200, StreamingList<ExportUserRow>, _>(|res| {
+            res.description(
+                "CSV 列为 id、username、email、status、role、created_at；NDJSON 每行一个用户对象",
+            )
+        })
+        .errors::<AuthError>()
+}
+
+/// 读取 multipart 中的 `file` 字段并识别导入格式，累计大小超过 `max_size` 时立即返回错误
+async fn read_import_field(
+    multipart: &mut Multipart,
+    max_size: usize,
+) -> Result<(ImportFormat, Vec<u8>), AppError> {
+    while let Some(mut field) = multipart.next_field().await? {
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let format = ImportFormat::detect(field.content_type(), field.file_name())
+            .ok_or(BulkImportError::UnsupportedFormat)?;
+        let mut bytes = Vec::new();
+        while let Some(chunk) = field.chunk().await? {
+            if bytes.len() + chunk.len() > max_size {
+                return Err(FileUploadError::TooLarge(max_size).into());
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        return Ok((format, bytes));
+    }
+
+    Err(FileUploadError::MissingField("file".to_string()).into())
+}
+
+/// 读取 multipart 中的 `avatar` 字段，累计大小超过 `max_size` 时立即返回错误
+async fn read_avatar_field(
+    multipart: &mut Multipart,
+    max_size: usize,
+) -> Result<Vec<u8>, FileUploadError> {
+    while let Some(mut field) = multipart.next_field().await? {
+        if field.name() != Some("avatar") {
+            continue;
+        }
+
+        let mut bytes = Vec::new();
+        while let Some(chunk) = field.chunk().await? {
+            if bytes.len() + chunk.len() > max_size {
+                return Err(FileUploadError::TooLarge(max_size));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        return Ok(bytes);
+    }
+
+    Err(FileUploadError::MissingField("avatar".to_string()))
 }
@@ -1,23 +1,38 @@
 //! 用户管理模块
 //!
-//! 提供用户注册、登录、获取当前用户信息等功能。
+//! 提供用户注册、登录、获取当前用户信息、个人数据导出和账号注销等功能。
 
-use crate::AppState;
+use crate::core::middleware::{ResponseCache, response_cache_middleware};
+use crate::{AppError, AppState, shared::FromState};
 use aide::axum::ApiRouter;
-use aide::axum::routing::{get_with, post_with};
+use aide::axum::routing::{get_with, post_with, put_with};
 use std::sync::Arc;
 use tower_governor::{GovernorLayer, governor::GovernorConfigBuilder};
 
+pub mod avatar;
+pub mod bulk;
 pub mod dto;
+pub mod export;
 mod handler;
 mod service;
 
+/// 用户数据的缓存命名空间，用户增删改后由服务层失效
+pub const USERS_CACHE: &str = "users";
+
 /// 构建用户模块的路由
 ///
 /// 配置以下端点：
+/// - GET / - 分页查询用户列表（需要认证，响应按当前用户缓存）
 /// - POST /register - 用户注册（限速2req/s）
 /// - POST /login - 用户登录（限速2req/s）
 /// - GET /me - 获取当前用户信息（需要认证）
+/// - DELETE /me - 注销当前账号（需要认证）
+/// - PUT /me/avatar - 上传当前用户头像（需要认证）
+/// - POST /me/export - 创建个人数据导出任务（需要认证）
+/// - GET /me/export/{id} - 查询导出任务状态（需要认证）
+/// - GET /me/export/{id}/download - 下载导出文件（需要认证）
+/// - POST /import - 批量导入用户（需要管理员权限）
+/// - GET /export - 以 CSV 或 NDJSON（按 Accept）流式导出全部用户（需要管理员权限）
 ///
 /// # 参数
 /// * `state` - 应用状态，包含数据库和服务实例
@@ -45,28 +60,109 @@ pub fn routes(state: Arc<AppState>) -> ApiRouter {
     ApiRouter::new()
         .api_route(
             "/",
-            get_with(handler::list_users, handler::list_users_docs).layer(
-                axum::middleware::from_fn_with_state(
+            get_with(handler::list_users, handler::list_users_docs)
+                .layer(axum::middleware::from_fn_with_state(
+                    ResponseCache::new(state.cache.clone(), USERS_CACHE),
+                    response_cache_middleware,
+                ))
+                .layer(axum::middleware::from_fn_with_state(
                     state.clone(),
                     crate::core::middleware::auth::require_auth,
-                ),
-            ),
+                )),
         )
         .api_route(
             "/register",
-            post_with(handler::register, handler::register_docs)
-                .layer(GovernorLayer::new(register_limiter)),
+            post_with(handler::register, handler::register_docs).layer(
+                GovernorLayer::new(register_limiter)
+                    .error_handler(crate::core::handle_rate_limit_error),
+            ),
         )
         .api_route(
             "/login",
-            post_with(handler::login, handler::login_docs).layer(GovernorLayer::new(login_limiter)),
+            post_with(handler::login, handler::login_docs).layer(
+                GovernorLayer::new(login_limiter)
+                    .error_handler(crate::core::handle_rate_limit_error),
+            ),
         )
         .api_route(
             "/me",
-            get_with(handler::me, handler::me_docs).layer(axum::middleware::from_fn_with_state(
-                state.clone(),
-                crate::core::middleware::auth::require_auth,
-            )),
+            get_with(handler::me, handler::me_docs)
+                .delete_with(handler::delete_me, handler::delete_me_docs)
+                .layer(axum::middleware::from_fn_with_state(
+                    state.clone(),
+                    crate::core::middleware::auth::require_auth,
+                )),
+        )
+        .api_route(
+            "/me/avatar",
+            put_with(handler::upload_avatar, handler::upload_avatar_docs).layer(
+                axum::middleware::from_fn_with_state(
+                    state.clone(),
+                    crate::core::middleware::auth::require_auth,
+                ),
+            ),
+        )
+        .api_route(
+            "/me/export",
+            post_with(handler::request_export, handler::request_export_docs).layer(
+                axum::middleware::from_fn_with_state(
+                    state.clone(),
+                    crate::core::middleware::auth::require_auth,
+                ),
+            ),
+        )
+        .api_route(
+            "/me/export/{id}",
+            get_with(handler::get_export, handler::get_export_docs).layer(
+                axum::middleware::from_fn_with_state(
+                    state.clone(),
+                    crate::core::middleware::auth::require_auth,
+                ),
+            ),
+        )
+        .api_route(
+            "/me/export/{id}/download",
+            get_with(handler::download_export, handler::download_export_docs).layer(
+                axum::middleware::from_fn_with_state(
+                    state.clone(),
+                    crate::core::middleware::auth::require_auth,
+                ),
+            ),
+        )
+        .api_route(
+            "/import",
+            post_with(handler::import_users, handler::import_users_docs)
+                .layer(axum::middleware::from_fn(
+                    crate::core::middleware::auth::require_admin,
+                ))
+                .layer(axum::middleware::from_fn_with_state(
+                    state.clone(),
+                    crate::core::middleware::auth::require_auth,
+                )),
+        )
+        .api_route(
+            "/export",
+            get_with(handler::export_users, handler::export_users_docs)
+                .layer(axum::middleware::from_fn(
+                    crate::core::middleware::auth::require_admin,
+                ))
+                .layer(axum::middleware::from_fn_with_state(
+                    state.clone(),
+                    crate::core::middleware::auth::require_auth,
+                )),
         )
         .with_state(state)
 }
+
+/// 清理过期个人数据
+///
+/// 匿名化注销超过宽限期的账号，并删除超过保留时间的导出文件。
+/// 由 main 中的后台任务按 `privacy.purge_interval` 定期调用。
+///
+/// # 返回
+/// 成功返回本次匿名化的账号数量
+pub async fn purge_expired_personal_data(state: &AppState) -> Result<usize, AppError> {
+    service::UserService::from_state(state)
+        .purge_expired()
+        .await
+}
@@ -1,31 +1,69 @@
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, PaginatorTrait,
-    QueryFilter, QueryOrder, Set, SqlErr, TransactionTrait,
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, DbErr, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, Set, SqlErr, TransactionTrait,
+    sea_query::{Expr, Func},
 };
 use tracing::instrument;
+use uuid::Uuid;
+use validator::Validate;
 
 use crate::{
-    AppState, Pagination,
-    error::AuthError,
-    shared::{FromState, jwt::JwtService, password},
+    AppError, AppState, Pagination,
+    error::{
+        AuthError, BulkImportError, FileUploadError, PreconditionError, PrivacyError,
+        ValidationError,
+    },
+    response::{Domain, ErrorDetail, Reason, StreamFormat, StreamingList},
+    shared::{Cache, FromState, IdGenerator, PublicId, Storage, jwt::JwtService, password},
 };
+use entity::data_export;
+use entity::enums::{ExportFormat, ExportStatus, UserRole, UserStatus};
 use entity::user;
 
-use super::dto::{LoginRequest, LoginResponse, RegisterRequest, RegisterResponse, UserListItem};
+use super::USERS_CACHE;
+use super::avatar::{self, THUMBNAIL_SIZES};
+use super::bulk::{ExportUserRow, ImportKeys, ImportRow, MAX_IMPORT_ROWS, ParsedRow};
+use super::dto::{
+    AccountDeletionResponse, AvatarResponse, AvatarThumbnail, DataExportResponse, ImportReport,
+    ImportRowError, LoginRequest, LoginResponse, RegisterRequest, RegisterResponse, UserListItem,
+};
+use super::export::{
+    self, EXPORT_SCHEMA_VERSION, ExportAccount, ExportAttachment, ExportProfile, ExportRecord,
+    UserDataExport,
+};
 
 /// 用户服务
 ///
 /// 处理用户注册、登录等业务逻辑
+#[derive(Clone)]
 pub struct UserService {
     db: DatabaseConnection,
+    cache: Cache,
     jwt_service: JwtService,
+    id_generator: IdGenerator,
+    storage: Arc<dyn Storage>,
+    private_storage: Arc<dyn Storage>,
+    deletion_grace_days: u64,
+    export_retention_days: u64,
+    export_timeout_minutes: u64,
 }
 
 impl FromState for UserService {
     fn from_state(app: &AppState) -> Self {
         Self {
             db: app.db.clone(),
+            cache: app.cache.clone(),
             jwt_service: app.jwt_service.clone(),
+            id_generator: app.id_generator.clone(),
+            storage: app.storage.clone(),
+            private_storage: app.private_storage.clone(),
+            deletion_grace_days: app.config.deletion_grace_days,
+            export_retention_days: app.config.export_retention_days,
+            export_timeout_minutes: app.config.export_timeout_minutes,
         }
     }
 }
@@ -38,6 +76,7 @@ impl UserService {
         pagination: Pagination,
     ) -> Result<(Vec<UserListItem>, u64), AuthError> {
         let paginator = user::Entity::find()
+            .filter(user::Column::DeletedAt.is_null())
             .order_by_desc(user::Column::CreatedAt)
             .paginate(&self.db, pagination.page_size);
         let total = paginator
@@ -56,35 +95,18 @@ impl UserService {
     /// 用户注册业务逻辑
     ///
     /// 执行以下步骤：
-    /// 1. 验证用户名长度（3-20字符）和密码长度（至少8字符）
-    /// 2. 验证两次密码输入是否一致
-    /// 3. 检查用户名和邮箱是否已存在
-    /// 4. 使用Argon2算法哈希密码
-    /// 5. 创建新用户并保存到数据库
+    /// 1. 检查用户名和邮箱是否已存在
+    /// 2. 使用Argon2算法哈希密码
+    /// 3. 创建新用户并保存到数据库
     ///
     /// # 参数
-    /// * `req` - 注册请求，包含用户名、邮箱、密码
+    /// * `req` - 注册请求，包含用户名、邮箱、密码（字段规则已由 `ValidatedJson` 校验）
     ///
     /// # 返回
     /// 成功返回 RegisterResponse（用户ID、用户名、邮箱）
     /// 失败返回 AuthError（如果用户已存在、验证失败等）
     #[instrument(skip(self, req))]
     pub async fn register(&self, req: RegisterRequest) -> Result<RegisterResponse, AuthError> {
-        // 验证用户名
-        if req.username.is_empty() || req.username.len() < 3 || req.username.len() > 20 {
-            return Err(AuthError::InvalidUsername);
-        }
-
-        // 验证密码长度
-        if req.password.len() < 8 {
-            return Err(AuthError::PasswordTooShort);
-        }
-
-        // 验证两次密码是否一致
-        if req.password != req.password_confirm {
-            return Err(AuthError::PasswordMismatch);
-        }
-
         let txn = self
             .db
             .begin()
@@ -131,11 +153,13 @@ impl UserService {
         txn.commit()
             .await
             .map_err(|_| AuthError::Internal("数据库事务提交失败".to_string()))?;
+        self.cache.invalidate(USERS_CACHE).await;
 
         Ok(RegisterResponse {
-            id: user_model.id,
+            id: PublicId::new(user_model.id),
             username: user_model.username,
             email: user_model.email,
+            avatar_url: None,
         })
     }
 
@@ -187,7 +211,7 @@ impl UserService {
             .map_err(|e| AuthError::Internal(e.to_string()))?;
 
         Ok(LoginResponse {
-            id: user_model.id,
+            id: PublicId::new(user_model.id),
             username: user_model.username,
             email: user_model.email,
             token,
@@ -197,27 +221,772 @@ impl UserService {
 
     /// 根据用户ID获取用户信息
     ///
-    /// 从数据库中查询指定ID的用户信息。
+    /// 从数据库中查询指定ID的用户信息，结果按用户缓存，修改用户后失效。
     ///
     /// # 参数
     /// * `user_id` - 用户ID
     ///
     /// # 返回
-    /// 成功返回 RegisterResponse（用户ID、用户名、邮箱）
-    /// 如果用户不存在返回 AuthError::UserNotFound
+    /// 成功返回 RegisterResponse（用户ID、用户名、邮箱）和用户当前版本号（用作 ETag）
+    /// 如果用户不存在或已注销返回 AuthError::UserNotFound
+    #[instrument(skip(self))]
+    pub async fn get_user(&self, user_id: i32) -> Result<(RegisterResponse, i32), AuthError> {
+        self.cache
+            .get_or_load(USERS_CACHE, &user_id.to_string(), || async {
+                let user_model = self.find_live_user(user_id).await?;
+
+                let response = RegisterResponse {
+                    id: PublicId::new(user_model.id),
+                    username: user_model.username,
+                    email: user_model.email,
+                    avatar_url: user_model
+                        .avatar_key
+                        .as_deref()
+                        .map(|prefix| self.storage.public_url(&avatar::avatar_key(prefix))),
+                };
+                Ok((response, user_model.version))
+            })
+            .await
+    }
+
+    /// 更新用户头像
+    ///
+    /// 执行以下步骤：
+    /// 1. 校验用户存在且版本与 `If-Match` 一致
+    /// 2. 在阻塞线程池中识别格式、重新编码并生成缩略图
+    /// 3. 以新的随机前缀写入存储（不覆盖旧文件，避免 CDN/浏览器缓存读到半新半旧的图片）
+    /// 4. 以读取时的版本为条件更新用户的头像 key，再尽力删除旧头像文件；
+    ///    期间被并发修改时删除本次写入的文件并返回错误
+    ///
+    /// # 参数
+    /// * `user_id` - 用户ID
+    /// * `expected_version` - 客户端期望的版本（`If-Match`），`None` 表示不检查
+    /// * `bytes` - 上传的原始图片内容
+    ///
+    /// # 返回
+    /// 成功返回头像和缩略图的访问 URL 以及用户的新版本号
+    /// 失败返回 AppError（用户不存在、版本不一致、图片格式不支持、存储失败等）
+    #[instrument(skip(self, bytes), fields(size = bytes.len()))]
+    pub async fn update_avatar(
+        &self,
+        user_id: i32,
+        expected_version: Option<i32>,
+        bytes: Vec<u8>,
+    ) -> Result<(AvatarResponse, i32), AppError> {
+        let user_model = self.find_live_user(user_id).await?;
+        ensure_version(&user_model, expected_version)?;
+
+        let processed = tokio::task::spawn_blocking(move || avatar::process_avatar(&bytes))
+            .await
+            .map_err(|e| FileUploadError::Failed(format!("图片处理任务失败：{}", e)))??;
+
+        let prefix = format!("avatars/{}", Uuid::new_v4());
+        self.storage
+            .put(&avatar::avatar_key(&prefix), processed.avatar, "image/png")
+            .await?;
+        for (size, data) in processed.thumbnails {
+            self.storage
+                .put(&avatar::thumbnail_key(&prefix, size), data, "image/png")
+                .await?;
+        }
+
+        let old_prefix = user_model.avatar_key.clone();
+        let version = user_model.version;
+        let mut active: user::ActiveModel = user_model.into();
+        active.avatar_key = Set(Some(prefix.clone()));
+        active.updated_at = Set(Utc::now().fixed_offset());
+        let saved = match self.save_user(active, version).await {
+            Ok(saved) => saved,
+            Err(error) => {
+                self.delete_avatar_files(&prefix).await;
+                return Err(error);
+            }
+        };
+
+        if let Some(old_prefix) = old_prefix {
+            self.delete_avatar_files(&old_prefix).await;
+        }
+
+        let response = AvatarResponse {
+            url: self.storage.public_url(&avatar::avatar_key(&prefix)),
+            thumbnails: THUMBNAIL_SIZES
+                .iter()
+                .map(|&size| AvatarThumbnail {
+                    size,
+                    url: self
+                        .storage
+                        .public_url(&avatar::thumbnail_key(&prefix, size)),
+                })
+                .collect(),
+        };
+        Ok((response, saved.version))
+    }
+
+    /// 以乐观锁保存用户修改
+    ///
+    /// 以读取时的版本为条件原子地写入并递增版本（`UPDATE ... WHERE id = ? AND version = ?`），
+    /// 读取后被并发修改时不写入，返回 `PreconditionError::Failed`。写入成功后使用户缓存失效。
+    async fn save_user(
+        &self,
+        mut active: user::ActiveModel,
+        version: i32,
+    ) -> Result<user::Model, AppError> {
+        active.version = Set(version + 1);
+        let saved = user::Entity::update(active)
+            .filter(user::Column::Version.eq(version))
+            .exec(&self.db)
+            .await
+            .map_err(|e| match e {
+                DbErr::RecordNotUpdated => AppError::from(PreconditionError::Failed),
+                e => e.into(),
+            })?;
+        self.cache.invalidate(USERS_CACHE).await;
+        Ok(saved)
+    }
+
+    /// 尽力删除旧头像文件，失败只记录日志
+    async fn delete_avatar_files(&self, prefix: &str) {
+        let keys = std::iter::once(avatar::avatar_key(prefix)).chain(
+            THUMBNAIL_SIZES
+                .iter()
+                .map(|&size| avatar::thumbnail_key(prefix, size)),
+        );
+        for key in keys {
+            if let Err(error) = self.storage.delete(&key).await {
+                tracing::warn!(key = %key, error = %error, "删除旧头像文件失败");
+            }
+        }
+    }
+}
+
+impl UserService {
+    /// 创建个人数据导出任务
+    ///
+    /// 同一用户同一格式已有处理中的任务时直接返回该任务，避免重复生成；
+    /// 超过 `privacy.export_timeout_minutes` 仍未完成的任务视为已中断，不再复用。
+    /// 新任务在后台生成导出文件，调用方通过 [`UserService::get_export`] 轮询状态。
+    ///
+    /// # 参数
+    /// * `user_id` - 用户ID
+    /// * `format` - 导出格式
+    ///
+    /// # 返回
+    /// 成功返回导出任务信息，用户不存在或已注销返回 AuthError::UserNotFound
     #[instrument(skip(self))]
-    pub async fn get_user(&self, user_id: i32) -> Result<RegisterResponse, AuthError> {
-        let user_model = user::Entity::find_by_id(user_id)
+    pub async fn request_export(
+        &self,
+        user_id: i32,
+        format: ExportFormat,
+    ) -> Result<DataExportResponse, AppError> {
+        self.find_live_user(user_id).await?;
+
+        let pending = data_export::Entity::find()
+            .filter(data_export::Column::UserId.eq(user_id))
+            .filter(data_export::Column::Format.eq(i16::from(format)))
+            .filter(data_export::Column::Status.eq(i16::from(ExportStatus::Pending)))
+            .filter(
+                data_export::Column::CreatedAt
+                    .gt(Utc::now().fixed_offset() - self.export_timeout()),
+            )
+            .one(&self.db)
+            .await?;
+        if let Some(pending) = pending {
+            return Ok(export_response(&pending));
+        }
+
+        let id = self
+            .id_generator
+            .next_id()
+            .map_err(|e| PrivacyError::Internal(format!("生成导出任务 ID 失败：{}", e)))?;
+        let model = data_export::ActiveModel {
+            id: Set(id),
+            user_id: Set(user_id),
+            format: Set(format.into()),
+            status: Set(ExportStatus::Pending.into()),
+            created_at: Set(Utc::now().fixed_offset()),
+            ..Default::default()
+        }
+        .insert(&self.db)
+        .await?;
+
+        let response = export_response(&model);
+        let service = self.clone();
+        tokio::spawn(service.run_export(model));
+
+        Ok(response)
+    }
+
+    /// 查询导出任务，只能查询自己的任务
+    #[instrument(skip(self))]
+    pub async fn get_export(
+        &self,
+        user_id: i32,
+        export_id: i64,
+    ) -> Result<DataExportResponse, AppError> {
+        let model = self.find_export(user_id, export_id).await?;
+        Ok(export_response(&model))
+    }
+
+    /// 读取已完成的导出文件
+    ///
+    /// # 返回
+    /// 成功返回导出格式和文件内容；任务不存在返回 ExportNotFound，
+    /// 未完成、失败或文件已过期清理返回 ExportNotReady
+    #[instrument(skip(self))]
+    pub async fn download_export(
+        &self,
+        user_id: i32,
+        export_id: i64,
+    ) -> Result<(ExportFormat, Vec<u8>), AppError> {
+        let model = self.find_export(user_id, export_id).await?;
+        let format = ExportFormat::try_from(model.format).unwrap_or_default();
+
+        let key = match (ExportStatus::try_from(model.status), model.file_key) {
+            (Ok(ExportStatus::Completed), Some(key)) => key,
+            _ => return Err(PrivacyError::ExportNotReady.into()),
+        };
+        let bytes = self
+            .private_storage
+            .get(&key)
+            .await?
+            .ok_or(PrivacyError::ExportNotReady)?;
+
+        Ok((format, bytes))
+    }
+
+    /// 注销账号（软删除）
+    ///
+    /// 执行以下步骤：
+    /// 1. 将用户标记为已删除并记录注销时间
+    /// 2. 设置令牌生效起点为当前时间，使已签发的全部令牌立即失效
+    /// 3. 宽限期结束后由 [`UserService::purge_expired`] 匿名化个人信息
+    ///
+    /// # 参数
+    /// * `user_id` - 用户ID
+    /// * `expected_version` - 客户端期望的版本（`If-Match`），`None` 表示不检查
+    ///
+    /// # 返回
+    /// 成功返回注销时间和预计匿名化时间，用户不存在、已注销或版本不一致返回错误
+    #[instrument(skip(self))]
+    pub async fn delete_account(
+        &self,
+        user_id: i32,
+        expected_version: Option<i32>,
+    ) -> Result<AccountDeletionResponse, AppError> {
+        let user_model = self.find_live_user(user_id).await?;
+        ensure_version(&user_model, expected_version)?;
+
+        let now = Utc::now().fixed_offset();
+        let version = user_model.version;
+        let mut active: user::ActiveModel = user_model.into();
+        active.status = Set(UserStatus::Deleted.into());
+        active.deleted_at = Set(Some(now));
+        active.tokens_valid_after = Set(Some(now));
+        active.updated_at = Set(now);
+        self.save_user(active, version).await?;
+
+        Ok(AccountDeletionResponse {
+            deleted_at: now,
+            purge_after: now + self.deletion_grace(),
+        })
+    }
+
+    /// 清理过期个人数据
+    ///
+    /// 匿名化注销超过宽限期的账号（用户名、邮箱、密码哈希、头像、导出文件），
+    /// 将超时未完成的导出任务标记为失败（生成期间进程重启时任务不会再被执行），
+    /// 并删除超过保留时间的导出文件。单个账号或导出文件失败只记录日志，不影响其他记录。
+    ///
+    /// # 返回
+    /// 成功返回本次匿名化的账号数量
+    #[instrument(skip(self))]
+    pub async fn purge_expired(&self) -> Result<usize, AppError> {
+        let now = Utc::now().fixed_offset();
+
+        let stale = data_export::Entity::update_many()
+            .col_expr(
+                data_export::Column::Status,
+                Expr::value(i16::from(ExportStatus::Failed)),
+            )
+            .col_expr(data_export::Column::CompletedAt, Expr::value(now))
+            .col_expr(
+                data_export::Column::Error,
+                Expr::value("导出任务超时未完成，可能因服务重启而中断"),
+            )
+            .filter(data_export::Column::Status.eq(i16::from(ExportStatus::Pending)))
+            .filter(data_export::Column::CreatedAt.lt(now - self.export_timeout()))
+            .exec(&self.db)
+            .await?;
+        if stale.rows_affected > 0 {
+            tracing::warn!(
+                stale = stale.rows_affected,
+                "已将超时未完成的导出任务标记为失败"
+            );
+        }
+
+        let expired_exports = data_export::Entity::find()
+            .filter(data_export::Column::CreatedAt.lt(now - self.export_retention()))
+            .all(&self.db)
+            .await?;
+        let failed = self.delete_exports(expired_exports).await;
+        if failed > 0 {
+            tracing::warn!(failed, "部分过期导出文件清理失败，下次清理时重试");
+        }
+
+        let users = user::Entity::find()
+            .filter(user::Column::DeletedAt.lt(now - self.deletion_grace()))
+            .filter(user::Column::AnonymizedAt.is_null())
+            .all(&self.db)
+            .await?;
+
+        let mut purged = 0;
+        for user_model in users {
+            let user_id = user_model.id;
+            match self.anonymize_user(user_model).await {
+                Ok(()) => purged += 1,
+                Err(error) => tracing::error!(user_id, error = %error, "匿名化已注销账号失败"),
+            }
+        }
+        Ok(purged)
+    }
+
+    /// 查询未注销的用户
+    async fn find_live_user(&self, user_id: i32) -> Result<user::Model, AuthError> {
+        user::Entity::find_by_id(user_id)
+            .filter(user::Column::DeletedAt.is_null())
             .one(&self.db)
             .await
             .map_err(|_| AuthError::Internal("数据库查询失败".to_string()))?
+            .ok_or(AuthError::UserNotFound)
+    }
+
+    /// 查询属于指定用户的导出任务
+    async fn find_export(
+        &self,
+        user_id: i32,
+        export_id: i64,
+    ) -> Result<data_export::Model, AppError> {
+        data_export::Entity::find_by_id(export_id)
+            .filter(data_export::Column::UserId.eq(user_id))
+            .one(&self.db)
+            .await?
+            .ok_or_else(|| PrivacyError::ExportNotFound.into())
+    }
+
+    /// 后台生成导出文件并更新任务状态
+    async fn run_export(self, model: data_export::Model) {
+        let export_id = model.id;
+        let result = self.generate_export(&model).await;
+
+        let mut active: data_export::ActiveModel = model.into();
+        active.completed_at = Set(Some(Utc::now().fixed_offset()));
+        match result {
+            Ok(key) => {
+                active.status = Set(ExportStatus::Completed.into());
+                active.file_key = Set(Some(key));
+            }
+            Err(error) => {
+                tracing::error!(export_id, error = %error, "生成个人数据导出失败");
+                active.status = Set(ExportStatus::Failed.into());
+                active.error = Set(Some(error.to_string()));
+            }
+        }
+
+        if let Err(error) = active.update(&self.db).await {
+            tracing::error!(export_id, error = %error, "更新导出任务状态失败");
+        }
+    }
+
+    /// 汇总个人数据、生成导出文件并写入私有存储，返回文件 key
+    ///
+    /// 导出内容即系统保存的该用户全部数据，没有会话和审计记录的原因见 [`UserDataExport`]。
+    async fn generate_export(&self, model: &data_export::Model) -> Result<String, AppError> {
+        let format = ExportFormat::try_from(model.format).unwrap_or_default();
+        let user_model = user::Entity::find_by_id(model.user_id)
+            .one(&self.db)
+            .await?
             .ok_or(AuthError::UserNotFound)?;
+        let exports = data_export::Entity::find()
+            .filter(data_export::Column::UserId.eq(model.user_id))
+            .order_by_desc(data_export::Column::CreatedAt)
+            .all(&self.db)
+            .await?;
 
-        Ok(RegisterResponse {
-            id: user_model.id,
-            username: user_model.username,
-            email: user_model.email,
+        let mut attachments = Vec::new();
+        if format == ExportFormat::Zip
+            && let Some(prefix) = user_model.avatar_key.as_deref()
+            && let Some(bytes) = self.storage.get(&avatar::avatar_key(prefix)).await?
+        {
+            attachments.push(ExportAttachment {
+                path: "avatar.png".to_string(),
+                bytes,
+            });
+        }
+
+        let data = UserDataExport {
+            schema_version: EXPORT_SCHEMA_VERSION,
+            generated_at: Utc::now().fixed_offset(),
+            profile: ExportProfile {
+                id: PublicId::new(user_model.id).encode(),
+                username: user_model.username,
+                email: user_model.email,
+                avatar_url: user_model
+                    .avatar_key
+                    .as_deref()
+                    .map(|prefix| self.storage.public_url(&avatar::avatar_key(prefix))),
+            },
+            account: ExportAccount {
+                status: UserStatus::try_from(user_model.status).ok(),
+                created_at: user_model.created_at,
+                updated_at: user_model.updated_at,
+                deleted_at: user_model.deleted_at,
+                tokens_valid_after: user_model.tokens_valid_after,
+            },
+            exports: exports
+                .iter()
+                .map(|record| ExportRecord {
+                    id: record.id.to_string(),
+                    format: ExportFormat::try_from(record.format).ok(),
+                    status: ExportStatus::try_from(record.status).ok(),
+                    created_at: record.created_at,
+                    completed_at: record.completed_at,
+                })
+                .collect(),
+        };
+
+        let bytes = tokio::task::spawn_blocking(move || match format {
+            ExportFormat::Json => export::build_json(&data).map_err(|e| e.to_string()),
+            ExportFormat::Zip => export::build_zip(&data, &attachments),
         })
+        .await
+        .map_err(|e| PrivacyError::Internal(format!("导出任务失败：{}", e)))?
+        .map_err(PrivacyError::Internal)?;
+
+        let key = export::export_file_key(model.user_id, model.id, format);
+        self.private_storage
+            .put(&key, bytes, export::content_type(format))
+            .await?;
+        Ok(key)
+    }
+
+    /// 删除导出任务及其文件
+    /// 逐个删除导出记录及文件，单个失败只记录日志，返回失败的数量
+    ///
+    /// 删除失败的记录保留在数据库中，由之后的过期清理重试。
+    async fn delete_exports(&self, models: Vec<data_export::Model>) -> usize {
+        let mut failed = 0;
+        for model in models {
+            let export_id = model.id;
+            if let Err(error) = self.delete_export(model).await {
+                tracing::warn!(export_id, error = %error, "删除导出文件失败");
+                failed += 1;
+            }
+        }
+        failed
+    }
+
+    async fn delete_export(&self, model: data_export::Model) -> Result<(), AppError> {
+        if let Some(key) = model.file_key.as_deref() {
+            self.private_storage.delete(key).await?;
+        }
+        data_export::Entity::delete_by_id(model.id)
+            .exec(&self.db)
+            .await?;
+        Ok(())
+    }
+
+    /// 匿名化单个已注销账号
+    ///
+    /// 保留用户行以维持外键和 ID 不被复用，个人信息替换为不可逆的占位值。
+    async fn anonymize_user(&self, user_model: user::Model) -> Result<(), AppError> {
+        let user_id = user_model.id;
+
+        let exports = data_export::Entity::find()
+            .filter(data_export::Column::UserId.eq(user_id))
+            .all(&self.db)
+            .await?;
+        let failed = self.delete_exports(exports).await;
+        if failed > 0 {
+            tracing::warn!(user_id, failed, "部分导出文件清理失败，由过期清理重试");
+        }
+        if let Some(prefix) = user_model.avatar_key.as_deref() {
+            self.delete_avatar_files(prefix).await;
+        }
+
+        let now = Utc::now().fixed_offset();
+        let version = user_model.version;
+        let mut active: user::ActiveModel = user_model.into();
+        active.username = Set(format!("deleted_user_{user_id}"));
+        active.email = Set(format!("deleted_{user_id}@deleted.invalid"));
+        active.password_hash = Set(String::new());
+        active.avatar_key = Set(None);
+        active.anonymized_at = Set(Some(now));
+        active.updated_at = Set(now);
+        self.save_user(active, version).await?;
+        Ok(())
+    }
+
+    fn deletion_grace(&self) -> Duration {
+        Duration::days(self.deletion_grace_days as i64)
+    }
+
+    fn export_retention(&self) -> Duration {
+        Duration::days(self.export_retention_days as i64)
+    }
+
+    fn export_timeout(&self) -> Duration {
+        Duration::minutes(self.export_timeout_minutes as i64)
+    }
+}
+
+impl UserService {
+    /// 批量导入用户
+    ///
+    /// 执行以下步骤：
+    /// 1. 逐行按注册规则校验（用户名、密码长度），并检查文件内和数据库中的用户名/邮箱冲突
+    ///    （邮箱不区分大小写）
+    /// 2. 预检模式：直接返回逐行校验结果，不写入任何数据
+    /// 3. 正式导入：任一行有误则整体拒绝；全部通过时并行哈希密码，在同一个事务中写入
+    ///
+    /// # 参数
+    /// * `rows` - 解析后的数据行
+    /// * `dry_run` - 是否为预检模式
+    ///
+    /// # 返回
+    /// 成功返回导入结果；正式导入存在错误行时返回 BulkImportError::InvalidRows
+    #[instrument(skip(self, rows), fields(rows = rows.len()))]
+    pub async fn import_users(
+        &self,
+        rows: Vec<ParsedRow>,
+        dry_run: bool,
+    ) -> Result<ImportReport, AppError> {
+        if rows.len() > MAX_IMPORT_ROWS {
+            return Err(BulkImportError::TooManyRows(MAX_IMPORT_ROWS).into());
+        }
+
+        let total = rows.len();
+        let mut errors = Vec::new();
+        let mut valid = Vec::new();
+        let mut keys = ImportKeys::default();
+        for ParsedRow { line, row } in rows {
+            let row = match row {
+                Ok(row) => row,
+                Err(message) => {
+                    errors.push(row_error(line, Reason::InvalidFormat, message));
+                    continue;
+                }
+            };
+            if let Err(error) = validate_import_row(&row) {
+                errors.extend(error.details().iter().map(|detail| ImportRowError {
+                    line,
+                    field: detail.location.clone(),
+                    reason: detail.reason.clone(),
+                    message: detail.message.clone(),
+                }));
+                continue;
+            }
+            if !keys.insert(&row) {
+                errors.push(row_error(
+                    line,
+                    Reason::AlreadyExists,
+                    "用户名或邮箱在导入文件中重复",
+                ));
+                continue;
+            }
+            valid.push((line, row));
+        }
+
+        // 与数据库中已有用户的冲突（邮箱不区分大小写）
+        let (usernames, emails) = keys.into_parts();
+        let existing = user::Entity::find()
+            .filter(
+                Condition::any()
+                    .add(user::Column::Username.is_in(usernames))
+                    .add(Expr::expr(Func::lower(Expr::col(user::Column::Email))).is_in(emails)),
+            )
+            .all(&self.db)
+            .await?;
+        let taken_usernames: HashSet<_> = existing.iter().map(|u| u.username.as_str()).collect();
+        let taken_emails: HashSet<_> = existing.iter().map(|u| u.email.to_lowercase()).collect();
+        valid.retain(|(line, row)| {
+            let taken = taken_usernames.contains(row.username.as_str())
+                || taken_emails.contains(&row.email.to_lowercase());
+            if taken {
+                errors.push(row_error(
+                    *line,
+                    Reason::AlreadyExists,
+                    AuthError::UserAlreadyExists.to_string(),
+                ));
+            }
+            !taken
+        });
+        errors.sort_by_key(|error| error.line);
+
+        if !dry_run && !errors.is_empty() {
+            return Err(BulkImportError::InvalidRows(
+                errors.iter().map(ImportRowError::to_detail).collect(),
+            )
+            .into());
+        }
+
+        let mut report = ImportReport {
+            dry_run,
+            total,
+            valid: valid.len(),
+            imported: 0,
+            errors,
+        };
+        if dry_run || valid.is_empty() {
+            return Ok(report);
+        }
+
+        let models = hash_import_rows(valid.into_iter().map(|(_, row)| row).collect()).await?;
+        report.imported = models.len();
+
+        let txn = self.db.begin().await?;
+        for chunk in models.chunks(IMPORT_INSERT_BATCH) {
+            user::Entity::insert_many(chunk.to_vec())
+                .exec(&txn)
+                .await
+                .map_err(map_insert_user_error)?;
+        }
+        txn.commit().await?;
+        self.cache.invalidate(USERS_CACHE).await;
+
+        Ok(report)
+    }
+
+    /// 以 CSV 或 NDJSON 流式导出全部未注销用户
+    ///
+    /// 通过数据库游标逐行读取并编码，内存占用与用户总数无关；客户端断开后读取随之结束。
+    pub fn export_users(&self, format: StreamFormat) -> StreamingList<ExportUserRow> {
+        let select = user::Entity::find()
+            .filter(user::Column::DeletedAt.is_null())
+            .order_by_asc(user::Column::Id);
+        StreamingList::from_select(format, self.db.clone(), select, ExportUserRow::from)
+    }
+}
+
+/// 单条 INSERT 语句写入的最大行数（受 PostgreSQL 绑定参数数量限制）
+const IMPORT_INSERT_BATCH: usize = 1000;
+
+/// 校验用户版本与客户端期望的版本（`If-Match`）一致，`None` 表示不检查
+fn ensure_version(
+    user_model: &user::Model,
+    expected: Option<i32>,
+) -> Result<(), PreconditionError> {
+    match expected {
+        Some(version) if version != user_model.version => Err(PreconditionError::Failed),
+        _ => Ok(()),
+    }
+}
+
+/// 按注册接口的规则校验导入行
+fn validate_import_row(row: &ImportRow) -> Result<(), ValidationError> {
+    RegisterRequest {
+        username: row.username.clone(),
+        email: row.email.clone(),
+        password: row.password.clone(),
+        password_confirm: row.password.clone(),
+    }
+    .validate()
+    .map_err(ValidationError::from_validator)
+}
+
+/// 并行哈希导入行的密码
+///
+/// Argon2 刻意设计得很慢，按 CPU 核数分片后在阻塞线程池中并行计算。
+async fn hash_import_rows(rows: Vec<ImportRow>) -> Result<Vec<user::ActiveModel>, AppError> {
+    let workers = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_size = rows.len().div_ceil(workers).max(1);
+
+    let mut tasks = Vec::new();
+    let mut rows = rows.into_iter().peekable();
+    while rows.peek().is_some() {
+        let chunk: Vec<ImportRow> = rows.by_ref().take(chunk_size).collect();
+        tasks.push(tokio::task::spawn_blocking(move || {
+            chunk
+                .into_iter()
+                .map(|row| {
+                    let password_hash = password::hash_password(&row.password)
+                        .map_err(|e| AuthError::Internal(e.to_string()))?;
+                    Ok(user::ActiveModel {
+                        username: Set(row.username),
+                        email: Set(row.email),
+                        password_hash: Set(password_hash),
+                        status: Set(UserStatus::Active.into()),
+                        role: Set(UserRole::User.into()),
+                        ..Default::default()
+                    })
+                })
+                .collect::<Result<Vec<_>, AuthError>>()
+        }));
+    }
+
+    let mut models = Vec::new();
+    for task in tasks {
+        let chunk = task
+            .await
+            .map_err(|e| AuthError::Internal(format!("密码哈希任务失败：{}", e)))??;
+        models.extend(chunk);
+    }
+    Ok(models)
+}
+
+fn row_error(line: usize, reason: Reason, message: impl Into<String>) -> ImportRowError {
+    ImportRowError {
+        line,
+        field: None,
+        reason: reason.to_string(),
+        message: message.into(),
+    }
+}
+
+impl ImportRowError {
+    /// 转换为错误响应中的错误详情，位置为原始文件中的行号（及字段名）
+    fn to_detail(&self) -> ErrorDetail {
+        let location = match &self.field {
+            Some(field) => format!("line {}: {}", self.line, field),
+            None => format!("line {}", self.line),
+        };
+        ErrorDetail {
+            domain: Domain::USER.to_string(),
+            reason: self.reason.clone(),
+            message: self.message.clone(),
+            location: Some(location),
+            location_type: Some("body".to_string()),
+            params: BTreeMap::new(),
+        }
+    }
+}
+
+impl From<user::Model> for ExportUserRow {
+    fn from(model: user::Model) -> Self {
+        Self {
+            id: PublicId::new(model.id).encode(),
+            username: model.username,
+            email: model.email,
+            status: UserStatus::try_from(model.status)
+                .map(|status| status.to_string())
+                .unwrap_or_else(|_| model.status.to_string()),
+            role: UserRole::try_from(model.role)
+                .map(|role| role.to_string())
+                .unwrap_or_else(|_| model.role.to_string()),
+            created_at: model.created_at,
+        }
+    }
+}
+
+fn export_response(model: &data_export::Model) -> DataExportResponse {
+    let status = ExportStatus::try_from(model.status).unwrap_or(ExportStatus::Failed);
+    DataExportResponse {
+        id: model.id.to_string(),
+        format: ExportFormat::try_from(model.format).unwrap_or_default(),
+        status,
+        created_at: model.created_at,
+        completed_at: model.completed_at,
+        download_url: (status == ExportStatus::Completed)
+            .then(|| format!("/v1/user/me/export/{}/download", model.id)),
     }
 }
 
@@ -233,7 +1002,7 @@ fn map_insert_user_error(error: sea_orm::DbErr) -> AuthError {
 impl From<user::Model> for UserListItem {
     fn from(model: user::Model) -> Self {
         Self {
-            id: model.id,
+            id: PublicId::new(model.id),
             username: model.username,
             email: model.email,
             status: model.status,
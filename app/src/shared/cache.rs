@@ -0,0 +1,343 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use deadpool_redis::Pool as RedisPool;
+use deadpool_redis::redis;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::core::config::CacheConfig;
+use crate::error::RedisError;
+
+/// Redis 中缓存条目的 key 前缀
+const KEY_PREFIX: &str = "cache:";
+
+/// Redis 中命名空间代数的 key 前缀
+const GENERATION_PREFIX: &str = "cache:generation:";
+
+/// 缓存存储
+///
+/// 条目按命名空间分组，命名空间的代数（generation）是条目 key 的一部分：
+/// 失效时只需递增代数，旧条目不再被读到，随后按各自的过期时间清理。
+#[async_trait]
+pub trait CacheStore: Send + Sync + Debug {
+    /// 读取条目，不存在或已过期时返回 None
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, RedisError>;
+
+    /// 写入条目
+    async fn set(&self, key: &str, value: Vec<u8>, ttl: Duration) -> Result<(), RedisError>;
+
+    /// 命名空间当前代数
+    async fn generation(&self, namespace: &str) -> Result<u64, RedisError>;
+
+    /// 递增命名空间代数，使其下全部条目失效
+    async fn invalidate(&self, namespace: &str) -> Result<(), RedisError>;
+}
+
+/// Redis 存储，多副本共享
+#[derive(Debug, Clone)]
+pub struct RedisCacheStore {
+    pool: RedisPool,
+}
+
+impl RedisCacheStore {
+    /// 使用应用的 Redis 连接池创建
+    pub fn new(pool: RedisPool) -> Self {
+        Self { pool }
+    }
+
+    async fn connection(&self) -> Result<deadpool_redis::Connection, RedisError> {
+        self.pool
+            .get()
+            .await
+            .map_err(|e| RedisError::Connection(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl CacheStore for RedisCacheStore {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, RedisError> {
+        let mut conn = self.connection().await?;
+        redis::cmd("GET")
+            .arg(format!("{KEY_PREFIX}{key}"))
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| RedisError::Operation(e.to_string()))
+    }
+
+    async fn set(&self, key: &str, value: Vec<u8>, ttl: Duration) -> Result<(), RedisError> {
+        let mut conn = self.connection().await?;
+        redis::cmd("SET")
+            .arg(format!("{KEY_PREFIX}{key}"))
+            .arg(value)
+            .arg("EX")
+            .arg(ttl.as_secs().max(1))
+            .query_async::<()>(&mut conn)
+            .await
+            .map_err(|e| RedisError::Operation(e.to_string()))
+    }
+
+    async fn generation(&self, namespace: &str) -> Result<u64, RedisError> {
+        let mut conn = self.connection().await?;
+        let generation: Option<u64> = redis::cmd("GET")
+            .arg(format!("{GENERATION_PREFIX}{namespace}"))
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| RedisError::Operation(e.to_string()))?;
+        Ok(generation.unwrap_or_default())
+    }
+
+    async fn invalidate(&self, namespace: &str) -> Result<(), RedisError> {
+        let mut conn = self.connection().await?;
+        redis::cmd("INCR")
+            .arg(format!("{GENERATION_PREFIX}{namespace}"))
+            .query_async::<()>(&mut conn)
+            .await
+            .map_err(|e| RedisError::Operation(e.to_string()))
+    }
+}
+
+/// 进程内存存储，仅适用于单副本部署（未配置 Redis 时使用）
+#[derive(Debug)]
+pub struct MemoryCacheStore {
+    entries: Mutex<HashMap<String, (Vec<u8>, Instant)>>,
+    generations: Mutex<HashMap<String, u64>>,
+    max_entries: usize,
+}
+
+impl MemoryCacheStore {
+    /// 创建空的内存存储，条目数达到 `max_entries` 后不再写入新条目
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            entries: Mutex::default(),
+            generations: Mutex::default(),
+            max_entries,
+        }
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, HashMap<String, (Vec<u8>, Instant)>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn generations(&self) -> std::sync::MutexGuard<'_, HashMap<String, u64>> {
+        self.generations
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[async_trait]
+impl CacheStore for MemoryCacheStore {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, RedisError> {
+        let mut entries = self.entries();
+        match entries.get(key) {
+            Some((value, expires_at)) if *expires_at > Instant::now() => Ok(Some(value.clone())),
+            Some(_) => {
+                entries.remove(key);
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn set(&self, key: &str, value: Vec<u8>, ttl: Duration) -> Result<(), RedisError> {
+        let mut entries = self.entries();
+        let now = Instant::now();
+        if entries.len() >= self.max_entries {
+            entries.retain(|_, (_, expires_at)| *expires_at > now);
+        }
+        if entries.len() < self.max_entries || entries.contains_key(key) {
+            entries.insert(key.to_string(), (value, now + ttl));
+        }
+        Ok(())
+    }
+
+    async fn generation(&self, namespace: &str) -> Result<u64, RedisError> {
+        Ok(self
+            .generations()
+            .get(namespace)
+            .copied()
+            .unwrap_or_default())
+    }
+
+    async fn invalidate(&self, namespace: &str) -> Result<(), RedisError> {
+        *self.generations().entry(namespace.to_string()).or_default() += 1;
+        // 旧代数的条目不会再被读到，直接清理以释放内存
+        let prefix = format!("{namespace}:");
+        self.entries().retain(|key, _| !key.starts_with(&prefix));
+        Ok(())
+    }
+}
+
+/// 应用缓存
+///
+/// 提供旁路缓存（cache-aside）读写和按命名空间失效。缓存只是加速手段：
+/// 存储出错时记录日志并按未命中处理，不影响业务结果。
+///
+/// ```ignore
+/// let user = cache
+///     .get_or_load(USERS_CACHE, &user_id.to_string(), || self.load_user(user_id))
+///     .await?;
+/// // 修改用户后
+/// cache.invalidate(USERS_CACHE).await;
+/// ```
+#[derive(Debug, Clone)]
+pub struct Cache {
+    store: Arc<dyn CacheStore>,
+    enabled: bool,
+    ttl: Duration,
+    max_body_size: usize,
+}
+
+impl Cache {
+    pub fn new(store: Arc<dyn CacheStore>, config: &CacheConfig) -> Self {
+        Self {
+            store,
+            enabled: config.enabled,
+            ttl: Duration::from_secs(config.ttl),
+            max_body_size: config.max_body_size,
+        }
+    }
+
+    /// 配置了 Redis 时缓存在 Redis，否则缓存在进程内存
+    pub fn from_config(redis: Option<&RedisPool>, config: &CacheConfig) -> Self {
+        let store: Arc<dyn CacheStore> = match redis {
+            Some(pool) => Arc::new(RedisCacheStore::new(pool.clone())),
+            None => Arc::new(MemoryCacheStore::new(config.max_entries)),
+        };
+        Self::new(store, config)
+    }
+
+    /// 是否启用
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// 默认缓存时间
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// 可缓存的响应体上限
+    pub fn max_body_size(&self) -> usize {
+        self.max_body_size
+    }
+
+    /// 读取条目
+    pub async fn get<T: DeserializeOwned>(&self, namespace: &str, key: &str) -> Option<T> {
+        if !self.enabled {
+            return None;
+        }
+        let key = self.entry_key(namespace, key).await?;
+        self.read(&key).await
+    }
+
+    /// 写入条目
+    pub async fn set<T: Serialize>(&self, namespace: &str, key: &str, value: &T, ttl: Duration) {
+        if !self.enabled {
+            return;
+        }
+        if let Some(key) = self.entry_key(namespace, key).await {
+            self.write(&key, value, ttl).await;
+        }
+    }
+
+    /// 旁路缓存：命中时直接返回，未命中时调用 `load` 并按默认缓存时间写入
+    ///
+    /// `load` 失败时不写入缓存，错误原样返回。
+    pub async fn get_or_load<T, E, F, Fut>(
+        &self,
+        namespace: &str,
+        key: &str,
+        load: F,
+    ) -> Result<T, E>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        self.get_or_load_with_ttl(namespace, key, self.ttl, load)
+            .await
+    }
+
+    /// 同 [`Cache::get_or_load`]，使用指定的缓存时间
+    ///
+    /// 条目 key 在调用 `load` 之前确定：加载期间命名空间被失效时，
+    /// 加载到的旧值写入旧代数，不会被之后的读取命中。
+    pub async fn get_or_load_with_ttl<T, E, F, Fut>(
+        &self,
+        namespace: &str,
+        key: &str,
+        ttl: Duration,
+        load: F,
+    ) -> Result<T, E>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let key = match self.enabled {
+            true => self.entry_key(namespace, key).await,
+            false => None,
+        };
+        let Some(key) = key else {
+            return load().await;
+        };
+        if let Some(value) = self.read(&key).await {
+            return Ok(value);
+        }
+        let value = load().await?;
+        self.write(&key, &value, ttl).await;
+        Ok(value)
+    }
+
+    /// 使命名空间下的全部条目失效，供服务层在写操作之后调用
+    pub async fn invalidate(&self, namespace: &str) {
+        if !self.enabled {
+            return;
+        }
+        if let Err(e) = self.store.invalidate(namespace).await {
+            tracing::warn!(error = %e, namespace, "failed to invalidate cache");
+        }
+    }
+
+    async fn read<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        match self.store.get(key).await {
+            Ok(Some(bytes)) => rmp_serde::from_slice(&bytes)
+                .inspect_err(|e| tracing::warn!(error = %e, key, "failed to decode cache entry"))
+                .ok(),
+            Ok(None) => None,
+            Err(e) => {
+                tracing::warn!(error = %e, key, "failed to read cache");
+                None
+            }
+        }
+    }
+
+    async fn write<T: Serialize>(&self, key: &str, value: &T, ttl: Duration) {
+        let result = match rmp_serde::to_vec(value) {
+            Ok(bytes) => self.store.set(key, bytes, ttl).await,
+            Err(e) => Err(RedisError::Operation(e.to_string())),
+        };
+        if let Err(e) = result {
+            tracing::warn!(error = %e, key, "failed to write cache");
+        }
+    }
+
+    /// 条目 key：命名空间、当前代数和调用方的 key，读取代数失败时返回 None（按未命中处理）
+    async fn entry_key(&self, namespace: &str, key: &str) -> Option<String> {
+        match self.store.generation(namespace).await {
+            Ok(generation) => Some(format!("{namespace}:{generation}:{key}")),
+            Err(e) => {
+                tracing::warn!(error = %e, namespace, "failed to read cache generation");
+                None
+            }
+        }
+    }
+}
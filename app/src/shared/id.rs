@@ -0,0 +1,334 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use deadpool_redis::Pool as RedisPool;
+use deadpool_redis::redis;
+use sonyflake::Sonyflake;
+use uuid::Uuid;
+
+use crate::error::{IdGeneratorError, RedisError};
+
+/// Redis 中机器 ID 租约的 key 前缀
+const LEASE_KEY_PREFIX: &str = "id_generator:machine:";
+
+/// 仅当租约仍归自己所有时才续期
+const RENEW_LEASE_SCRIPT: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('EXPIRE', KEYS[1], ARGV[2])
+end
+return 0
+"#;
+
+/// 租约丢失后重新抢占机器 ID 的最长退避间隔
+const MAX_REACQUIRE_BACKOFF: Duration = Duration::from_secs(30);
+
+/// 分布式 ID 生成器
+///
+/// 基于 Sonyflake 生成全局有序、可按时间排序的 64 位 ID，
+/// 供需要跨副本唯一主键的新实体使用（配合 `migration::pk_snowflake`）。
+/// ID 结构：39 位时间（10ms 精度）+ 8 位序列号 + 16 位机器 ID。
+/// 机器 ID 来自 Redis 租约时，租约丢失期间停止生成 ID，
+/// 重新抢到机器 ID 后用新的机器 ID 重建 Sonyflake 继续生成。
+#[derive(Debug, Clone)]
+pub struct IdGenerator {
+    /// 当前机器 ID 对应的 Sonyflake，所有克隆共享同一个实例
+    flake: Arc<Mutex<Flake>>,
+    start_time: DateTime<Utc>,
+
+    /// 机器 ID 租约，固定机器 ID 时为 None
+    lease: Option<MachineIdLease>,
+}
+
+/// 绑定到某个机器 ID 的 Sonyflake
+#[derive(Debug)]
+struct Flake {
+    inner: Sonyflake,
+    machine_id: u16,
+}
+
+impl Flake {
+    fn new(machine_id: u16, start_time: DateTime<Utc>) -> Result<Self, sonyflake::Error> {
+        let inner = Sonyflake::builder()
+            .start_time(start_time)
+            .machine_id(&|| Ok(machine_id))
+            .finalize()?;
+
+        Ok(Self { inner, machine_id })
+    }
+}
+
+impl IdGenerator {
+    /// 创建 ID 生成器
+    ///
+    /// # 参数
+    /// * `machine_id` - 当前副本的机器 ID，同一时刻各副本必须互不相同
+    /// * `start_time` - ID 时间戳起点
+    pub fn new(machine_id: u16, start_time: DateTime<Utc>) -> Result<Self, sonyflake::Error> {
+        Ok(Self {
+            flake: Arc::new(Mutex::new(Flake::new(machine_id, start_time)?)),
+            start_time,
+            lease: None,
+        })
+    }
+
+    /// 使用 Redis 租约分配的机器 ID 创建 ID 生成器
+    ///
+    /// 租约丢失期间 `next_id` 返回错误；租约重新分配到新的机器 ID 后自动切换。
+    pub fn leased(
+        lease: &MachineIdLease,
+        start_time: DateTime<Utc>,
+    ) -> Result<Self, sonyflake::Error> {
+        let mut generator = Self::new(lease.machine_id(), start_time)?;
+        generator.lease = Some(lease.clone());
+        Ok(generator)
+    }
+
+    /// 当前副本的机器 ID
+    pub fn machine_id(&self) -> u16 {
+        match &self.lease {
+            Some(lease) => lease.machine_id(),
+            None => self.flake().machine_id,
+        }
+    }
+
+    /// 生成下一个 ID
+    ///
+    /// 同一 10ms 内超过 256 个 ID 时会短暂阻塞等待下一个时间片。
+    /// 返回 `i64` 以便直接写入 PostgreSQL `BIGINT` 列（最高位恒为 0）。
+    /// 机器 ID 租约已丢失时返回 `LeaseLost`，不再生成可能重复的 ID。
+    pub fn next_id(&self) -> Result<i64, IdGeneratorError> {
+        let inner = self.current()?;
+        Ok(inner.next_id()? as i64)
+    }
+
+    /// 取得当前机器 ID 对应的 Sonyflake，租约换了机器 ID 时先重建
+    fn current(&self) -> Result<Sonyflake, IdGeneratorError> {
+        let mut flake = self.flake();
+        if let Some(lease) = &self.lease {
+            let LeaseState { machine_id, lost } = *lease.state();
+            if lost {
+                return Err(IdGeneratorError::LeaseLost(machine_id));
+            }
+            if flake.machine_id != machine_id {
+                *flake = Flake::new(machine_id, self.start_time)?;
+            }
+        }
+        Ok(flake.inner.clone())
+    }
+
+    fn flake(&self) -> MutexGuard<'_, Flake> {
+        self.flake
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// 由主机名派生机器 ID
+///
+/// 对 `HOSTNAME` 环境变量或 `/etc/hostname` 做 FNV-1a 哈希后折叠为 16 位。
+/// 无法获取主机名时返回 None。
+///
+/// 不同主机名可能折叠为同一个机器 ID 且无从察觉，只适用于单副本部署；
+/// 多副本部署必须配置 Redis（租约分配）或为每个副本指定不同的 `MACHINE_ID`。
+pub fn hostname_machine_id() -> Option<u16> {
+    let hostname = std::env::var("HOSTNAME")
+        .ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())?;
+
+    Some(machine_id_from_name(&hostname))
+}
+
+/// 将任意名称哈希为机器 ID（FNV-1a，结果在各平台、各版本间稳定）
+pub fn machine_id_from_name(name: &str) -> u16 {
+    let hash = name.bytes().fold(0x811c_9dc5_u32, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    });
+    ((hash >> 16) ^ (hash & 0xffff)) as u16
+}
+
+/// Redis 分配的机器 ID 租约
+///
+/// 由后台续期任务维护：租约丢失时标记为已丢失，重新抢到机器 ID 后更新为新的机器 ID。
+#[derive(Debug, Clone)]
+pub struct MachineIdLease {
+    state: Arc<Mutex<LeaseState>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct LeaseState {
+    machine_id: u16,
+    lost: bool,
+}
+
+impl MachineIdLease {
+    /// 创建尚未丢失的租约，由 `lease_machine_id` 在抢到机器 ID 后调用
+    #[doc(hidden)]
+    pub fn new(machine_id: u16) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(LeaseState {
+                machine_id,
+                lost: false,
+            })),
+        }
+    }
+
+    /// 标记租约已丢失，所有共享该租约的生成器随即停止生成 ID
+    #[doc(hidden)]
+    pub fn mark_lost(&self) {
+        self.state().lost = true;
+    }
+
+    /// 重新抢到机器 ID 后更新租约，共享该租约的生成器改用新的机器 ID 继续生成
+    #[doc(hidden)]
+    pub fn reassign(&self, machine_id: u16) {
+        *self.state() = LeaseState {
+            machine_id,
+            lost: false,
+        };
+    }
+
+    /// 租约对应的机器 ID（租约已丢失时为最后持有的机器 ID）
+    pub fn machine_id(&self) -> u16 {
+        self.state().machine_id
+    }
+
+    /// 租约是否已丢失（被其他副本占用，或续期失败到租约过期），重新抢到机器 ID 前保持丢失
+    pub fn is_lost(&self) -> bool {
+        self.state().lost
+    }
+
+    fn state(&self) -> MutexGuard<'_, LeaseState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// 通过 Redis 租约分配机器 ID
+///
+/// 从 `preferred` 开始依次尝试 `SET NX EX` 抢占租约，抢到后在后台
+/// 每 `ttl / 3` 秒续期一次，保证多个副本不会同时持有同一个机器 ID。
+/// 租约被其他副本占用、或续期失败到下次续期前租约就会过期时，标记为已丢失，
+/// 并按指数退避重新抢占机器 ID，抢到后更新租约继续续期。
+///
+/// # 参数
+/// * `pool` - Redis 连接池
+/// * `preferred` - 优先尝试的机器 ID（通常由主机名派生，重启后尽量保持不变）
+/// * `ttl` - 租约时长（秒）
+///
+/// # 返回
+/// 成功返回抢到的租约，所有 ID 均被占用或 Redis 操作失败时返回 RedisError
+pub async fn lease_machine_id(
+    pool: &RedisPool,
+    preferred: u16,
+    ttl: u64,
+) -> Result<MachineIdLease, RedisError> {
+    let owner = Uuid::new_v4().to_string();
+    let machine_id = acquire_machine_id(pool, preferred, &owner, ttl).await?;
+    let lease = MachineIdLease::new(machine_id);
+    spawn_lease_renewal(pool.clone(), owner, ttl, lease.clone());
+    Ok(lease)
+}
+
+/// 从 `preferred` 开始依次抢占第一个空闲的机器 ID
+async fn acquire_machine_id(
+    pool: &RedisPool,
+    preferred: u16,
+    owner: &str,
+    ttl: u64,
+) -> Result<u16, RedisError> {
+    let mut conn = pool
+        .get()
+        .await
+        .map_err(|e| RedisError::Connection(e.to_string()))?;
+
+    for offset in 0..=u16::MAX {
+        let machine_id = preferred.wrapping_add(offset);
+        let acquired: Option<String> = redis::cmd("SET")
+            .arg(lease_key(machine_id))
+            .arg(owner)
+            .arg("NX")
+            .arg("EX")
+            .arg(ttl)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| RedisError::Operation(e.to_string()))?;
+
+        if acquired.is_some() {
+            return Ok(machine_id);
+        }
+    }
+
+    Err(RedisError::Operation("没有可用的机器 ID".to_string()))
+}
+
+fn lease_key(machine_id: u16) -> String {
+    format!("{LEASE_KEY_PREFIX}{machine_id}")
+}
+
+/// 后台续期机器 ID 租约，租约丢失后标记 `lease` 并重新抢占机器 ID
+fn spawn_lease_renewal(pool: RedisPool, owner: String, ttl: u64, lease: MachineIdLease) {
+    tokio::spawn(async move {
+        let interval = Duration::from_secs((ttl / 3).max(1));
+        let mut renewed_at = Instant::now();
+        loop {
+            tokio::time::sleep(interval).await;
+
+            let key = lease_key(lease.machine_id());
+            let renewed = match pool.get().await {
+                Ok(mut conn) => redis::cmd("EVAL")
+                    .arg(RENEW_LEASE_SCRIPT)
+                    .arg(1)
+                    .arg(&key)
+                    .arg(&owner)
+                    .arg(ttl)
+                    .query_async::<i64>(&mut conn)
+                    .await
+                    .map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+
+            match renewed {
+                Ok(1) => {
+                    renewed_at = Instant::now();
+                    continue;
+                }
+                Ok(_) => {
+                    tracing::error!(key = %key, "机器 ID 租约已被其他副本占用，暂停生成 ID");
+                }
+                // 下次续期前租约就会过期，其他副本随时可能接手该机器 ID
+                Err(error) if renewed_at.elapsed() + interval >= Duration::from_secs(ttl) => {
+                    tracing::error!(key = %key, error = %error, "机器 ID 租约续期失败且即将过期，暂停生成 ID");
+                }
+                Err(error) => {
+                    tracing::warn!(key = %key, error = %error, "机器 ID 租约续期失败");
+                    continue;
+                }
+            }
+
+            lease.mark_lost();
+            let machine_id = reacquire(&pool, lease.machine_id(), &owner, ttl).await;
+            lease.reassign(machine_id);
+            renewed_at = Instant::now();
+            tracing::info!(machine_id, "已重新获得机器 ID 租约，恢复生成 ID");
+        }
+    });
+}
+
+/// 按指数退避重新抢占机器 ID，直到成功
+async fn reacquire(pool: &RedisPool, preferred: u16, owner: &str, ttl: u64) -> u16 {
+    let mut backoff = Duration::from_secs(1);
+    loop {
+        match acquire_machine_id(pool, preferred, owner, ttl).await {
+            Ok(machine_id) => return machine_id,
+            Err(error) => {
+                tracing::warn!(error = %error, retry_in = ?backoff, "重新获取机器 ID 租约失败");
+            }
+        }
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_REACQUIRE_BACKOFF);
+    }
+}
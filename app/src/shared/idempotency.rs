@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use deadpool_redis::Pool as RedisPool;
+use deadpool_redis::redis;
+use serde::{Deserialize, Serialize};
+
+use crate::error::RedisError;
+
+/// Redis 中幂等记录的 key 前缀
+const KEY_PREFIX: &str = "idempotency:";
+
+/// 仅当 key 仍为自己的占用记录时才写入完成的响应
+const COMPLETE_SCRIPT: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('SET', KEYS[1], ARGV[2], 'EX', ARGV[3])
+end
+return nil
+"#;
+
+/// 仅当 key 仍为自己的占用记录时才删除
+const RELEASE_SCRIPT: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('DEL', KEYS[1])
+end
+return 0
+"#;
+
+/// 幂等 key 对应的记录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IdempotencyRecord {
+    /// 首个请求处理中
+    InFlight {
+        /// 请求指纹（方法、路径、请求体的摘要）
+        fingerprint: String,
+        /// 占用者标识，保证只有占用者能写入结果或释放
+        owner: String,
+    },
+
+    /// 首个请求已完成
+    Completed {
+        /// 请求指纹
+        fingerprint: String,
+        /// 保存的响应
+        response: StoredResponse,
+    },
+}
+
+impl IdempotencyRecord {
+    /// 请求指纹
+    pub fn fingerprint(&self) -> &str {
+        match self {
+            Self::InFlight { fingerprint, .. } | Self::Completed { fingerprint, .. } => fingerprint,
+        }
+    }
+
+    fn encode(&self) -> Result<Vec<u8>, RedisError> {
+        rmp_serde::to_vec(self).map_err(|e| RedisError::Operation(e.to_string()))
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, RedisError> {
+        rmp_serde::from_slice(bytes).map_err(|e| RedisError::Operation(e.to_string()))
+    }
+}
+
+/// 保存的响应（状态码、响应头、响应体）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredResponse {
+    /// 状态码
+    pub status: u16,
+    /// 响应头（不含 `x-request-id` 等随请求变化的头）
+    pub headers: Vec<(String, String)>,
+    /// 响应体
+    #[serde(with = "serde_bytes")]
+    pub body: Vec<u8>,
+}
+
+/// 幂等记录存储
+///
+/// 同一 key 的并发请求中只有一个能占用成功；占用者完成后写入响应，失败或取消时释放。
+#[async_trait]
+pub trait IdempotencyStore: Send + Sync + Debug {
+    /// 以 `owned`（处理中记录）占用 key
+    ///
+    /// 成功返回 None，key 已存在时返回已有记录。
+    async fn acquire(
+        &self,
+        key: &str,
+        owned: &IdempotencyRecord,
+        ttl: Duration,
+    ) -> Result<Option<IdempotencyRecord>, RedisError>;
+
+    /// 仍持有占用时写入完成的记录，占用已过期被他人取得时不做改动
+    async fn complete(
+        &self,
+        key: &str,
+        owned: &IdempotencyRecord,
+        record: &IdempotencyRecord,
+        ttl: Duration,
+    ) -> Result<(), RedisError>;
+
+    /// 仍持有占用时释放 key，使客户端可以用同一个 key 重试
+    async fn release(&self, key: &str, owned: &IdempotencyRecord) -> Result<(), RedisError>;
+}
+
+/// Redis 存储，多副本共享（需要 Redis 7.0 及以上，占用依赖 `SET NX GET`）
+#[derive(Debug, Clone)]
+pub struct RedisIdempotencyStore {
+    pool: RedisPool,
+}
+
+impl RedisIdempotencyStore {
+    /// 使用应用的 Redis 连接池创建
+    pub fn new(pool: RedisPool) -> Self {
+        Self { pool }
+    }
+
+    async fn connection(&self) -> Result<deadpool_redis::Connection, RedisError> {
+        self.pool
+            .get()
+            .await
+            .map_err(|e| RedisError::Connection(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl IdempotencyStore for RedisIdempotencyStore {
+    async fn acquire(
+        &self,
+        key: &str,
+        owned: &IdempotencyRecord,
+        ttl: Duration,
+    ) -> Result<Option<IdempotencyRecord>, RedisError> {
+        let mut conn = self.connection().await?;
+        let key = format!("{KEY_PREFIX}{key}");
+        // SET NX GET：占用成功返回 nil，已存在时返回原值且不覆盖
+        let existing: Option<Vec<u8>> = redis::cmd("SET")
+            .arg(&key)
+            .arg(owned.encode()?)
+            .arg("NX")
+            .arg("GET")
+            .arg("EX")
+            .arg(ttl.as_secs().max(1))
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| RedisError::Operation(e.to_string()))?;
+        existing
+            .map(|bytes| IdempotencyRecord::decode(&bytes))
+            .transpose()
+    }
+
+    async fn complete(
+        &self,
+        key: &str,
+        owned: &IdempotencyRecord,
+        record: &IdempotencyRecord,
+        ttl: Duration,
+    ) -> Result<(), RedisError> {
+        let mut conn = self.connection().await?;
+        redis::cmd("EVAL")
+            .arg(COMPLETE_SCRIPT)
+            .arg(1)
+            .arg(format!("{KEY_PREFIX}{key}"))
+            .arg(owned.encode()?)
+            .arg(record.encode()?)
+            .arg(ttl.as_secs().max(1))
+            .query_async::<()>(&mut conn)
+            .await
+            .map_err(|e| RedisError::Operation(e.to_string()))
+    }
+
+    async fn release(&self, key: &str, owned: &IdempotencyRecord) -> Result<(), RedisError> {
+        let mut conn = self.connection().await?;
+        redis::cmd("EVAL")
+            .arg(RELEASE_SCRIPT)
+            .arg(1)
+            .arg(format!("{KEY_PREFIX}{key}"))
+            .arg(owned.encode()?)
+            .query_async::<()>(&mut conn)
+            .await
+            .map_err(|e| RedisError::Operation(e.to_string()))
+    }
+}
+
+/// 进程内存存储，仅适用于单副本部署（未配置 Redis 时使用）
+#[derive(Debug, Default)]
+pub struct MemoryIdempotencyStore {
+    records: Mutex<HashMap<String, (IdempotencyRecord, Instant)>>,
+}
+
+impl MemoryIdempotencyStore {
+    /// 创建空的内存存储
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 取得记录表并清理已过期的记录
+    fn records(&self) -> std::sync::MutexGuard<'_, HashMap<String, (IdempotencyRecord, Instant)>> {
+        let mut records = self
+            .records
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let now = Instant::now();
+        records.retain(|_, (_, expires_at)| *expires_at > now);
+        records
+    }
+}
+
+#[async_trait]
+impl IdempotencyStore for MemoryIdempotencyStore {
+    async fn acquire(
+        &self,
+        key: &str,
+        owned: &IdempotencyRecord,
+        ttl: Duration,
+    ) -> Result<Option<IdempotencyRecord>, RedisError> {
+        let mut records = self.records();
+        if let Some((existing, _)) = records.get(key) {
+            return Ok(Some(existing.clone()));
+        }
+        records.insert(key.to_string(), (owned.clone(), Instant::now() + ttl));
+        Ok(None)
+    }
+
+    async fn complete(
+        &self,
+        key: &str,
+        owned: &IdempotencyRecord,
+        record: &IdempotencyRecord,
+        ttl: Duration,
+    ) -> Result<(), RedisError> {
+        let mut records = self.records();
+        if let Some(entry) = records.get_mut(key)
+            && entry.0 == *owned
+        {
+            *entry = (record.clone(), Instant::now() + ttl);
+        }
+        Ok(())
+    }
+
+    async fn release(&self, key: &str, owned: &IdempotencyRecord) -> Result<(), RedisError> {
+        let mut records = self.records();
+        if records.get(key).is_some_and(|(record, _)| record == owned) {
+            records.remove(key);
+        }
+        Ok(())
+    }
+}
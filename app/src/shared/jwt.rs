@@ -2,11 +2,13 @@ use chrono::Utc;
 use jsonwebtoken::{DecodingKey, EncodingKey, Header, TokenData, Validation, decode, encode};
 use serde::{Deserialize, Serialize};
 
+use super::PublicId;
+
 /// JWT Claims
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
-    /// 用户 ID
-    pub sub: i32,
+    /// 用户 ID（对外编码后的形式）
+    pub sub: PublicId,
 
     /// 过期时间（Unix timestamp）
     pub exp: i64,
@@ -20,7 +22,7 @@ impl Claims {
     pub fn new(user_id: i32, expires_in_secs: i64) -> Self {
         let now = Utc::now().timestamp();
         Self {
-            sub: user_id,
+            sub: PublicId::new(user_id),
             exp: now + expires_in_secs,
             iat: now,
         }
@@ -82,6 +84,6 @@ impl JwtService {
     /// # 返回
     /// 返回用户 ID，如果 token 无效或过期则返回错误
     pub fn extract_user_id(&self, token: &str) -> Result<i32, jsonwebtoken::errors::Error> {
-        self.verify_token(token).map(|data| data.claims.sub)
+        self.verify_token(token).map(|data| data.claims.sub.get())
     }
 }
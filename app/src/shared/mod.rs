@@ -1,8 +1,26 @@
+/// 缓存（旁路缓存与按命名空间失效，Redis 或进程内存）
+pub mod cache;
 /// 从应用状态中提取服务的 Trait
 mod from_state;
+/// 分布式 ID 生成器（使用 Sonyflake）
+pub mod id;
+/// 幂等请求记录存储（Redis 或进程内存）
+pub mod idempotency;
 /// JWT 令牌生成和验证服务
 pub mod jwt;
 /// 密码哈希和验证功能（使用 Argon2）
 pub mod password;
+/// 对外 ID 混淆编码（使用 sqids）
+pub mod public_id;
+/// 文件存储抽象和本地文件系统实现
+pub mod storage;
 
+pub use cache::{Cache, CacheStore, MemoryCacheStore, RedisCacheStore};
 pub use from_state::*;
+pub use id::{IdGenerator, MachineIdLease};
+pub use idempotency::{
+    IdempotencyRecord, IdempotencyStore, MemoryIdempotencyStore, RedisIdempotencyStore,
+    StoredResponse,
+};
+pub use public_id::{PublicId, PublicIdCodec};
+pub use storage::{LocalStorage, Storage};
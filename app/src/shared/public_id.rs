@@ -0,0 +1,209 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sqids::Sqids;
+
+use crate::core::config::PublicIdConfig;
+use crate::error::{ConfigError, ValidationError};
+
+/// 全局 ID 编码器，启动时由 [`PublicId::install`] 设置
+static CODEC: OnceLock<PublicIdCodec> = OnceLock::new();
+
+/// 对外 ID 编解码器
+///
+/// 基于 sqids 把数据库自增 ID 编码为短字符串，避免暴露注册量和可枚举的 ID。
+/// sqids 本身没有盐值概念，这里用盐值对字母表做一次确定性洗牌，
+/// 不同盐值得到的编码互不兼容。
+#[derive(Debug, Clone)]
+pub struct PublicIdCodec {
+    sqids: Sqids,
+    /// 洗牌后的字母表和最小长度，二者相同即编码结果相同
+    alphabet: Vec<char>,
+    min_length: u8,
+}
+
+impl PublicIdCodec {
+    /// 根据配置创建编解码器
+    ///
+    /// # 参数
+    /// * `config` - 对外 ID 编码配置
+    ///
+    /// # 返回
+    /// 字母表无效时返回 ConfigError
+    pub fn new(config: &PublicIdConfig) -> Result<Self, ConfigError> {
+        let alphabet = salted_alphabet(&config.alphabet, &config.salt);
+        let sqids = Sqids::builder()
+            .alphabet(alphabet.clone())
+            .min_length(config.min_length)
+            .build()
+            .map_err(|e| ConfigError::Invalid(format!("ID 编码器初始化失败：{}", e)))?;
+
+        Ok(Self {
+            sqids,
+            alphabet,
+            min_length: config.min_length,
+        })
+    }
+
+    /// 编码数据库 ID
+    ///
+    /// `i32` 按位转为 `u32` 后编码，保证任意 ID 都能编码且可逆。
+    pub fn encode(&self, id: i32) -> String {
+        self.sqids
+            .encode(&[u64::from(id as u32)])
+            .expect("sqids 编码单个数字不应失败")
+    }
+
+    /// 解码对外 ID
+    ///
+    /// 只接受规范编码：解码后重新编码必须与输入一致，
+    /// 防止同一个 ID 存在多个可用的字符串形式。
+    pub fn decode(&self, value: &str) -> Option<i32> {
+        let [number] = self.sqids.decode(value)[..] else {
+            return None;
+        };
+        let id = u32::try_from(number).ok()? as i32;
+
+        (self.encode(id) == value).then_some(id)
+    }
+}
+
+impl PartialEq for PublicIdCodec {
+    fn eq(&self, other: &Self) -> bool {
+        self.alphabet == other.alphabet && self.min_length == other.min_length
+    }
+}
+
+impl Eq for PublicIdCodec {}
+
+impl Default for PublicIdCodec {
+    fn default() -> Self {
+        Self::new(&PublicIdConfig::default()).expect("默认 ID 编码配置必须有效")
+    }
+}
+
+/// 用盐值对字母表做确定性洗牌（与 hashids 的 consistent shuffle 相同）
+fn salted_alphabet(alphabet: &str, salt: &str) -> Vec<char> {
+    let mut chars: Vec<char> = alphabet.chars().collect();
+    let salt = salt.as_bytes();
+    if salt.is_empty() {
+        return chars;
+    }
+
+    let (mut v, mut p) = (0usize, 0usize);
+    for i in (1..chars.len()).rev() {
+        v %= salt.len();
+        let n = usize::from(salt[v]);
+        p += n;
+        let j = (n + v + p) % i;
+        chars.swap(i, j);
+        v += 1;
+    }
+    chars
+}
+
+/// 对外暴露的资源 ID
+///
+/// 内部持有数据库 ID，序列化、反序列化和路径参数解析时自动编解码，
+/// 例如 `Path<PublicId>` 可直接用作路径提取器。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PublicId(i32);
+
+impl PublicId {
+    /// 安装全局编解码器
+    ///
+    /// 应在应用启动时调用。重复安装相同配置（字母表、盐值、最小长度）的编码器时
+    /// 不做任何事，便于同一进程内多次初始化应用；已安装（或已被默认初始化）的
+    /// 编码器配置不同时返回错误，避免同一进程内出现互不兼容的对外 ID。
+    pub fn install(codec: PublicIdCodec) -> Result<(), ConfigError> {
+        let installed = CODEC.get_or_init(|| codec.clone());
+        if *installed == codec {
+            Ok(())
+        } else {
+            Err(ConfigError::Invalid(
+                "ID 编码器已使用不同的配置初始化".to_string(),
+            ))
+        }
+    }
+
+    /// 从数据库 ID 创建
+    pub const fn new(id: i32) -> Self {
+        Self(id)
+    }
+
+    /// 获取数据库 ID
+    pub const fn get(self) -> i32 {
+        self.0
+    }
+
+    /// 编码为对外字符串
+    pub fn encode(self) -> String {
+        codec().encode(self.0)
+    }
+
+    /// 从对外字符串解码
+    pub fn decode(value: &str) -> Option<Self> {
+        codec().decode(value).map(Self)
+    }
+}
+
+/// 获取全局编解码器，未安装时使用默认配置
+fn codec() -> &'static PublicIdCodec {
+    CODEC.get_or_init(PublicIdCodec::default)
+}
+
+impl From<i32> for PublicId {
+    fn from(id: i32) -> Self {
+        Self(id)
+    }
+}
+
+impl From<PublicId> for i32 {
+    fn from(id: PublicId) -> Self {
+        id.0
+    }
+}
+
+impl fmt::Display for PublicId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.encode())
+    }
+}
+
+impl FromStr for PublicId {
+    type Err = ValidationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::decode(s).ok_or_else(|| ValidationError::custom(format!("无效的 ID：{}", s)))
+    }
+}
+
+impl Serialize for PublicId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.encode())
+    }
+}
+
+impl<'de> Deserialize<'de> for PublicId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Cow::<str>::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl JsonSchema for PublicId {
+    fn schema_name() -> Cow<'static, str> {
+        "PublicId".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "description": "对外资源 ID（sqids 编码）"
+        })
+    }
+}
@@ -0,0 +1,120 @@
+use std::fmt::Debug;
+use std::io::ErrorKind;
+use std::path::{Component, Path, PathBuf};
+
+use async_trait::async_trait;
+
+use crate::core::config::StorageConfig;
+use crate::error::FileUploadError;
+
+/// 文件存储抽象
+///
+/// 业务代码只通过 key（如 `avatars/xxx/avatar.png`）读写文件，
+/// 具体存储位置由实现决定，便于后续切换到对象存储。
+#[async_trait]
+pub trait Storage: Send + Sync + Debug {
+    /// 写入文件，已存在时覆盖
+    async fn put(
+        &self,
+        key: &str,
+        bytes: Vec<u8>,
+        content_type: &str,
+    ) -> Result<(), FileUploadError>;
+
+    /// 读取文件内容，文件不存在时返回 None
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, FileUploadError>;
+
+    /// 删除文件，文件不存在时视为成功
+    async fn delete(&self, key: &str) -> Result<(), FileUploadError>;
+
+    /// 获取文件的对外访问 URL
+    fn public_url(&self, key: &str) -> String;
+}
+
+/// 本地文件系统存储
+///
+/// 公开存储的文件写入 `local_dir`，通过 `/static` 路由的 ServeDir 对外提供；
+/// 私有存储写入 `private_dir`，只能通过 [`Storage::get`] 读取。
+#[derive(Debug, Clone)]
+pub struct LocalStorage {
+    root: PathBuf,
+    public_url_prefix: String,
+}
+
+impl LocalStorage {
+    /// 根据存储配置创建本地存储
+    pub fn new(config: &StorageConfig) -> Self {
+        Self {
+            root: PathBuf::from(&config.local_dir),
+            public_url_prefix: config.public_url_prefix.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// 根据存储配置创建私有存储（不对外提供访问 URL）
+    pub fn private(config: &StorageConfig) -> Self {
+        Self {
+            root: PathBuf::from(&config.private_dir),
+            public_url_prefix: String::new(),
+        }
+    }
+
+    /// 存储根目录
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// 将 key 解析为根目录下的路径，拒绝绝对路径和 `..`
+    fn resolve(&self, key: &str) -> Result<PathBuf, FileUploadError> {
+        let relative = Path::new(key);
+        let is_safe = !key.is_empty()
+            && relative
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)));
+        if !is_safe {
+            return Err(FileUploadError::Failed(format!("非法的存储 key：{}", key)));
+        }
+        Ok(self.root.join(relative))
+    }
+}
+
+#[async_trait]
+impl Storage for LocalStorage {
+    async fn put(
+        &self,
+        key: &str,
+        bytes: Vec<u8>,
+        _content_type: &str,
+    ) -> Result<(), FileUploadError> {
+        let path = self.resolve(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| FileUploadError::Failed(format!("创建目录失败：{}", e)))?;
+        }
+        tokio::fs::write(&path, bytes)
+            .await
+            .map_err(|e| FileUploadError::Failed(format!("写入文件失败：{}", e)))
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, FileUploadError> {
+        let path = self.resolve(key)?;
+        match tokio::fs::read(&path).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(FileUploadError::Failed(format!("读取文件失败：{}", e))),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), FileUploadError> {
+        let path = self.resolve(key)?;
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(FileUploadError::Failed(format!("删除文件失败：{}", e))),
+        }
+    }
+
+    fn public_url(&self, key: &str) -> String {
+        format!("{}/{}", self.public_url_prefix, key)
+    }
+}
@@ -3,7 +3,45 @@
 //! 测试放在 `app/tests`，避免生产模块携带测试专用的模块声明。
 //! 每个子模块对应一个被测核心能力。
 
+#[path = "core/body_limit.rs"]
+mod body_limit;
+#[path = "core/cache.rs"]
+mod cache;
 #[path = "core/database_bootstrap.rs"]
 mod database_bootstrap;
+#[path = "core/encoding.rs"]
+mod encoding;
+#[path = "core/error_docs.rs"]
+mod error_docs;
+#[path = "core/etag.rs"]
+mod etag;
+#[path = "core/extract.rs"]
+mod extract;
+#[path = "core/fields.rs"]
+mod fields;
+#[path = "core/i18n.rs"]
+mod i18n;
+#[path = "core/idempotency.rs"]
+mod idempotency;
+#[path = "core/middleware_errors.rs"]
+mod middleware_errors;
+#[path = "core/page_links.rs"]
+mod page_links;
 #[path = "core/pagination.rs"]
 mod pagination;
+#[path = "core/precondition.rs"]
+mod precondition;
+#[path = "core/problem.rs"]
+mod problem;
+#[path = "core/request_id.rs"]
+mod request_id;
+#[path = "core/security_headers.rs"]
+mod security_headers;
+#[path = "core/storage_config.rs"]
+mod storage_config;
+#[path = "core/streaming.rs"]
+mod streaming;
+#[path = "core/timeout.rs"]
+mod timeout;
+#[path = "core/validated.rs"]
+mod validated;
@@ -0,0 +1,191 @@
+//! 请求体大小限制与解压测试。
+//!
+//! 覆盖 `Content-Length` 超限直接返回 413、无长度的流式请求体读取时超限、
+//! 按路径前缀覆盖上限、上传路由按文件大小推导上限、gzip 请求体解压与解压后超限（压缩炸弹）、
+//! 不支持的 `Content-Encoding` 返回 415，以及配置校验。
+
+use std::io::Write;
+
+use app::core::config::{BodyLimitConfig, ConfigSection};
+use app::core::middleware::{BodyLimit, MULTIPART_OVERHEAD, body_limit_middleware};
+use app::{ApiResponse, Json};
+use axum::Router;
+use axum::body::{Body, Bytes, to_bytes};
+use axum::extract::DefaultBodyLimit;
+use axum::http::header::{ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_TYPE};
+use axum::http::{Request, StatusCode};
+use axum::response::Response;
+use axum::routing::post;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use futures_util::stream;
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+async fn echo(Json(value): Json<Value>) -> ApiResponse<Value> {
+    ApiResponse::success(value)
+}
+
+fn router(body_limit: BodyLimit) -> Router {
+    Router::new()
+        .route("/v1/items", post(echo))
+        .route("/v1/user/login", post(echo))
+        .route("/v1/user/loginx", post(echo))
+        .layer(DefaultBodyLimit::disable())
+        .layer(axum::middleware::from_fn_with_state(
+            body_limit,
+            body_limit_middleware,
+        ))
+}
+
+async fn send(body_limit: BodyLimit, request: Request<Body>) -> (Response, Value) {
+    let response = router(body_limit).oneshot(request).await.unwrap();
+    let (parts, body) = response.into_parts();
+    let bytes = to_bytes(body, usize::MAX).await.unwrap();
+    let body = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    (Response::from_parts(parts, Body::empty()), body)
+}
+
+fn post_json(uri: &str, encoding: Option<&str>, body: impl Into<Body>) -> Request<Body> {
+    let mut request = Request::post(uri).header(CONTENT_TYPE, "application/json");
+    if let Some(encoding) = encoding {
+        request = request.header(CONTENT_ENCODING, encoding);
+    }
+    request.body(body.into()).unwrap()
+}
+
+fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn payload(size: usize) -> String {
+    json!({"name": "x".repeat(size)}).to_string()
+}
+
+fn assert_too_large(response: &Response, body: &Value) {
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(body["error"]["errors"][0]["domain"], "validation");
+    assert_eq!(body["error"]["errors"][0]["reason"], "PAYLOAD_TOO_LARGE");
+}
+
+#[tokio::test]
+async fn rejects_oversized_bodies() {
+    // 声明了 Content-Length 的请求体在读取前拒绝
+    let request = post_json("/v1/items", None, payload(2048));
+    let (response, body) = send(BodyLimit::new(1024), request).await;
+    assert_too_large(&response, &body);
+
+    // 分块传输的请求体在读取时计数
+    let chunks = (0..4).map(|_| Ok::<_, std::io::Error>(Bytes::from(vec![b' '; 512])));
+    let request = post_json("/v1/items", None, Body::from_stream(stream::iter(chunks)));
+    let (response, body) = send(BodyLimit::new(1024), request).await;
+    assert_too_large(&response, &body);
+}
+
+#[tokio::test]
+async fn overrides_limit_by_route_prefix() {
+    let body_limit = BodyLimit::new(4096).with_route("/v1/user/login/", 256);
+
+    let (response, body) = send(
+        body_limit.clone(),
+        post_json("/v1/items", None, payload(1024)),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(body["data"]["name"].as_str().map(str::len), Some(1024));
+
+    let request = post_json("/v1/user/login", None, payload(1024));
+    let (response, body) = send(body_limit.clone(), request).await;
+    assert_too_large(&response, &body);
+
+    // 按路径段匹配，/v1/user/loginx 不属于 /v1/user/login
+    let request = post_json("/v1/user/loginx", None, payload(1024));
+    let (response, _) = send(body_limit, request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[test]
+fn derives_upload_limits_from_file_size() {
+    let body_limit = BodyLimit::new(1024)
+        .with_route("/v1/upload/large", 1024 * 1024)
+        .with_upload_route("/v1/upload", 4096)
+        .with_upload_route("/v1/upload/large", 4096);
+
+    assert_eq!(
+        body_limit.limit_for("/v1/upload"),
+        4096 + MULTIPART_OVERHEAD
+    );
+    // 配置中更大的覆盖值保持不变
+    assert_eq!(body_limit.limit_for("/v1/upload/large"), 1024 * 1024);
+    assert_eq!(body_limit.limit_for("/v1/items"), 1024);
+
+    // 配置中较小的覆盖值被调高到能容纳文件
+    let body_limit = BodyLimit::new(1024)
+        .with_route("/v1/upload", 2048)
+        .with_upload_route("/v1/upload", 4096);
+    assert_eq!(
+        body_limit.limit_for("/v1/upload"),
+        4096 + MULTIPART_OVERHEAD
+    );
+}
+
+#[tokio::test]
+async fn decompresses_gzip_bodies() {
+    let compressed = gzip(payload(16).as_bytes());
+    let request = post_json("/v1/items", Some("GZIP"), compressed);
+    let (response, body) = send(BodyLimit::new(1024), request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(body["data"]["name"], "x".repeat(16));
+}
+
+#[tokio::test]
+async fn limits_decompressed_size() {
+    // 1 MiB 的 JSON 压缩后只有约 1 KiB，按解压后的大小计算上限
+    let compressed = gzip(payload(1024 * 1024).as_bytes());
+    assert!(compressed.len() < 64 * 1024);
+
+    let request = post_json("/v1/items", Some("gzip"), compressed);
+    let (response, body) = send(BodyLimit::new(64 * 1024), request).await;
+    assert_too_large(&response, &body);
+}
+
+#[tokio::test]
+async fn rejects_unsupported_encodings() {
+    let request = post_json("/v1/items", Some("compress"), payload(16));
+    let (response, body) = send(BodyLimit::new(1024), request).await;
+    assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_eq!(response.headers()[ACCEPT_ENCODING], "gzip, deflate, br");
+    assert_eq!(
+        body["error"]["errors"][0]["reason"],
+        "UNSUPPORTED_MEDIA_TYPE"
+    );
+    assert_eq!(body["error"]["errors"][0]["location"], "Content-Encoding");
+
+    let body_limit = BodyLimit::new(1024).with_decompression(false);
+    let request = post_json("/v1/items", Some("gzip"), gzip(payload(16).as_bytes()));
+    let (response, _) = send(body_limit, request).await;
+    assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_eq!(response.headers()[ACCEPT_ENCODING], "identity");
+}
+
+#[test]
+fn validates_config() {
+    let config = BodyLimitConfig::default();
+    assert!(config.validate().is_ok());
+    let body_limit = BodyLimit::from_config(&config);
+    assert_eq!(body_limit.limit_for("/v1/user/login"), 16 * 1024);
+    assert_eq!(body_limit.limit_for("/v1/items"), 1024 * 1024);
+
+    let mut config = BodyLimitConfig::default();
+    config.route_limits.insert("v1/upload".to_string(), 1024);
+    assert!(config.validate().is_err());
+
+    let config = BodyLimitConfig {
+        max_size: 0,
+        ..BodyLimitConfig::default()
+    };
+    assert!(config.validate().is_err());
+}
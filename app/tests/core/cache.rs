@@ -0,0 +1,193 @@
+//! 缓存测试。
+//!
+//! 覆盖旁路缓存的命中、失效与加载失败不缓存，以及路由级 GET 响应缓存的 key 组成
+//! （`Accept-Language`、当前用户）、`Cache-Control`、非 200 响应不缓存和命中时 ETag 不变。
+
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use app::ApiResponse;
+use app::core::config::CacheConfig;
+use app::core::middleware::{
+    CurrentUser, ResponseCache, etag_middleware, response_cache_middleware,
+};
+use app::shared::{Cache, MemoryCacheStore};
+use axum::Router;
+use axum::body::{Body, to_bytes};
+use axum::extract::{Request, State};
+use axum::http::StatusCode;
+use axum::http::header::{ACCEPT_LANGUAGE, CACHE_CONTROL, ETAG};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use entity::enums::UserRole;
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+fn cache() -> Cache {
+    Cache::new(
+        Arc::new(MemoryCacheStore::new(100)),
+        &CacheConfig::default(),
+    )
+}
+
+#[tokio::test]
+async fn loads_once_until_invalidated() {
+    let cache = cache();
+    let loads = AtomicUsize::new(0);
+    let load = || async { Ok::<_, Infallible>(loads.fetch_add(1, Ordering::SeqCst) + 1) };
+
+    assert_eq!(cache.get_or_load("users", "1", load).await, Ok(1));
+    assert_eq!(cache.get_or_load("users", "1", load).await, Ok(1));
+    assert_eq!(cache.get_or_load("users", "2", load).await, Ok(2));
+
+    cache.invalidate("users").await;
+    assert_eq!(cache.get_or_load("users", "1", load).await, Ok(3));
+    assert_eq!(loads.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn does_not_cache_failed_loads() {
+    let cache = cache();
+
+    let failed: Result<String, &str> = cache
+        .get_or_load("users", "1", || async { Err("db") })
+        .await;
+    assert_eq!(failed, Err("db"));
+    let loaded: Result<String, &str> = cache
+        .get_or_load("users", "1", || async { Ok("alice".to_string()) })
+        .await;
+    assert_eq!(loaded.as_deref(), Ok("alice"));
+}
+
+#[tokio::test]
+async fn disabled_cache_stores_nothing() {
+    let config = CacheConfig {
+        enabled: false,
+        ..CacheConfig::default()
+    };
+    let cache = Cache::new(Arc::new(MemoryCacheStore::new(100)), &config);
+
+    cache.set("users", "1", &"stale", cache.ttl()).await;
+    assert_eq!(cache.get::<String>("users", "1").await, None);
+}
+
+#[derive(Clone, Default)]
+struct Counter(Arc<AtomicUsize>);
+
+async fn list(State(counter): State<Counter>, request: Request) -> Response {
+    let calls = counter.0.fetch_add(1, Ordering::SeqCst) + 1;
+    if request.uri().query() == Some("missing") {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    ApiResponse::success(json!({"call": calls}))
+        .with_etag("v1")
+        .into_response()
+}
+
+async fn as_user(mut request: Request, next: Next) -> Response {
+    let user_id = request
+        .headers()
+        .get("x-test-user")
+        .and_then(|value| value.to_str().ok()?.parse().ok());
+    if let Some(user_id) = user_id {
+        request.extensions_mut().insert(CurrentUser {
+            user_id,
+            role: UserRole::User,
+        });
+    }
+    next.run(request).await
+}
+
+fn router(cache: Cache, counter: Counter) -> Router {
+    Router::new()
+        .route("/users", get(list))
+        .with_state(counter)
+        .layer(axum::middleware::from_fn_with_state(
+            ResponseCache::new(cache, "users"),
+            response_cache_middleware,
+        ))
+        .layer(axum::middleware::from_fn(as_user))
+        .layer(axum::middleware::from_fn(etag_middleware))
+}
+
+async fn send(router: &Router, uri: &str, headers: &[(&str, &str)]) -> Response {
+    let mut request = Request::get(uri);
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    router
+        .clone()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+async fn body_json(response: Response) -> Value {
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn caches_get_responses() {
+    let counter = Counter::default();
+    let router = router(cache(), counter.clone());
+
+    let first = send(&router, "/users", &[]).await;
+    assert_eq!(first.headers()[CACHE_CONTROL], "public, max-age=60");
+    let etag = first.headers()[ETAG].clone();
+    let first = body_json(first).await;
+
+    let hit = send(&router, "/users", &[]).await;
+    assert_eq!(hit.status(), StatusCode::OK);
+    assert_eq!(hit.headers()[CACHE_CONTROL], "public, max-age=60");
+    // 资源版本随缓存保存，命中时 ETag 不变
+    assert_eq!(hit.headers()[ETAG], etag);
+    assert_eq!(body_json(hit).await, first);
+    assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+
+    let other_page = send(&router, "/users?page=2", &[]).await;
+    assert_eq!(body_json(other_page).await["data"]["call"], 2);
+}
+
+#[tokio::test]
+async fn separates_language_and_user() {
+    let counter = Counter::default();
+    let router = router(cache(), counter.clone());
+
+    send(&router, "/users", &[]).await;
+    send(&router, "/users", &[(ACCEPT_LANGUAGE.as_str(), "en")]).await;
+    let alice = send(&router, "/users", &[("x-test-user", "1")]).await;
+    assert_eq!(alice.headers()[CACHE_CONTROL], "private, max-age=60");
+    send(&router, "/users", &[("x-test-user", "2")]).await;
+    send(&router, "/users", &[("x-test-user", "1")]).await;
+
+    assert_eq!(counter.0.load(Ordering::SeqCst), 4);
+}
+
+#[tokio::test]
+async fn reloads_after_invalidation() {
+    let cache = cache();
+    let counter = Counter::default();
+    let router = router(cache.clone(), counter.clone());
+
+    send(&router, "/users", &[]).await;
+    cache.invalidate("users").await;
+    let reloaded = send(&router, "/users", &[]).await;
+
+    assert_eq!(body_json(reloaded).await["data"]["call"], 2);
+}
+
+#[tokio::test]
+async fn skips_non_ok_responses() {
+    let counter = Counter::default();
+    let router = router(cache(), counter.clone());
+
+    for _ in 0..2 {
+        let response = send(&router, "/users?missing", &[]).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert!(!response.headers().contains_key(CACHE_CONTROL));
+    }
+    assert_eq!(counter.0.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,214 @@
+//! 请求体与响应体编码测试。
+//!
+//! 覆盖按 `Accept` 协商 JSON / MessagePack / CBOR、二进制响应体与 JSON 结构一致、
+//! 二进制请求体的解码与 422 错误位置、二进制响应的 ETag，以及 OpenAPI 列出的备选媒体类型。
+
+use aide::axum::ApiRouter;
+use aide::axum::routing::post_with;
+use aide::openapi::OpenApi;
+use app::core::middleware::{encoding_middleware, etag_middleware};
+use app::{ApiResponse, BodyEncoding, CBOR, Json, MSGPACK};
+use axum::Router;
+use axum::body::{Body, to_bytes};
+use axum::http::header::{ACCEPT, CONTENT_TYPE, ETAG, VARY};
+use axum::http::{Request, StatusCode};
+use axum::response::Response;
+use axum::routing::{get, post};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+struct Item {
+    name: String,
+    tags: Vec<String>,
+}
+
+fn item() -> Item {
+    Item {
+        name: "alice".to_string(),
+        tags: vec!["a".to_string(), "b".to_string()],
+    }
+}
+
+fn router() -> Router {
+    Router::new()
+        .route("/item", get(|| async { ApiResponse::success(item()) }))
+        .route(
+            "/item",
+            post(|Json(item): Json<Item>| async move { ApiResponse::success(item) }),
+        )
+        .layer(axum::middleware::from_fn(etag_middleware))
+        .layer(axum::middleware::from_fn(encoding_middleware))
+}
+
+async fn send(request: Request<Body>) -> Response {
+    router().oneshot(request).await.unwrap()
+}
+
+async fn body_bytes(response: Response) -> Vec<u8> {
+    to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap()
+        .to_vec()
+}
+
+fn decode(encoding: BodyEncoding, bytes: &[u8]) -> Value {
+    encoding.decode(bytes).unwrap()
+}
+
+#[test]
+fn negotiates_encoding_from_accept() {
+    use BodyEncoding::{Cbor, Json, MessagePack};
+
+    assert_eq!(BodyEncoding::negotiate(None), Json);
+    assert_eq!(BodyEncoding::negotiate(Some("*/*")), Json);
+    assert_eq!(
+        BodyEncoding::negotiate(Some("application/msgpack")),
+        MessagePack
+    );
+    assert_eq!(
+        BodyEncoding::negotiate(Some("application/x-msgpack")),
+        MessagePack
+    );
+    assert_eq!(BodyEncoding::negotiate(Some("application/cbor")), Cbor);
+    assert_eq!(
+        BodyEncoding::negotiate(Some("application/cbor;q=0.5, application/json")),
+        Json
+    );
+    assert_eq!(
+        BodyEncoding::negotiate(Some("application/json;q=0.5, application/cbor")),
+        Cbor
+    );
+}
+
+#[tokio::test]
+async fn encodes_response_as_negotiated() {
+    let response = send(Request::get("/item").body(Body::empty()).unwrap()).await;
+    assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+    let json: Value = serde_json::from_slice(&body_bytes(response).await).unwrap();
+
+    for (accept, encoding) in [
+        (MSGPACK, BodyEncoding::MessagePack),
+        (CBOR, BodyEncoding::Cbor),
+    ] {
+        let request = Request::get("/item")
+            .header(ACCEPT, accept)
+            .body(Body::empty())
+            .unwrap();
+        let response = send(request).await;
+
+        assert_eq!(response.headers()[CONTENT_TYPE], accept);
+        assert_eq!(response.headers()[VARY], "accept");
+        assert!(response.headers().contains_key(ETAG));
+        let body = decode(encoding, &body_bytes(response).await);
+        assert_eq!(body["data"]["name"], json["data"]["name"]);
+        assert_eq!(body["data"]["tags"], json!(["a", "b"]));
+    }
+}
+
+#[tokio::test]
+async fn binary_etag_differs_from_json() {
+    let json = send(Request::get("/item").body(Body::empty()).unwrap()).await;
+    let request = Request::get("/item")
+        .header(ACCEPT, MSGPACK)
+        .body(Body::empty())
+        .unwrap();
+    let msgpack = send(request).await;
+    let etag = msgpack.headers()[ETAG].clone();
+    assert_ne!(json.headers()[ETAG], etag);
+
+    let request = Request::get("/item")
+        .header(ACCEPT, MSGPACK)
+        .header("if-none-match", etag)
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(send(request).await.status(), StatusCode::NOT_MODIFIED);
+}
+
+fn post_body(content_type: &str, body: Vec<u8>) -> Request<Body> {
+    Request::post("/item")
+        .header(CONTENT_TYPE, content_type)
+        .body(Body::from(body))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn decodes_binary_request_bodies() {
+    let bodies = [
+        (MSGPACK, BodyEncoding::MessagePack.encode(&item()).unwrap()),
+        (CBOR, BodyEncoding::Cbor.encode(&item()).unwrap()),
+    ];
+    for (content_type, body) in bodies {
+        let response = send(post_body(content_type, body)).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = serde_json::from_slice(&body_bytes(response).await).unwrap();
+        assert_eq!(body["data"]["name"], "alice");
+    }
+}
+
+async fn first_detail(response: Response) -> Value {
+    let body: Value = serde_json::from_slice(&body_bytes(response).await).unwrap();
+    body["error"]["errors"][0].clone()
+}
+
+#[tokio::test]
+async fn reports_binary_field_errors_with_pointer() {
+    for encoding in [BodyEncoding::MessagePack, BodyEncoding::Cbor] {
+        let missing = encoding.encode(&json!({"tags": []})).unwrap();
+        let response = send(post_body(encoding.media_type(), missing)).await;
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let detail = first_detail(response).await;
+        assert_eq!(detail["reason"], "REQUIRED_FIELD_MISSING");
+        assert_eq!(detail["location"], "/name");
+
+        let mistyped = encoding.encode(&json!({"name": "a", "tags": [1]})).unwrap();
+        let response = send(post_body(encoding.media_type(), mistyped)).await;
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(first_detail(response).await["location"], "/tags/0");
+    }
+}
+
+#[tokio::test]
+async fn rejects_malformed_binary_bodies() {
+    for encoding in [BodyEncoding::MessagePack, BodyEncoding::Cbor] {
+        let encoded = encoding.encode(&item()).unwrap();
+        let truncated = encoded[..encoded.len() - 1].to_vec();
+        let mut trailing = encoded;
+        trailing.push(0);
+        for body in [truncated, trailing] {
+            let response = send(post_body(encoding.media_type(), body)).await;
+
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            assert_eq!(first_detail(response).await["reason"], "INVALID_FORMAT");
+        }
+    }
+}
+
+#[test]
+fn documents_binary_media_types() {
+    async fn create(Json(item): Json<Item>) -> ApiResponse<Item> {
+        ApiResponse::success(item)
+    }
+
+    let mut api = OpenApi::default();
+    let _router: Router = ApiRouter::new()
+        .api_route(
+            "/item",
+            post_with(create, |op| op.response::<200, ApiResponse<Item>>()),
+        )
+        .finish_api(&mut api);
+    let api = serde_json::to_value(api).unwrap();
+
+    let operation = &api["paths"]["/item"]["post"];
+    for content in [
+        &operation["requestBody"]["content"],
+        &operation["responses"]["200"]["content"],
+    ] {
+        assert!(content["application/json"]["schema"].is_object());
+        assert_eq!(content[MSGPACK], content["application/json"]);
+        assert_eq!(content[CBOR], content["application/json"]);
+    }
+}
@@ -0,0 +1,114 @@
+//! 错误响应 OpenAPI 文档测试。
+//!
+//! 覆盖错误类型声明的 `(状态码, domain, reason)`、按状态码分组生成的响应与示例，
+//! 以及提取器拒绝响应与处理器错误响应的合并。
+
+use aide::axum::ApiRouter;
+use aide::axum::routing::post_with;
+use aide::openapi::OpenApi;
+use app::core::response::{ErrorCase, ErrorCases, ErrorDocs, Reason};
+use app::{ApiResponse, AuthError, Domain, Json, PrivacyError, ValidationError};
+use axum::http::StatusCode;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct LoginBody {
+    #[allow(dead_code)]
+    username: String,
+}
+
+async fn login(Json(_body): Json<LoginBody>) -> ApiResponse<()> {
+    ApiResponse::success(())
+}
+
+fn openapi() -> Value {
+    let mut api = OpenApi::default();
+    let _router: axum::Router = ApiRouter::new()
+        .api_route(
+            "/login",
+            post_with(login, |op| op.errors::<(AuthError, PrivacyError)>()),
+        )
+        .finish_api(&mut api);
+    serde_json::to_value(api).unwrap()
+}
+
+fn responses(api: &Value) -> &Value {
+    &api["paths"]["/login"]["post"]["responses"]
+}
+
+#[test]
+fn error_enums_declare_their_cases() {
+    let cases = AuthError::error_cases();
+
+    assert!(cases.contains(&ErrorCase::new(
+        StatusCode::CONFLICT,
+        Domain::AUTH,
+        Reason::AlreadyExists
+    )));
+    assert!(cases.contains(&ErrorCase::INTERNAL));
+    assert!(
+        ValidationError::error_cases()
+            .iter()
+            .all(|case| case.status == StatusCode::BAD_REQUEST)
+    );
+}
+
+#[test]
+fn tuples_concatenate_without_duplicates() {
+    let cases = <(AuthError, PrivacyError)>::error_cases();
+
+    let internal = cases
+        .iter()
+        .filter(|case| **case == ErrorCase::INTERNAL)
+        .count();
+    assert_eq!(internal, 1);
+    assert_eq!(
+        cases.len(),
+        AuthError::error_cases().len() + PrivacyError::error_cases().len() - 1
+    );
+}
+
+#[test]
+fn documents_each_status_with_examples() {
+    let api = openapi();
+    let conflict = &responses(&api)["409"];
+
+    assert_eq!(conflict["description"], "Conflict");
+    let json = &conflict["content"]["application/json"];
+    let example = &json["examples"]["auth.ALREADY_EXISTS"]["value"];
+    assert_eq!(example["error"]["code"], 409);
+    assert_eq!(example["error"]["errors"][0]["domain"], "auth");
+    assert_eq!(example["error"]["errors"][0]["reason"], "ALREADY_EXISTS");
+    assert!(json["examples"]["user.CONFLICT"].is_object());
+
+    let problem = &conflict["content"]["application/problem+json"]["examples"];
+    assert_eq!(
+        problem["auth.ALREADY_EXISTS"]["value"]["type"],
+        "/problems/auth/already-exists"
+    );
+}
+
+#[test]
+fn merges_extractor_rejections_with_handler_errors() {
+    let api = openapi();
+    let responses = responses(&api);
+
+    // 415 只来自 Json 提取器
+    assert!(
+        responses["415"]["content"]["application/json"]["examples"]["validation.UNSUPPORTED_MEDIA_TYPE"]
+            .is_object()
+    );
+
+    // 400 同时包含提取器和处理器的示例
+    let examples = &responses["400"]["content"]["application/json"]["examples"];
+    assert!(examples["validation.INVALID_FORMAT"].is_object());
+    assert!(examples["auth.INVALID_USERNAME"].is_object());
+
+    // 内部错误没有错误详情
+    let internal = &responses["500"]["content"]["application/json"]["examples"]["global.INTERNAL_ERROR"]
+        ["value"];
+    assert_eq!(internal["error"]["code"], 500);
+    assert!(internal["error"].get("errors").is_none());
+}
@@ -0,0 +1,234 @@
+//! ETag 与条件请求测试。
+//!
+//! 覆盖由响应体或处理器版本生成强 ETag 并写入 `data.etag`、`If-None-Match` 命中返回 304
+//! （包括弱比较和 HEAD）、处理器版本的 ETag 随协商出的语言变化，
+//! 以及处理器借助 `IfNoneMatch` 提前返回 304。
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use app::core::config::ResponseConfig;
+use app::core::middleware::{etag_middleware, fields_middleware, locale_middleware};
+use app::core::response::Reason;
+use app::{ApiResponse, Conditional, Domain, IfNoneMatch, digest_etag, version_etag};
+use axum::Router;
+use axum::body::{Body, to_bytes};
+use axum::http::header::{ACCEPT_LANGUAGE, ETAG, IF_NONE_MATCH};
+use axum::http::{HeaderMap, HeaderValue, Method, Request, StatusCode};
+use axum::response::Response;
+use axum::routing::{get, post};
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+#[test]
+fn builds_strong_etags() {
+    let etag = digest_etag(b"{}");
+    assert!(etag.starts_with('"') && etag.ends_with('"'));
+    assert_eq!(etag.len(), 34);
+    assert_eq!(etag, digest_etag(b"{}"));
+    assert_ne!(etag, digest_etag(b"[]"));
+
+    assert_eq!(version_etag("v1", None), "\"v1\"");
+    assert_eq!(version_etag("\"v1\"", None), "\"v1\"");
+    assert_ne!(
+        version_etag("v1", Some("id")),
+        version_etag("v1", Some("name"))
+    );
+}
+
+#[test]
+fn if_none_match_uses_weak_comparison() {
+    let mut headers = HeaderMap::new();
+    headers.insert(IF_NONE_MATCH, HeaderValue::from_static("\"a\", W/\"b\""));
+    let condition = IfNoneMatch::from_headers(&headers);
+
+    assert!(condition.matches("\"a\""));
+    assert!(condition.matches("\"b\""));
+    assert!(condition.matches("W/\"a\""));
+    assert!(!condition.matches("\"c\""));
+
+    headers.insert(IF_NONE_MATCH, HeaderValue::from_static("*"));
+    assert!(IfNoneMatch::from_headers(&headers).matches("\"c\""));
+    assert!(!IfNoneMatch::default().matches("\"c\""));
+}
+
+fn router(calls: Arc<AtomicUsize>) -> Router {
+    Router::new()
+        .route(
+            "/user",
+            get(|| async { ApiResponse::success(json!({"id": 1, "username": "admin"})) }),
+        )
+        .route(
+            "/versioned",
+            get(|| async { ApiResponse::success(json!({"id": 1})).with_etag("v7") }),
+        )
+        .route(
+            "/conditional",
+            get(move |if_none_match: IfNoneMatch| async move {
+                if if_none_match.matches_version("v7") {
+                    return Conditional::not_modified(if_none_match.etag_for("v7"));
+                }
+                calls.fetch_add(1, Ordering::SeqCst);
+                Conditional::Modified(ApiResponse::success(json!({"id": 1})).with_etag("v7"))
+            }),
+        )
+        .route("/user", post(|| async { ApiResponse::success(json!({})) }))
+        .route(
+            "/missing",
+            get(|| async { ApiResponse::not_found(Domain::GLOBAL, Reason::NotFound) }),
+        )
+        .layer(axum::middleware::from_fn(etag_middleware))
+        .layer(axum::middleware::from_fn(fields_middleware))
+}
+
+async fn send(method: Method, uri: &str, if_none_match: Option<&str>) -> Response {
+    send_with(router(Arc::default()), method, uri, if_none_match).await
+}
+
+async fn send_with(
+    router: Router,
+    method: Method,
+    uri: &str,
+    if_none_match: Option<&str>,
+) -> Response {
+    let mut request = Request::builder().method(method).uri(uri);
+    if let Some(tag) = if_none_match {
+        request = request.header(IF_NONE_MATCH, tag);
+    }
+    router
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+async fn body_bytes(response: Response) -> Vec<u8> {
+    to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap()
+        .to_vec()
+}
+
+fn etag_of(response: &Response) -> String {
+    response.headers()[ETAG].to_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn mirrors_etag_in_header_and_data() {
+    let response = send(Method::GET, "/user", None).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let etag = etag_of(&response);
+    let body: Value = serde_json::from_slice(&body_bytes(response).await).unwrap();
+    assert_eq!(body["data"]["etag"], etag.as_str());
+    assert_eq!(body["data"]["username"], "admin");
+
+    // 相同内容得到相同的 ETag
+    let again = send(Method::GET, "/user", None).await;
+    assert_eq!(etag_of(&again), etag);
+}
+
+#[tokio::test]
+async fn returns_304_for_matching_if_none_match() {
+    let etag = etag_of(&send(Method::GET, "/user", None).await);
+
+    for method in [Method::GET, Method::HEAD] {
+        let response = send(method.clone(), "/user", Some(&format!("W/{etag}"))).await;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED, "{method}");
+        assert_eq!(etag_of(&response), etag);
+        assert!(body_bytes(response).await.is_empty());
+    }
+
+    let response = send(Method::GET, "/user", Some("\"stale\"")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn head_shares_get_etag_without_body() {
+    let get = send(Method::GET, "/user", None).await;
+    let head = send(Method::HEAD, "/user", None).await;
+
+    assert_eq!(head.status(), StatusCode::OK);
+    assert_eq!(etag_of(&head), etag_of(&get));
+    assert!(body_bytes(head).await.is_empty());
+}
+
+#[tokio::test]
+async fn uses_handler_version() {
+    let response = send(Method::GET, "/versioned", None).await;
+    assert_eq!(etag_of(&response), "\"v7\"");
+    let body: Value = serde_json::from_slice(&body_bytes(response).await).unwrap();
+    assert_eq!(body["data"]["etag"], "\"v7\"");
+
+    let response = send(Method::GET, "/versioned", Some("\"v7\"")).await;
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+    // 部分响应是不同的表示，ETag 随 fields 变化
+    let response = send(Method::GET, "/versioned?fields=id", None).await;
+    assert_eq!(etag_of(&response), version_etag("v7", Some("id")));
+}
+
+#[tokio::test]
+async fn varies_handler_version_by_locale() {
+    let send_in = |accept_language: &'static str, if_none_match: Option<String>| async move {
+        let router = router(Arc::default()).layer(axum::middleware::from_fn_with_state(
+            ResponseConfig::default(),
+            locale_middleware,
+        ));
+        let mut request = Request::get("/versioned").header(ACCEPT_LANGUAGE, accept_language);
+        if let Some(tag) = if_none_match {
+            request = request.header(IF_NONE_MATCH, tag);
+        }
+        router
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    };
+
+    let zh = send_in("zh-CN", None).await;
+    let zh_etag = etag_of(&zh);
+    assert_eq!(zh_etag, "\"v7;zh-CN\"");
+    let body: Value = serde_json::from_slice(&body_bytes(zh).await).unwrap();
+    assert_eq!(body["data"]["etag"], zh_etag.as_str());
+    assert_eq!(body["data"]["lang"], "zh-CN");
+
+    // 其他语言的缓存不能以 304 命中
+    let en = send_in("en", Some(zh_etag)).await;
+    assert_eq!(en.status(), StatusCode::OK);
+    let en_etag = etag_of(&en);
+    assert_eq!(en_etag, "\"v7;en\"");
+
+    let en = send_in("en", Some(en_etag)).await;
+    assert_eq!(en.status(), StatusCode::NOT_MODIFIED);
+}
+
+#[tokio::test]
+async fn handler_short_circuits_before_work() {
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let response = send_with(router(calls.clone()), Method::GET, "/conditional", None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    let etag = etag_of(&response);
+
+    let response = send_with(
+        router(calls.clone()),
+        Method::GET,
+        "/conditional",
+        Some(&etag),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(etag_of(&response), "\"v7\"");
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn skips_other_methods_and_errors() {
+    let response = send(Method::POST, "/user", Some("*")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(!response.headers().contains_key(ETAG));
+
+    let response = send(Method::GET, "/missing", Some("*")).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert!(!response.headers().contains_key(ETAG));
+}
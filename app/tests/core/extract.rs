@@ -0,0 +1,185 @@
+//! 统一错误格式的请求提取器测试。
+//!
+//! 覆盖 `Json` / `Query` / `Path` 拒绝时的状态码（400/415/422）、
+//! `validation` 域的错误详情、JSON Pointer 位置和解析错误的行列号与字节偏移。
+
+use app::{Json, Path, Query};
+use axum::Router;
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use serde::Deserialize;
+use serde_json::Value;
+use tower::ServiceExt;
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct Payload {
+    name: String,
+    items: Vec<Item>,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct Item {
+    count: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct Paging {
+    page: u64,
+}
+
+fn router() -> Router {
+    Router::new()
+        .route("/json", post(|Json(_): Json<Payload>| async { "ok" }))
+        .route("/query", get(|Query(_): Query<Paging>| async { "ok" }))
+        .route("/users/{id}", get(|Path(_): Path<u64>| async { "ok" }))
+}
+
+async fn send(request: Request<Body>) -> (StatusCode, Value) {
+    let response: Response = router().oneshot(request).await.unwrap().into_response();
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    (status, body)
+}
+
+fn post_json(content_type: &str, body: &str) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/json")
+        .header("content-type", content_type)
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+fn get_request(uri: &str) -> Request<Body> {
+    Request::builder().uri(uri).body(Body::empty()).unwrap()
+}
+
+fn first_detail(body: &Value) -> &Value {
+    &body["error"]["errors"][0]
+}
+
+#[tokio::test]
+async fn accepts_valid_json() {
+    let (status, _) = send(post_json(
+        "application/json; charset=utf-8",
+        r#"{"name":"a","items":[]}"#,
+    ))
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn rejects_non_json_content_type_with_415() {
+    let (status, body) = send(post_json("text/plain", r#"{"name":"a","items":[]}"#)).await;
+
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    let detail = first_detail(&body);
+    assert_eq!(detail["domain"], "validation");
+    assert_eq!(detail["reason"], "UNSUPPORTED_MEDIA_TYPE");
+    assert_eq!(detail["location_type"], "header");
+}
+
+#[tokio::test]
+async fn accepts_structured_json_suffix() {
+    let (status, _) = send(post_json(
+        "application/merge-patch+json",
+        r#"{"name":"a","items":[]}"#,
+    ))
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn syntax_error_is_400_with_position() {
+    let (status, body) = send(post_json(
+        "application/json",
+        "{\n  \"name\": \"a\",\n  oops\n}",
+    ))
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let detail = first_detail(&body);
+    assert_eq!(detail["domain"], "validation");
+    assert_eq!(detail["reason"], "INVALID_FORMAT");
+    assert_eq!(detail["location_type"], "body");
+    let message = detail["message"].as_str().unwrap();
+    assert!(message.contains("第 3 行第 3 列，字节偏移 19"), "{message}");
+}
+
+#[tokio::test]
+async fn trailing_characters_are_syntax_errors() {
+    let (status, body) = send(post_json(
+        "application/json",
+        r#"{"name":"a","items":[]} x"#,
+    ))
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let message = first_detail(&body)["message"].as_str().unwrap();
+    assert!(message.contains("字节偏移 24"), "{message}");
+}
+
+#[tokio::test]
+async fn type_mismatch_is_422_with_pointer() {
+    let (status, body) = send(post_json(
+        "application/json",
+        r#"{"name":"a","items":[{"count":1},{"count":"x"}]}"#,
+    ))
+    .await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    let detail = first_detail(&body);
+    assert_eq!(detail["reason"], "INVALID_FORMAT");
+    assert_eq!(detail["location"], "/items/1/count");
+    assert_eq!(detail["location_type"], "body");
+}
+
+#[tokio::test]
+async fn missing_field_points_at_the_field() {
+    let (status, body) = send(post_json("application/json", r#"{"items":[]}"#)).await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    let detail = first_detail(&body);
+    assert_eq!(detail["reason"], "REQUIRED_FIELD_MISSING");
+    assert_eq!(detail["location"], "/name");
+}
+
+#[tokio::test]
+async fn invalid_query_is_400_with_pointer() {
+    let (status, body) = send(get_request("/query?page=abc")).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let detail = first_detail(&body);
+    assert_eq!(detail["domain"], "validation");
+    assert_eq!(detail["location"], "/page");
+    assert_eq!(detail["location_type"], "query");
+}
+
+#[tokio::test]
+async fn missing_query_parameter_is_reported() {
+    let (status, body) = send(get_request("/query")).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let detail = first_detail(&body);
+    assert_eq!(detail["reason"], "REQUIRED_FIELD_MISSING");
+    assert_eq!(detail["location"], "/page");
+}
+
+#[tokio::test]
+async fn invalid_path_is_400_with_key() {
+    let (status, body) = send(get_request("/users/abc")).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let detail = first_detail(&body);
+    assert_eq!(detail["domain"], "validation");
+    assert_eq!(detail["location"], "/id");
+    assert_eq!(detail["location_type"], "path");
+}
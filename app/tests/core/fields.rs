@@ -0,0 +1,201 @@
+//! 部分响应（`fields` 查询参数）测试。
+//!
+//! 覆盖语法解析与错误、按 schema 校验字段（包括 `Option` 字段和嵌套对象），
+//! 以及中间件对单个资源和分页列表的 `data` 裁剪。
+
+use app::ApiResponse;
+use app::core::middleware::fields_middleware;
+use app::core::response::{DataWrapper, FieldSelection};
+use axum::Router;
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode};
+use axum::routing::get;
+use schemars::{JsonSchema, schema_for};
+use serde::Serialize;
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+#[derive(Serialize, JsonSchema)]
+struct Profile {
+    city: String,
+    bio: String,
+}
+
+#[derive(Serialize, JsonSchema)]
+struct User {
+    id: i64,
+    username: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nickname: Option<String>,
+    profile: Profile,
+}
+
+fn user(id: i64) -> User {
+    User {
+        id,
+        username: format!("user{id}"),
+        nickname: None,
+        profile: Profile {
+            city: "Shanghai".into(),
+            bio: "hi".into(),
+        },
+    }
+}
+
+fn validate(fields: &str) -> Result<(), String> {
+    let schema = schema_for!(DataWrapper<User>);
+    FieldSelection::parse(fields)
+        .and_then(|selection| selection.validate(schema.as_value(), schema.as_value()))
+        .map_err(|e| e.to_string())
+}
+
+#[test]
+fn parses_nested_paths_and_groups() {
+    assert_eq!(
+        FieldSelection::parse("profile/city").unwrap(),
+        FieldSelection::parse("profile(city)").unwrap()
+    );
+    assert_eq!(
+        FieldSelection::parse("profile(city),profile(bio)").unwrap(),
+        FieldSelection::parse("profile(bio,city)").unwrap()
+    );
+    // 选择整个字段优先于子字段选择
+    assert_eq!(
+        FieldSelection::parse("profile(city),profile").unwrap(),
+        FieldSelection::parse("profile").unwrap()
+    );
+}
+
+#[test]
+fn rejects_malformed_fields() {
+    for input in ["", "id,", "items(id", "id)", "a b", "items()", "(id)"] {
+        assert!(FieldSelection::parse(input).is_err(), "{input}");
+    }
+}
+
+#[test]
+fn validates_against_schema() {
+    assert!(validate("id,username,kind").is_ok());
+    assert!(validate("nickname").is_ok());
+    assert!(validate("profile(city)").is_ok());
+    assert!(validate("items(id,username),total_items").is_ok());
+    assert!(validate("*").is_ok());
+
+    assert!(validate("password").unwrap_err().contains("password"));
+    assert!(
+        validate("profile(zip)")
+            .unwrap_err()
+            .contains("profile/zip")
+    );
+    assert!(validate("username(first)").is_err());
+    assert!(validate("*(id)").is_err());
+}
+
+#[test]
+fn open_objects_accept_any_field() {
+    let schema = schema_for!(DataWrapper<Value>);
+    let selection = FieldSelection::parse("anything(nested)").unwrap();
+    assert!(
+        selection
+            .validate(schema.as_value(), schema.as_value())
+            .is_ok()
+    );
+}
+
+#[test]
+fn applies_selection_to_objects_and_arrays() {
+    let value = json!({
+        "kind": "UserList",
+        "items": [{"id": 1, "username": "a", "email": "x"}, {"id": 2, "username": "b"}],
+        "total_items": 2,
+        "page_index": 1
+    });
+
+    let trimmed = FieldSelection::parse("items(id),total_items")
+        .unwrap()
+        .apply(value.clone());
+    assert_eq!(
+        trimmed,
+        json!({"items": [{"id": 1}, {"id": 2}], "total_items": 2})
+    );
+
+    let trimmed = FieldSelection::parse("*,items(username)")
+        .unwrap()
+        .apply(value);
+    assert_eq!(trimmed["kind"], "UserList");
+    assert_eq!(
+        trimmed["items"],
+        json!([{"username": "a"}, {"username": "b"}])
+    );
+}
+
+fn router() -> Router {
+    Router::new()
+        .route(
+            "/user",
+            get(|| async { ApiResponse::success(user(1)).with_kind("User") }),
+        )
+        .route(
+            "/users",
+            get(|| async { ApiResponse::list(vec![user(1), user(2)], 20, 1, 2) }),
+        )
+        .layer(axum::middleware::from_fn(fields_middleware))
+}
+
+async fn get_json(uri: &str) -> (StatusCode, Value) {
+    let response = router()
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn trims_list_response() {
+    let (status, body) = get_json("/users?fields=items(id,username),total_items").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["api_version"], "1.0");
+    assert_eq!(
+        body["data"],
+        json!({
+            "items": [{"id": 1, "username": "user1"}, {"id": 2, "username": "user2"}],
+            "total_items": 20
+        })
+    );
+}
+
+#[tokio::test]
+async fn trims_single_response() {
+    let (status, body) = get_json("/user?fields=kind,profile%2Fcity").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["data"],
+        json!({"kind": "User", "profile": {"city": "Shanghai"}})
+    );
+}
+
+#[tokio::test]
+async fn without_fields_returns_full_response() {
+    let (status, body) = get_json("/user").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["username"], "user1");
+    assert_eq!(body["data"]["profile"]["bio"], "hi");
+}
+
+#[tokio::test]
+async fn invalid_fields_return_400() {
+    for uri in ["/users?fields=items(password)", "/users?fields=items(id"] {
+        let (status, body) = get_json(uri).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{uri}");
+        let detail = &body["error"]["errors"][0];
+        assert_eq!(detail["reason"], "INVALID_FORMAT");
+        assert_eq!(detail["location"], "fields");
+        assert_eq!(detail["location_type"], "query");
+    }
+}
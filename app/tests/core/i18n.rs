@@ -0,0 +1,224 @@
+//! 错误消息本地化测试。
+//!
+//! 覆盖内置消息目录的完整性、`Accept-Language` 协商、源语言与其他语言下的替换规则、
+//! 消息参数填充占位符，以及中间件设置 `data.lang` 和 `Content-Language`。
+
+use app::core::config::ResponseConfig;
+use app::core::middleware::locale_middleware;
+use app::core::response::{ApiError, Reason};
+use app::{ApiResponse, Domain, ErrorDetail, Json, Locale, MessageCatalog, ValidationError};
+use axum::Router;
+use axum::body::{Body, to_bytes};
+use axum::http::header::{ACCEPT_LANGUAGE, CONTENT_LANGUAGE};
+use axum::http::{Request, StatusCode};
+use axum::routing::{get, post};
+use serde_json::{Value, json};
+use tower::ServiceExt;
+use validator::Validate;
+
+#[test]
+fn builtin_catalog_covers_every_reason() {
+    let catalog = MessageCatalog::builtin();
+    for locale in Locale::ALL {
+        for reason in Reason::ALL {
+            assert!(
+                catalog
+                    .message(locale, "global", &reason.to_string())
+                    .is_some(),
+                "{} 缺少 {} 的消息",
+                locale.tag(),
+                reason
+            );
+        }
+    }
+}
+
+#[test]
+fn domain_entries_override_reason_defaults() {
+    let catalog = MessageCatalog::builtin();
+
+    assert_eq!(
+        catalog.message(Locale::En, "auth", "ALREADY_EXISTS"),
+        Some("Username or email is already registered")
+    );
+    assert_eq!(
+        catalog.message(Locale::En, "user", "ALREADY_EXISTS"),
+        Some("Resource already exists")
+    );
+}
+
+#[test]
+fn negotiates_accept_language() {
+    let default = Locale::ZhCn;
+    assert_eq!(Locale::negotiate(None, default), Locale::ZhCn);
+    assert_eq!(
+        Locale::negotiate(Some("en-US,en;q=0.9"), default),
+        Locale::En
+    );
+    assert_eq!(
+        Locale::negotiate(Some("fr;q=1.0, zh-TW;q=0.4, en;q=0.8"), Locale::ZhCn),
+        Locale::En
+    );
+    assert_eq!(Locale::negotiate(Some("fr, de"), Locale::En), Locale::En);
+    assert_eq!(
+        Locale::negotiate(Some("en;q=0"), Locale::ZhCn),
+        Locale::ZhCn
+    );
+}
+
+#[test]
+fn translates_all_messages_outside_source_locale() {
+    let error = ApiError::new(StatusCode::BAD_REQUEST, "用户名: 长度不符合要求").with_detail(
+        ErrorDetail::with_message(Domain::VALIDATION, Reason::InvalidLength, "长度不符合要求")
+            .at("/username", "body"),
+    );
+
+    let localized = MessageCatalog::builtin().localize(error, Locale::En);
+
+    assert_eq!(localized.message, "Length is not allowed");
+    assert_eq!(localized.errors[0].message, "Length is not allowed");
+    assert_eq!(localized.errors[0].location.as_deref(), Some("/username"));
+}
+
+#[test]
+fn source_locale_keeps_contextual_messages() {
+    let error = ApiError::new(StatusCode::BAD_REQUEST, "用户名长度必须在 3-20 之间")
+        .with_detail(ErrorDetail::with_message(
+            Domain::VALIDATION,
+            Reason::InvalidLength,
+            "用户名长度必须在 3-20 之间",
+        ))
+        .with_detail(ErrorDetail::new(Domain::VALIDATION, Reason::InvalidEmail));
+
+    let localized = MessageCatalog::builtin().localize(error, Locale::ZhCn);
+
+    assert_eq!(localized.message, "用户名长度必须在 3-20 之间");
+    assert_eq!(localized.errors[0].message, "用户名长度必须在 3-20 之间");
+    assert_eq!(localized.errors[1].message, "邮箱格式无效");
+}
+
+#[derive(Validate)]
+struct Profile {
+    #[validate(length(min = 3, max = 20, message = "用户名长度必须在 3-20 之间"))]
+    username: String,
+
+    #[validate(length(min = 8, message = "密码至少 8 位"))]
+    password: String,
+}
+
+#[test]
+fn fills_params_outside_source_locale() {
+    let profile = Profile {
+        username: "ab".to_string(),
+        password: "short".to_string(),
+    };
+    let errors = ValidationError::from_validator(profile.validate().unwrap_err());
+    let error = ApiError::new(StatusCode::BAD_REQUEST, errors.to_string())
+        .with_details(errors.details().to_vec());
+    assert!(
+        errors
+            .details()
+            .iter()
+            .all(|d| !d.params.contains_key("value"))
+    );
+
+    let localized = MessageCatalog::builtin().localize(error, Locale::En);
+
+    assert_eq!(localized.errors[0].location.as_deref(), Some("/password"));
+    // 没有能用上 min 的译文，保留原始消息
+    assert_eq!(localized.errors[0].message, "密码至少 8 位");
+    assert_eq!(
+        localized.errors[1].message,
+        "Length must be between 3 and 20"
+    );
+}
+
+#[test]
+fn errors_without_details_use_status_message() {
+    let error = ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Internal server error");
+
+    let localized = MessageCatalog::builtin().localize(error, Locale::ZhCn);
+
+    assert_eq!(localized.message, "服务器内部错误");
+}
+
+fn router() -> Router {
+    Router::new()
+        .route(
+            "/fail",
+            get(|| async {
+                ApiResponse::fail(StatusCode::NOT_FOUND, Domain::USER, Reason::NotFound)
+            }),
+        )
+        .route(
+            "/ok",
+            get(|| async { ApiResponse::success(json!({ "message": "ok" })) }),
+        )
+        .route("/locale", get(|locale: Locale| async move { locale.tag() }))
+        .route(
+            "/echo",
+            post(|Json(value): Json<Value>| async move { ApiResponse::success(value) }),
+        )
+        .layer(axum::middleware::from_fn_with_state(
+            ResponseConfig::default(),
+            locale_middleware,
+        ))
+}
+
+async fn send(uri: &str, accept_language: Option<&str>) -> (axum::http::HeaderMap, Vec<u8>) {
+    let mut request = Request::builder().uri(uri);
+    if let Some(accept_language) = accept_language {
+        request = request.header(ACCEPT_LANGUAGE, accept_language);
+    }
+    let response = router()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let headers = response.headers().clone();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (headers, bytes.to_vec())
+}
+
+#[tokio::test]
+async fn middleware_localizes_error_responses() {
+    let (headers, body) = send("/fail", Some("en")).await;
+    let body: Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(headers[CONTENT_LANGUAGE], "en");
+    assert_eq!(body["error"]["message"], "Resource not found");
+    assert_eq!(body["error"]["errors"][0]["message"], "Resource not found");
+}
+
+#[tokio::test]
+async fn middleware_sets_data_lang() {
+    let (headers, body) = send("/ok", None).await;
+    let body: Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(headers[CONTENT_LANGUAGE], "zh-CN");
+    assert_eq!(body["data"]["lang"], "zh-CN");
+}
+
+#[tokio::test]
+async fn locale_extractor_reads_negotiated_locale() {
+    let (_, body) = send("/locale", Some("en-GB")).await;
+
+    assert_eq!(body, b"en");
+}
+
+#[tokio::test]
+async fn middleware_keeps_json_error_position() {
+    let request = Request::post("/echo")
+        .header(ACCEPT_LANGUAGE, "en")
+        .header("content-type", "application/json")
+        .body(Body::from("{\n  \"name\": }"))
+        .unwrap();
+    let response = router().oneshot(request).await.unwrap();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+
+    assert_eq!(
+        body["error"]["errors"][0]["message"],
+        "Invalid request body: expected value (line 2, column 11)"
+    );
+    assert!(body["error"]["errors"][0].get("params").is_none());
+}
@@ -0,0 +1,223 @@
+//! 幂等请求测试。
+//!
+//! 覆盖 `Idempotency-Key` 的首次成功响应重放、同一 key 用于不同请求体时的 409、
+//! 首个请求处理中的 409 与 `Retry-After`、失败响应不保存、超大请求体返回 413（可按路由放宽），
+//! 以及按客户端地址区分 key。
+
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use app::core::config::IdempotencyConfig;
+use app::core::middleware::{Idempotency, idempotency_middleware};
+use app::shared::MemoryIdempotencyStore;
+use app::{ApiResponse, Json, TrustedProxies};
+use axum::Router;
+use axum::body::{Body, to_bytes};
+use axum::extract::{ConnectInfo, State};
+use axum::http::header::{CONTENT_TYPE, RETRY_AFTER};
+use axum::http::{HeaderMap, Request, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use serde_json::{Value, json};
+use tokio::sync::Notify;
+use tower::ServiceExt;
+
+#[derive(Clone, Default)]
+struct Counter {
+    calls: Arc<AtomicUsize>,
+    gate: Option<Arc<Notify>>,
+}
+
+async fn create(State(counter): State<Counter>, Json(body): Json<Value>) -> Response {
+    if let Some(gate) = &counter.gate {
+        gate.notified().await;
+    }
+    let calls = counter.calls.fetch_add(1, Ordering::SeqCst) + 1;
+    if body["fail"] == true {
+        return (StatusCode::UNPROCESSABLE_ENTITY, "invalid").into_response();
+    }
+    let mut response = ApiResponse::success(json!({"call": calls})).into_response();
+    *response.status_mut() = StatusCode::CREATED;
+    response
+}
+
+fn router(counter: Counter) -> Router {
+    let idempotency = Idempotency::new(
+        Arc::new(MemoryIdempotencyStore::new()),
+        &IdempotencyConfig::default(),
+    );
+    Router::new()
+        .route("/orders", post(create))
+        .with_state(counter)
+        .layer(axum::middleware::from_fn_with_state(
+            idempotency,
+            idempotency_middleware,
+        ))
+}
+
+fn request(key: &str, body: Value, peer: [u8; 4]) -> Request<Body> {
+    Request::post("/orders")
+        .header(CONTENT_TYPE, "application/json")
+        .header("idempotency-key", key)
+        .extension(ConnectInfo(SocketAddr::from((peer, 4000))))
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+async fn send(router: &Router, request: Request<Body>) -> Response {
+    router.clone().oneshot(request).await.unwrap()
+}
+
+async fn body_json(response: Response) -> Value {
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+const CLIENT: [u8; 4] = [203, 0, 113, 9];
+
+#[tokio::test]
+async fn replays_first_successful_response() {
+    let counter = Counter::default();
+    let router = router(counter.clone());
+
+    let first = send(&router, request("k1", json!({"item": 1}), CLIENT)).await;
+    assert_eq!(first.status(), StatusCode::CREATED);
+    assert!(!first.headers().contains_key("idempotent-replayed"));
+    let first = body_json(first).await;
+
+    let retry = send(&router, request("k1", json!({"item": 1}), CLIENT)).await;
+    assert_eq!(retry.status(), StatusCode::CREATED);
+    assert_eq!(retry.headers()["idempotent-replayed"], "true");
+    assert_eq!(retry.headers()[CONTENT_TYPE], "application/json");
+    assert_eq!(body_json(retry).await, first);
+    assert_eq!(counter.calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn rejects_key_reused_with_different_body() {
+    let router = router(Counter::default());
+    send(&router, request("k1", json!({"item": 1}), CLIENT)).await;
+
+    let response = send(&router, request("k1", json!({"item": 2}), CLIENT)).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body = body_json(response).await;
+    assert_eq!(body["error"]["status"], "FAILED_PRECONDITION");
+    assert_eq!(
+        body["error"]["errors"][0]["reason"],
+        "IDEMPOTENCY_KEY_REUSED"
+    );
+}
+
+#[tokio::test]
+async fn rejects_retry_while_first_request_in_flight() {
+    let gate = Arc::new(Notify::new());
+    let counter = Counter {
+        gate: Some(gate.clone()),
+        ..Counter::default()
+    };
+    let router = router(counter.clone());
+
+    let first = tokio::spawn({
+        let router = router.clone();
+        async move { send(&router, request("k1", json!({"item": 1}), CLIENT)).await }
+    });
+    tokio::task::yield_now().await;
+
+    let response = send(&router, request("k1", json!({"item": 1}), CLIENT)).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    assert!(response.headers().contains_key(RETRY_AFTER));
+    let body = body_json(response).await;
+    assert_eq!(body["error"]["errors"][0]["reason"], "REQUEST_IN_PROGRESS");
+
+    gate.notify_one();
+    assert_eq!(first.await.unwrap().status(), StatusCode::CREATED);
+    assert_eq!(counter.calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn failed_responses_are_not_stored() {
+    let counter = Counter::default();
+    let router = router(counter.clone());
+
+    for _ in 0..2 {
+        let response = send(&router, request("k1", json!({"fail": true}), CLIENT)).await;
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+    assert_eq!(counter.calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn rejects_oversized_bodies_unless_route_allows_more() {
+    let config = IdempotencyConfig {
+        max_body_size: 64,
+        ..Default::default()
+    };
+    let idempotency = Idempotency::new(Arc::new(MemoryIdempotencyStore::new()), &config)
+        .with_route_body_size("/imports", 4096);
+    let counter = Counter::default();
+    let router = Router::new()
+        .route("/orders", post(create))
+        .route("/imports", post(create))
+        .with_state(counter.clone())
+        .layer(axum::middleware::from_fn_with_state(
+            idempotency,
+            idempotency_middleware,
+        ));
+    let body = json!({"blob": "x".repeat(256)});
+
+    let rejected = send(&router, request("k1", body.clone(), CLIENT)).await;
+    assert_eq!(rejected.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(counter.calls.load(Ordering::SeqCst), 0);
+
+    for _ in 0..2 {
+        let mut import = request("k2", body.clone(), CLIENT);
+        *import.uri_mut() = "/imports".parse().unwrap();
+        assert_eq!(send(&router, import).await.status(), StatusCode::CREATED);
+    }
+    assert_eq!(counter.calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn scopes_keys_by_client() {
+    let counter = Counter::default();
+    let router = router(counter.clone());
+
+    send(&router, request("k1", json!({"item": 1}), CLIENT)).await;
+    let other = send(
+        &router,
+        request("k1", json!({"item": 2}), [198, 51, 100, 7]),
+    )
+    .await;
+
+    assert_eq!(other.status(), StatusCode::CREATED);
+    assert_eq!(counter.calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn rejects_invalid_key() {
+    let router = router(Counter::default());
+
+    let response = send(&router, request("bad key", json!({}), CLIENT)).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = body_json(response).await;
+    assert_eq!(body["error"]["errors"][0]["location"], "Idempotency-Key");
+}
+
+#[test]
+fn resolves_client_ip_behind_trusted_proxies() {
+    let proxies = TrustedProxies::parse(&["10.0.0.0/8"]).unwrap();
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        "x-forwarded-for",
+        "1.2.3.4, 203.0.113.9, 10.0.0.2".parse().unwrap(),
+    );
+
+    let proxy: IpAddr = "10.0.0.1".parse().unwrap();
+    let client: IpAddr = "203.0.113.9".parse().unwrap();
+    assert_eq!(proxies.client_ip(&headers, proxy), client);
+
+    // 对端不是受信任代理时不采信 X-Forwarded-For
+    let direct: IpAddr = "198.51.100.7".parse().unwrap();
+    assert_eq!(proxies.client_ip(&headers, direct), direct);
+}
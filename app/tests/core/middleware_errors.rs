@@ -0,0 +1,133 @@
+//! 中间件错误映射测试。
+//!
+//! 覆盖限流（429 + `Retry-After` 和限流响应头）、超时（504）、过载（503）
+//! 以及未知错误（500）都转换为统一格式的 JSON 错误响应。
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use app::core::middleware::handle_middleware_error;
+use app::handle_rate_limit_error;
+use axum::body::{Body, to_bytes};
+use axum::error_handling::HandleErrorLayer;
+use axum::http::header::RETRY_AFTER;
+use axum::http::{HeaderMap, HeaderValue, Request, StatusCode};
+use axum::response::Response;
+use axum::routing::get;
+use axum::{BoxError, Router};
+use serde_json::Value;
+use tower::ServiceBuilder;
+use tower::load_shed::error::Overloaded;
+use tower::timeout::TimeoutLayer;
+use tower::timeout::error::Elapsed;
+use tower::{ServiceExt, service_fn};
+use tower_governor::governor::GovernorConfigBuilder;
+use tower_governor::key_extractor::GlobalKeyExtractor;
+use tower_governor::{GovernorError, GovernorLayer};
+
+async fn body_json(response: Response) -> Value {
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+fn too_many_requests() -> GovernorError {
+    let mut headers = HeaderMap::new();
+    headers.insert("x-ratelimit-after", HeaderValue::from(3u64));
+    GovernorError::TooManyRequests {
+        wait_time: 3,
+        headers: Some(headers),
+    }
+}
+
+#[tokio::test]
+async fn rate_limit_error_becomes_429_json() {
+    let response = handle_rate_limit_error(too_many_requests());
+
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(response.headers()[RETRY_AFTER], "3");
+    assert_eq!(response.headers()["x-ratelimit-after"], "3");
+    let body = body_json(response).await;
+    assert_eq!(body["error"]["code"], 429);
+    assert_eq!(body["error"]["errors"][0]["domain"], "rate_limit");
+    assert_eq!(body["error"]["errors"][0]["reason"], "RATE_LIMIT_EXCEEDED");
+}
+
+#[tokio::test]
+async fn maps_middleware_errors_by_type() {
+    let cases: Vec<(BoxError, StatusCode, Option<&str>)> = vec![
+        (
+            Box::new(too_many_requests()),
+            StatusCode::TOO_MANY_REQUESTS,
+            Some("RATE_LIMIT_EXCEEDED"),
+        ),
+        (
+            Box::new(Elapsed::new()),
+            StatusCode::GATEWAY_TIMEOUT,
+            Some("TIMEOUT"),
+        ),
+        (
+            Box::new(Overloaded::new()),
+            StatusCode::SERVICE_UNAVAILABLE,
+            Some("SERVICE_UNAVAILABLE"),
+        ),
+        ("boom".into(), StatusCode::INTERNAL_SERVER_ERROR, None),
+    ];
+
+    for (error, status, reason) in cases {
+        let response = handle_middleware_error(error).await;
+        assert_eq!(response.status(), status);
+        let body = body_json(response).await;
+        assert_eq!(body["error"]["code"], status.as_u16());
+        match reason {
+            Some(reason) => assert_eq!(body["error"]["errors"][0]["reason"], reason),
+            None => assert!(body["error"].get("errors").is_none()),
+        }
+    }
+}
+
+#[tokio::test]
+async fn timeout_layer_returns_504_json() {
+    let service = ServiceBuilder::new()
+        .layer(HandleErrorLayer::new(handle_middleware_error))
+        .layer(TimeoutLayer::new(Duration::from_millis(10)))
+        .service(service_fn(|_request: Request<Body>| async {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            Ok::<_, std::convert::Infallible>(Response::new(Body::empty()))
+        }));
+
+    let response = service.oneshot(Request::new(Body::empty())).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+    let body = body_json(response).await;
+    assert_eq!(body["error"]["errors"][0]["reason"], "TIMEOUT");
+}
+
+#[tokio::test]
+async fn governor_layer_returns_429_json() {
+    let config = Arc::new(
+        GovernorConfigBuilder::default()
+            .key_extractor(GlobalKeyExtractor)
+            .per_second(60)
+            .burst_size(1)
+            .use_headers()
+            .finish()
+            .unwrap(),
+    );
+    let router = Router::new()
+        .route("/", get(|| async { "ok" }))
+        .layer(GovernorLayer::new(config).error_handler(handle_rate_limit_error));
+
+    let first = router
+        .clone()
+        .oneshot(Request::new(Body::empty()))
+        .await
+        .unwrap();
+    assert_eq!(first.status(), StatusCode::OK);
+
+    let second = router.oneshot(Request::new(Body::empty())).await.unwrap();
+    assert_eq!(second.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(second.headers().contains_key(RETRY_AFTER));
+    assert!(second.headers().contains_key("x-ratelimit-limit"));
+    let body = body_json(second).await;
+    assert_eq!(body["error"]["errors"][0]["reason"], "RATE_LIMIT_EXCEEDED");
+}
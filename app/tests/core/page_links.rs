@@ -0,0 +1,177 @@
+//! 分页链接测试。
+//!
+//! 覆盖还原请求原始 URL（仅采信受信任代理的 `X-Forwarded-*`）、列表响应的
+//! `self_link` / `previous_link` / `next_link`，以及 `Link` 与 `X-Total-Count` 响应头。
+
+use std::net::{IpAddr, SocketAddr};
+
+use app::core::middleware::{PaginationLinks, pagination_links_middleware};
+use app::{ApiResponse, RequestUrl, TrustedProxies};
+use axum::Router;
+use axum::body::{Body, to_bytes};
+use axum::extract::ConnectInfo;
+use axum::http::header::LINK;
+use axum::http::{HeaderMap, Request, Uri};
+use axum::response::Response;
+use axum::routing::get;
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+fn forwarded_headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert("host", "10.0.0.5:3000".parse().unwrap());
+    headers.insert("x-forwarded-proto", "https".parse().unwrap());
+    headers.insert(
+        "x-forwarded-host",
+        "api.example.com, internal".parse().unwrap(),
+    );
+    headers.insert("x-forwarded-prefix", "/api/".parse().unwrap());
+    headers
+}
+
+fn proxies() -> TrustedProxies {
+    TrustedProxies::parse(&["10.0.0.0/8", "::1"]).unwrap()
+}
+
+#[test]
+fn trusts_forwarded_headers_only_from_proxies() {
+    let uri: Uri = "/v1/user/list?page=2&page_size=10".parse().unwrap();
+    let headers = forwarded_headers();
+
+    let proxy: IpAddr = "10.1.2.3".parse().unwrap();
+    let url = RequestUrl::from_request(&headers, &uri, Some(proxy), &proxies());
+    assert_eq!(url.origin(), "https://api.example.com");
+    assert_eq!(
+        url.href(),
+        "https://api.example.com/api/v1/user/list?page=2&page_size=10"
+    );
+
+    let client: IpAddr = "203.0.113.9".parse().unwrap();
+    let url = RequestUrl::from_request(&headers, &uri, Some(client), &proxies());
+    assert_eq!(url.origin(), "http://10.0.0.5:3000");
+
+    let url = RequestUrl::from_request(&headers, &uri, None, &proxies());
+    assert_eq!(url.origin(), "http://10.0.0.5:3000");
+}
+
+#[test]
+fn matches_proxy_networks() {
+    let proxies = proxies();
+    assert!(proxies.contains("10.255.0.1".parse().unwrap()));
+    assert!(proxies.contains("::ffff:10.0.0.1".parse().unwrap()));
+    assert!(proxies.contains("::1".parse().unwrap()));
+    assert!(!proxies.contains("192.168.0.1".parse().unwrap()));
+
+    assert!(TrustedProxies::parse(&["not-an-ip"]).is_err());
+}
+
+#[test]
+fn builds_page_urls_keeping_other_params() {
+    let uri: Uri = "/users?page_size=10&page=3&fields=items(id)"
+        .parse()
+        .unwrap();
+    let url = RequestUrl::from_request(&HeaderMap::new(), &uri, None, &TrustedProxies::default());
+
+    assert_eq!(
+        url.page_href(4),
+        "http://localhost/users?page_size=10&fields=items%28id%29&page=4"
+    );
+    assert_eq!(
+        url.page_template(),
+        "http://localhost/users?page_size=10&fields=items%28id%29&page={page}"
+    );
+}
+
+fn list_data(page: i64) -> Value {
+    let uri: Uri = "/users?page_size=10".parse().unwrap();
+    let url = RequestUrl::from_request(&HeaderMap::new(), &uri, None, &TrustedProxies::default());
+    let response = ApiResponse::list(vec![json!({"id": 1})], 25, page, 10).with_page_links(&url);
+    serde_json::to_value(response).unwrap()["data"].clone()
+}
+
+#[test]
+fn omits_previous_on_first_page_and_next_on_last_page() {
+    let data = list_data(1);
+    assert_eq!(
+        data["self_link"],
+        "http://localhost/users?page_size=10&page=1"
+    );
+    assert_eq!(
+        data["next_link"],
+        "http://localhost/users?page_size=10&page=2"
+    );
+    assert!(data.get("previous_link").is_none());
+
+    let data = list_data(2);
+    assert_eq!(
+        data["previous_link"],
+        "http://localhost/users?page_size=10&page=1"
+    );
+    assert_eq!(
+        data["next_link"],
+        "http://localhost/users?page_size=10&page=3"
+    );
+
+    let data = list_data(3);
+    assert!(data.get("next_link").is_none());
+    assert_eq!(
+        data["page_link_template"],
+        "http://localhost/users?page_size=10&page={page}"
+    );
+}
+
+fn router(headers: bool) -> Router {
+    let users = Router::new().route(
+        "/users",
+        get(|url: RequestUrl| async move {
+            ApiResponse::list(vec![json!({"id": 1})], 25, 2, 10).with_page_links(&url)
+        }),
+    );
+    Router::new()
+        .nest("/v1", users)
+        .layer(axum::middleware::from_fn_with_state(
+            PaginationLinks::new(proxies(), headers),
+            pagination_links_middleware,
+        ))
+}
+
+async fn send(headers: bool) -> Response {
+    // 直接对端为受信任代理
+    let mut request = Request::get("/v1/users?page=2&page_size=10")
+        .extension(ConnectInfo(SocketAddr::from(([10, 0, 0, 1], 4000))));
+    for (name, value) in &forwarded_headers() {
+        request = request.header(name, value);
+    }
+    router(headers)
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn emits_link_and_total_count_headers() {
+    let response = send(true).await;
+
+    let base = "https://api.example.com/api/v1/users?page_size=10";
+    assert_eq!(
+        response.headers()[LINK],
+        format!(
+            "<{base}&page=1>; rel=\"first\", <{base}&page=1>; rel=\"prev\", \
+             <{base}&page=3>; rel=\"next\", <{base}&page=3>; rel=\"last\""
+        )
+        .as_str()
+    );
+    assert_eq!(response.headers()["x-total-count"], "25");
+
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["data"]["self_link"], format!("{base}&page=2").as_str());
+}
+
+#[tokio::test]
+async fn pagination_headers_can_be_disabled() {
+    let response = send(false).await;
+
+    assert!(!response.headers().contains_key(LINK));
+    assert!(!response.headers().contains_key("x-total-count"));
+}
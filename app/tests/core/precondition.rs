@@ -0,0 +1,115 @@
+//! 乐观并发控制（`If-Match`）测试。
+//!
+//! 覆盖 `If-Match` 解析为期望版本（未携带、`*`、弱 ETag、非版本号），
+//! 以及版本不一致返回 412（`ABORTED`）、缺少请求头返回 428（`FAILED_PRECONDITION`）。
+
+use app::error::PreconditionError;
+use app::{AppError, IfMatch, version_tag};
+use axum::Router;
+use axum::body::{Body, to_bytes};
+use axum::http::header::IF_MATCH;
+use axum::http::{HeaderMap, HeaderValue, Request, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::put;
+use serde_json::Value;
+use tower::ServiceExt;
+
+fn if_match(value: &str) -> IfMatch {
+    let mut headers = HeaderMap::new();
+    headers.insert(IF_MATCH, HeaderValue::from_str(value).unwrap());
+    IfMatch::from_headers(&headers)
+}
+
+#[test]
+fn parses_expected_version() {
+    assert_eq!(version_tag(3), "\"3\"");
+    assert_eq!(if_match("\"3\"").version(true).unwrap(), Some(3));
+    assert_eq!(if_match("W/\"2\", \"3\"").version(true).unwrap(), Some(3));
+    // 协商了语言的 ETag 带语言后缀，版本相同
+    assert_eq!(if_match("\"3;en\"").version(true).unwrap(), Some(3));
+    assert_eq!(if_match("*").version(true).unwrap(), None);
+    assert_eq!(IfMatch::default().version(false).unwrap(), None);
+
+    assert!(matches!(
+        IfMatch::default().version(true),
+        Err(PreconditionError::Required)
+    ));
+    assert!(matches!(
+        if_match("W/\"3\"").version(false),
+        Err(PreconditionError::Failed)
+    ));
+    assert!(matches!(
+        if_match("\"abc\"").version(false),
+        Err(PreconditionError::Failed)
+    ));
+}
+
+#[test]
+fn matches_with_strong_comparison() {
+    assert!(if_match("\"3\"").matches("\"3\""));
+    assert!(if_match("*").matches("\"3\""));
+    assert!(!if_match("W/\"3\"").matches("\"3\""));
+    assert!(!if_match("\"2\"").matches("\"3\""));
+}
+
+async fn body_json(response: Response) -> Value {
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn errors_carry_status_semantics() {
+    let response = PreconditionError::Failed.into_response();
+    assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+    let body = body_json(response).await;
+    assert_eq!(body["error"]["code"], 412);
+    assert_eq!(body["error"]["status"], "ABORTED");
+    assert_eq!(body["error"]["errors"][0]["reason"], "PRECONDITION_FAILED");
+
+    let response = AppError::from(PreconditionError::Required).into_response();
+    assert_eq!(response.status(), StatusCode::PRECONDITION_REQUIRED);
+    let body = body_json(response).await;
+    assert_eq!(body["error"]["status"], "FAILED_PRECONDITION");
+    assert_eq!(
+        body["error"]["errors"][0]["reason"],
+        "PRECONDITION_REQUIRED"
+    );
+    assert_eq!(body["error"]["errors"][0]["location"], "If-Match");
+    assert_eq!(body["error"]["errors"][0]["location_type"], "header");
+}
+
+/// 模拟当前版本为 3 的资源
+fn router(required: bool) -> Router {
+    Router::new().route(
+        "/resource",
+        put(move |if_match: IfMatch| async move {
+            match if_match.version(required)? {
+                Some(version) if version != 3 => Err(PreconditionError::Failed),
+                _ => Ok(StatusCode::NO_CONTENT),
+            }
+        }),
+    )
+}
+
+async fn send(required: bool, if_match: Option<&str>) -> StatusCode {
+    let mut request = Request::put("/resource");
+    if let Some(tag) = if_match {
+        request = request.header(IF_MATCH, tag);
+    }
+    router(required)
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn enforces_if_match_on_mutation() {
+    assert_eq!(send(true, Some("\"3\"")).await, StatusCode::NO_CONTENT);
+    assert_eq!(
+        send(true, Some("\"2\"")).await,
+        StatusCode::PRECONDITION_FAILED
+    );
+    assert_eq!(send(true, None).await, StatusCode::PRECONDITION_REQUIRED);
+    assert_eq!(send(false, None).await, StatusCode::NO_CONTENT);
+}
@@ -0,0 +1,164 @@
+//! RFC 9457 Problem Details 错误格式测试。
+//!
+//! 覆盖 `Accept` 协商、配置默认格式、Problem 文档字段（type/title/status/detail/instance/errors）
+//! 以及改写时保留原响应头。
+
+use app::core::config::ResponseConfig;
+use app::core::middleware::error_format_middleware;
+use app::core::response::{ApiError, ErrorFormat, ProblemDetails, Reason, problem_type_uri};
+use app::{ApiResponse, Domain};
+use axum::Router;
+use axum::body::{Body, to_bytes};
+use axum::http::header::{ACCEPT, CONTENT_TYPE, VARY};
+use axum::http::{HeaderValue, Request, StatusCode};
+use axum::response::IntoResponse;
+use axum::routing::get;
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+#[test]
+fn negotiates_by_quality() {
+    let default = ErrorFormat::Google;
+    assert_eq!(ErrorFormat::negotiate(None, default), ErrorFormat::Google);
+    assert_eq!(
+        ErrorFormat::negotiate(Some("application/problem+json"), default),
+        ErrorFormat::Problem
+    );
+    assert_eq!(
+        ErrorFormat::negotiate(
+            Some("application/problem+json;q=0.5, application/json"),
+            ErrorFormat::Problem
+        ),
+        ErrorFormat::Google
+    );
+    assert_eq!(
+        ErrorFormat::negotiate(Some("*/*"), ErrorFormat::Problem),
+        ErrorFormat::Problem
+    );
+    assert_eq!(
+        ErrorFormat::negotiate(Some("application/json"), ErrorFormat::Problem),
+        ErrorFormat::Google
+    );
+}
+
+#[test]
+fn derives_type_uri_from_domain_and_reason() {
+    assert_eq!(
+        problem_type_uri("/problems/", "validation", "INVALID_FORMAT"),
+        "/problems/validation/invalid-format"
+    );
+}
+
+#[test]
+fn converts_api_error_without_details_to_about_blank() {
+    let error = ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Internal server error");
+    let problem = ProblemDetails::from_api_error(&error, "/problems", None);
+
+    assert_eq!(problem.problem_type, "about:blank");
+    assert_eq!(problem.title, "Internal Server Error");
+    assert_eq!(problem.status, 500);
+}
+
+fn router(config: ResponseConfig) -> Router {
+    Router::new()
+        .route(
+            "/fail",
+            get(|| async {
+                let mut response = ApiResponse::error(ApiError::from_reason(
+                    StatusCode::NOT_FOUND,
+                    Domain::USER,
+                    Reason::UserNotFound,
+                ))
+                .into_response();
+                response
+                    .headers_mut()
+                    .insert("retry-after", HeaderValue::from_static("5"));
+                response
+            }),
+        )
+        .route(
+            "/ok",
+            get(|| async { ApiResponse::success(json!({ "message": "ok" })) }),
+        )
+        .layer(axum::middleware::from_fn_with_state(
+            config,
+            error_format_middleware,
+        ))
+}
+
+async fn send(
+    config: ResponseConfig,
+    uri: &str,
+    accept: Option<&str>,
+) -> (StatusCode, axum::http::HeaderMap, Value) {
+    let mut request = Request::builder().uri(uri).header("x-request-id", "req-1");
+    if let Some(accept) = accept {
+        request = request.header(ACCEPT, accept);
+    }
+    let response = router(config)
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, headers, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn keeps_google_format_by_default() {
+    let (status, headers, body) = send(ResponseConfig::default(), "/fail", None).await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(headers[CONTENT_TYPE], "application/json");
+    assert_eq!(headers[VARY], "accept");
+    assert_eq!(body["error"]["code"], 404);
+}
+
+#[tokio::test]
+async fn renders_problem_when_accepted() {
+    let (status, headers, body) = send(
+        ResponseConfig::default(),
+        "/fail",
+        Some("application/problem+json"),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(headers[CONTENT_TYPE], "application/problem+json");
+    assert_eq!(headers["retry-after"], "5");
+    assert_eq!(body["type"], "/problems/user/user-not-found");
+    assert_eq!(body["title"], "Not Found");
+    assert_eq!(body["status"], 404);
+    assert_eq!(body["detail"], "USER_NOT_FOUND");
+    assert_eq!(body["instance"], "req-1");
+    assert_eq!(body["errors"][0]["reason"], "USER_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn uses_configured_default_format() {
+    let config = ResponseConfig {
+        error_format: ErrorFormat::Problem,
+        ..Default::default()
+    };
+    let (_, headers, _) = send(config.clone(), "/fail", Some("*/*")).await;
+    assert_eq!(headers[CONTENT_TYPE], "application/problem+json");
+
+    let (_, headers, _) = send(config, "/fail", Some("application/json")).await;
+    assert_eq!(headers[CONTENT_TYPE], "application/json");
+}
+
+#[tokio::test]
+async fn leaves_success_responses_untouched() {
+    let (status, headers, body) = send(
+        ResponseConfig::default(),
+        "/ok",
+        Some("application/problem+json"),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers[CONTENT_TYPE], "application/json");
+    assert!(headers.get(VARY).is_none());
+    assert_eq!(body["data"]["message"], "ok");
+}
@@ -0,0 +1,127 @@
+//! 请求 ID 与追踪上下文测试。
+//!
+//! 覆盖沿用合法的 `X-Request-ID`、不合法时重新生成、`traceparent` / `tracestate` 解析，
+//! 以及错误响应体中的 `request_id` 与响应头一致。
+
+use app::core::middleware::request_id_middleware;
+use app::response::Reason;
+use app::{ApiResponse, Domain, RequestId, TraceContext};
+use axum::body::{Body, to_bytes};
+use axum::extract::Request;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Extension, Router};
+use serde_json::{Value, json};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+/// 返回处理器看到的请求 ID 与追踪 ID
+async fn echo(request: Request) -> ApiResponse<Value> {
+    let request_id = request
+        .extensions()
+        .get::<RequestId>()
+        .map(RequestId::to_string);
+    let trace = request.extensions().get::<TraceContext>();
+    ApiResponse::success(json!({
+        "header": request.headers()["x-request-id"].to_str().unwrap(),
+        "request_id": request_id,
+        "trace_id": trace.map(|context| context.trace_id.clone()),
+        "tracestate": trace.and_then(|context| context.tracestate.clone()),
+    }))
+}
+
+async fn fail(Extension(_): Extension<RequestId>) -> Response {
+    ApiResponse::<()>::fail(StatusCode::NOT_FOUND, Domain::USER, Reason::UserNotFound)
+        .into_response()
+}
+
+async fn send(headers: &[(&str, &str)], uri: &str) -> (Response, Value) {
+    let router = Router::new()
+        .route("/echo", get(echo))
+        .route("/fail", get(fail))
+        .layer(axum::middleware::from_fn(request_id_middleware));
+    let mut request = Request::get(uri);
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    let response = router
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let (parts, body) = response.into_parts();
+    let bytes = to_bytes(body, usize::MAX).await.unwrap();
+    let body = serde_json::from_slice(&bytes).unwrap();
+    (Response::from_parts(parts, Body::empty()), body)
+}
+
+#[tokio::test]
+async fn generates_request_id() {
+    let (response, body) = send(&[], "/echo").await;
+
+    let request_id = response.headers()["x-request-id"].to_str().unwrap();
+    assert!(Uuid::parse_str(request_id).is_ok());
+    assert_eq!(body["data"]["header"], request_id);
+    assert_eq!(body["data"]["request_id"], request_id);
+    assert_eq!(body["data"]["trace_id"], Value::Null);
+}
+
+#[tokio::test]
+async fn keeps_valid_incoming_request_id() {
+    let (response, body) = send(&[("x-request-id", "gw-2024.10:abc_1")], "/echo").await;
+
+    assert_eq!(response.headers()["x-request-id"], "gw-2024.10:abc_1");
+    assert_eq!(body["data"]["request_id"], "gw-2024.10:abc_1");
+}
+
+#[tokio::test]
+async fn replaces_invalid_incoming_request_id() {
+    let too_long = "a".repeat(129);
+    for invalid in ["", "has space", "line\tbreak", "id;drop", too_long.as_str()] {
+        let (response, body) = send(&[("x-request-id", invalid)], "/echo").await;
+
+        let request_id = response.headers()["x-request-id"].to_str().unwrap();
+        assert!(Uuid::parse_str(request_id).is_ok(), "{invalid:?}");
+        assert_eq!(body["data"]["header"], request_id);
+    }
+}
+
+#[tokio::test]
+async fn parses_traceparent() {
+    let headers = [("traceparent", TRACEPARENT), ("tracestate", "vendor=abc")];
+    let (_, body) = send(&headers, "/echo").await;
+    assert_eq!(body["data"]["trace_id"], "4bf92f3577b34da6a3ce929d0e0e4736");
+    assert_eq!(body["data"]["tracestate"], "vendor=abc");
+
+    let context = TraceContext::parse(TRACEPARENT, None).unwrap();
+    assert_eq!(context.parent_id, "00f067aa0ba902b7");
+    assert!(context.sampled());
+    // 更高版本可以追加字段
+    assert!(TraceContext::parse(&format!("01-{}-extra", &TRACEPARENT[3..]), None).is_some());
+
+    for invalid in [
+        "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+        "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+        "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+    ] {
+        assert_eq!(
+            TraceContext::parse(invalid, Some("vendor=abc")),
+            None,
+            "{invalid}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn includes_request_id_in_errors() {
+    let (response, body) = send(&[("x-request-id", "support-123")], "/fail").await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(response.headers()["x-request-id"], "support-123");
+    assert_eq!(body["error"]["request_id"], "support-123");
+}
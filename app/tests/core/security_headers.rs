@@ -0,0 +1,137 @@
+//! 安全响应头测试。
+//!
+//! 覆盖默认安全响应头、`/docs` 按前缀覆盖内容安全策略、404 页面保留自己的策略、
+//! 空值与 HSTS 关闭时不输出，以及配置校验。
+
+use app::core::config::{ConfigSection, DEFAULT_CSP, DOCS_CSP, SecurityHeadersConfig};
+use app::core::middleware::{SecurityHeaders, security_headers_middleware};
+use app::{ApiResponse, NOT_FOUND_CSP, handle_404};
+use axum::Router;
+use axum::body::Body;
+use axum::http::header::{
+    ACCEPT, CONTENT_SECURITY_POLICY, REFERRER_POLICY, STRICT_TRANSPORT_SECURITY,
+    X_CONTENT_TYPE_OPTIONS, X_FRAME_OPTIONS,
+};
+use axum::http::{Request, StatusCode};
+use axum::response::Response;
+use axum::routing::get;
+use serde_json::json;
+use tower::ServiceExt;
+
+fn router(config: &SecurityHeadersConfig) -> Router {
+    let ok = || async { ApiResponse::success(json!({"ok": true})) };
+    Router::new()
+        .route("/v1/items", get(ok))
+        .route("/docs/", get(ok))
+        .route("/docs2", get(ok))
+        .fallback(handle_404)
+        .layer(axum::middleware::from_fn_with_state(
+            SecurityHeaders::from_config(config).unwrap(),
+            security_headers_middleware,
+        ))
+}
+
+async fn send(config: &SecurityHeadersConfig, request: Request<Body>) -> Response {
+    router(config).oneshot(request).await.unwrap()
+}
+
+fn get_request(uri: &str) -> Request<Body> {
+    Request::get(uri).body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn adds_default_security_headers() {
+    let config = SecurityHeadersConfig::default();
+    for uri in ["/v1/items", "/v1/missing"] {
+        let response = send(&config, get_request(uri)).await;
+        let headers = response.headers();
+
+        assert_eq!(
+            headers[STRICT_TRANSPORT_SECURITY],
+            "max-age=31536000; includeSubDomains"
+        );
+        assert_eq!(headers[X_CONTENT_TYPE_OPTIONS], "nosniff");
+        assert_eq!(headers[X_FRAME_OPTIONS], "DENY");
+        assert_eq!(headers[REFERRER_POLICY], "no-referrer");
+        assert!(headers.contains_key("permissions-policy"));
+        assert_eq!(headers[CONTENT_SECURITY_POLICY], DEFAULT_CSP);
+    }
+}
+
+#[tokio::test]
+async fn overrides_csp_by_route_prefix() {
+    let config = SecurityHeadersConfig::default();
+
+    let docs = send(&config, get_request("/docs/")).await;
+    assert_eq!(docs.headers()[CONTENT_SECURITY_POLICY], DOCS_CSP);
+    // 按路径段匹配，/docs2 不属于 /docs
+    let other = send(&config, get_request("/docs2")).await;
+    assert_eq!(other.headers()[CONTENT_SECURITY_POLICY], DEFAULT_CSP);
+}
+
+#[tokio::test]
+async fn keeps_not_found_page_policy() {
+    let request = Request::get("/missing")
+        .header(ACCEPT, "text/html")
+        .body(Body::empty())
+        .unwrap();
+    let response = send(&SecurityHeadersConfig::default(), request).await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(response.headers()[CONTENT_SECURITY_POLICY], NOT_FOUND_CSP);
+    assert_eq!(response.headers()[X_FRAME_OPTIONS], "DENY");
+}
+
+#[tokio::test]
+async fn omits_disabled_headers() {
+    let mut config = SecurityHeadersConfig {
+        hsts_max_age: 0,
+        frame_options: String::new(),
+        ..SecurityHeadersConfig::default()
+    };
+    config.route_csp.insert("/docs".to_string(), String::new());
+
+    let response = send(&config, get_request("/docs/")).await;
+    let headers = response.headers();
+    assert!(!headers.contains_key(STRICT_TRANSPORT_SECURITY));
+    assert!(!headers.contains_key(X_FRAME_OPTIONS));
+    assert!(!headers.contains_key(CONTENT_SECURITY_POLICY));
+    assert_eq!(headers[X_CONTENT_TYPE_OPTIONS], "nosniff");
+
+    let config = SecurityHeadersConfig {
+        enabled: false,
+        ..SecurityHeadersConfig::default()
+    };
+    let response = send(&config, get_request("/v1/items")).await;
+    assert!(!response.headers().contains_key(X_CONTENT_TYPE_OPTIONS));
+}
+
+#[test]
+fn validates_config() {
+    assert!(SecurityHeadersConfig::default().validate().is_ok());
+
+    let invalid = [
+        SecurityHeadersConfig {
+            frame_options: "ALLOW-FROM https://example.com".to_string(),
+            ..SecurityHeadersConfig::default()
+        },
+        SecurityHeadersConfig {
+            hsts_preload: true,
+            hsts_max_age: 3600,
+            ..SecurityHeadersConfig::default()
+        },
+        SecurityHeadersConfig {
+            content_security_policy: "default-src 'none'\n".to_string(),
+            ..SecurityHeadersConfig::default()
+        },
+    ];
+    for config in invalid {
+        assert!(config.validate().is_err(), "{config:?}");
+    }
+
+    let mut config = SecurityHeadersConfig::default();
+    config
+        .route_csp
+        .insert("docs".to_string(), DOCS_CSP.to_string());
+    assert!(config.validate().is_err());
+}
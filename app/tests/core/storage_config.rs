@@ -0,0 +1,55 @@
+//! 存储配置测试。
+//!
+//! 覆盖私有目录与公开目录相同、互相包含（含 `..` 和符号链接绕过）时拒绝配置，
+//! 避免私有文件经 `/static` 被公开下载。
+
+use app::core::config::{ConfigSection, StorageConfig};
+
+fn storage(local_dir: &str, private_dir: &str) -> StorageConfig {
+    StorageConfig {
+        local_dir: local_dir.to_string(),
+        private_dir: private_dir.to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn accepts_separate_directories() {
+    assert!(storage("./uploads", "./data/private").validate().is_ok());
+    assert!(StorageConfig::default().validate().is_ok());
+}
+
+#[test]
+fn rejects_same_or_nested_directories() {
+    for (local_dir, private_dir) in [
+        ("./uploads", "./uploads/"),
+        ("./uploads", "uploads/private"),
+        ("./uploads/public", "./uploads"),
+        ("./uploads", "./data/../uploads/exports"),
+    ] {
+        assert!(
+            storage(local_dir, private_dir).validate().is_err(),
+            "{local_dir} / {private_dir}"
+        );
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn rejects_private_directory_reached_through_symlink() {
+    let root = std::env::temp_dir().join(format!("storage-config-{}", std::process::id()));
+    let public = root.join("public");
+    std::fs::create_dir_all(&public).unwrap();
+    let link = root.join("link");
+    let _ = std::fs::remove_file(&link);
+    std::os::unix::fs::symlink(&public, &link).unwrap();
+
+    let config = storage(
+        public.to_str().unwrap(),
+        link.join("exports").to_str().unwrap(),
+    );
+    let result = config.validate();
+    std::fs::remove_dir_all(&root).unwrap();
+
+    assert!(result.is_err());
+}
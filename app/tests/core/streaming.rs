@@ -0,0 +1,128 @@
+//! 流式列表响应测试。
+//!
+//! 覆盖按 `Accept` 协商 NDJSON / CSV、逐行编码（CSV 表头与公式注入防护）、
+//! 中途出错时中断响应体，以及两种媒体类型的 OpenAPI 描述。
+
+use std::io;
+
+use aide::axum::ApiRouter;
+use aide::axum::routing::get_with;
+use aide::openapi::OpenApi;
+use app::core::response::{CsvRecord, StreamFormat, StreamingList, escape_formula};
+use axum::body::to_bytes;
+use axum::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use axum::response::{IntoResponse, Response};
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::Value;
+
+#[derive(Debug, Serialize, JsonSchema)]
+struct Row {
+    id: u32,
+    name: String,
+}
+
+impl CsvRecord for Row {
+    fn csv_header() -> Vec<&'static str> {
+        vec!["id", "name"]
+    }
+
+    fn csv_record(&self) -> Vec<String> {
+        vec![self.id.to_string(), escape_formula(&self.name)]
+    }
+}
+
+fn rows() -> Vec<Result<Row, io::Error>> {
+    vec![
+        Ok(Row {
+            id: 1,
+            name: "alice".to_string(),
+        }),
+        Ok(Row {
+            id: 2,
+            name: "=cmd, \"x\"".to_string(),
+        }),
+    ]
+}
+
+fn streaming(format: StreamFormat, rows: Vec<Result<Row, io::Error>>) -> Response {
+    StreamingList::new(format, futures_util::stream::iter(rows))
+        .with_filename("rows")
+        .into_response()
+}
+
+async fn body_text(response: Response) -> String {
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+#[test]
+fn negotiates_format_from_accept() {
+    use StreamFormat::{Csv, Ndjson};
+
+    assert_eq!(StreamFormat::negotiate(None, Csv), Csv);
+    assert_eq!(StreamFormat::negotiate(Some("*/*"), Ndjson), Ndjson);
+    assert_eq!(
+        StreamFormat::negotiate(Some("application/x-ndjson"), Csv),
+        Ndjson
+    );
+    assert_eq!(
+        StreamFormat::negotiate(Some("application/x-ndjson;q=0.5, text/csv"), Ndjson),
+        Csv
+    );
+}
+
+#[tokio::test]
+async fn streams_ndjson_lines() {
+    let response = streaming(StreamFormat::Ndjson, rows());
+
+    assert_eq!(response.headers()[CONTENT_TYPE], "application/x-ndjson");
+    assert_eq!(
+        response.headers()[CONTENT_DISPOSITION],
+        "attachment; filename=\"rows.ndjson\""
+    );
+    let body = body_text(response).await;
+    let lines: Vec<Value> = body
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[1]["name"], "=cmd, \"x\"");
+}
+
+#[tokio::test]
+async fn streams_csv_with_header() {
+    let response = streaming(StreamFormat::Csv, rows());
+
+    assert_eq!(response.headers()[CONTENT_TYPE], "text/csv; charset=utf-8");
+    assert_eq!(
+        body_text(response).await,
+        "id,name\n1,alice\n2,\"'=cmd, \"\"x\"\"\"\n"
+    );
+}
+
+#[tokio::test]
+async fn aborts_body_on_row_error() {
+    let mut rows = rows();
+    rows.insert(1, Err(io::Error::other("cursor closed")));
+    let response = streaming(StreamFormat::Ndjson, rows);
+
+    assert!(to_bytes(response.into_body(), usize::MAX).await.is_err());
+}
+
+#[test]
+fn documents_both_media_types() {
+    async fn export() -> StreamingList<Row> {
+        StreamingList::new(StreamFormat::Csv, futures_util::stream::iter(rows()))
+    }
+
+    let mut api = OpenApi::default();
+    let _router: axum::Router = ApiRouter::new()
+        .api_route("/export", get_with(export, |op| op))
+        .finish_api(&mut api);
+    let api = serde_json::to_value(api).unwrap();
+
+    let content = &api["paths"]["/export"]["get"]["responses"]["200"]["content"];
+    assert!(content["application/x-ndjson"]["schema"].is_object());
+    assert_eq!(content["text/csv"]["schema"]["type"], "string");
+}
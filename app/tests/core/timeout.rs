@@ -0,0 +1,122 @@
+//! 请求超时测试。
+//!
+//! 覆盖按路径段的最长前缀匹配、从 `ServerConfig` 构建超时设置，
+//! 以及超时后返回 504 JSON 并取消处理器。
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use app::core::config::ServerConfig;
+use app::core::middleware::{RequestTimeout, timeout_middleware};
+use axum::Router;
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode};
+use axum::routing::get;
+use serde_json::Value;
+use tower::ServiceExt;
+
+#[test]
+fn matches_longest_prefix_by_segment() {
+    let timeout = RequestTimeout::new(Duration::from_secs(30))
+        .with_route("/v1/user", Duration::from_secs(10))
+        .with_route("/v1/user/export/", Duration::from_secs(600));
+
+    assert_eq!(timeout.timeout_for("/health"), Duration::from_secs(30));
+    assert_eq!(
+        timeout.timeout_for("/v1/user/login"),
+        Duration::from_secs(10)
+    );
+    assert_eq!(
+        timeout.timeout_for("/v1/user/export"),
+        Duration::from_secs(600)
+    );
+    assert_eq!(
+        timeout.timeout_for("/v1/user/export/1/download"),
+        Duration::from_secs(600)
+    );
+    assert_eq!(
+        timeout.timeout_for("/v1/user/exports"),
+        Duration::from_secs(10)
+    );
+    assert_eq!(timeout.timeout_for("/v1/users"), Duration::from_secs(30));
+}
+
+#[test]
+fn builds_from_server_config() {
+    let mut config = ServerConfig {
+        timeout: 30,
+        ..Default::default()
+    };
+    config.route_timeouts.insert("/v1/user/import".into(), 600);
+    config.route_timeouts.insert("/v1/user/login".into(), 5);
+
+    let timeout = RequestTimeout::from_config(&config);
+
+    assert_eq!(timeout.timeout_for("/"), Duration::from_secs(30));
+    assert_eq!(
+        timeout.timeout_for("/v1/user/login"),
+        Duration::from_secs(5)
+    );
+    assert_eq!(
+        timeout.timeout_for("/v1/user/import"),
+        Duration::from_secs(600)
+    );
+}
+
+fn router(timeout: RequestTimeout, finished: Arc<AtomicBool>) -> Router {
+    Router::new()
+        .route(
+            "/slow",
+            get(move || async move {
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                finished.store(true, Ordering::SeqCst);
+                "done"
+            }),
+        )
+        .route(
+            "/export/slow",
+            get(|| async {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                "done"
+            }),
+        )
+        .layer(axum::middleware::from_fn_with_state(
+            timeout,
+            timeout_middleware,
+        ))
+}
+
+#[tokio::test]
+async fn returns_504_json_and_cancels_handler() {
+    let finished = Arc::new(AtomicBool::new(false));
+    let timeout = RequestTimeout::new(Duration::from_millis(20));
+
+    let response = router(timeout, finished.clone())
+        .oneshot(Request::get("/slow").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["error"]["code"], 504);
+    assert_eq!(body["error"]["errors"][0]["reason"], "TIMEOUT");
+
+    // 处理器的 future 已被丢弃，不会继续执行
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert!(!finished.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn route_override_extends_timeout() {
+    let timeout = RequestTimeout::new(Duration::from_millis(20))
+        .with_route("/export", Duration::from_secs(5));
+
+    let response = router(timeout, Arc::default())
+        .oneshot(Request::get("/export/slow").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
@@ -0,0 +1,158 @@
+//! 声明式请求校验测试。
+//!
+//! 覆盖 `ValidatedJson` / `ValidatedQuery` 的逐字段、逐约束错误详情（JSON Pointer 位置、
+//! 原因映射、嵌套结构和列表下标），以及约束写入 OpenAPI schema。
+
+use app::user::dto::RegisterRequest;
+use app::{AppError, ErrorDetail, ValidatedJson, ValidatedQuery, ValidationError};
+use axum::body::Body;
+use axum::extract::{FromRequest, FromRequestParts};
+use axum::http::Request;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+fn json_request(body: &str) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/register")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+fn field_errors(err: AppError) -> Vec<ErrorDetail> {
+    let AppError::Validation(ValidationError::Fields(details)) = err else {
+        panic!("expected field validation error, got {err:?}");
+    };
+    details
+}
+
+fn locations(details: &[ErrorDetail]) -> Vec<(&str, &str)> {
+    details
+        .iter()
+        .map(|d| {
+            (
+                d.location.as_deref().unwrap(),
+                d.location_type.as_deref().unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn accepts_valid_body() {
+    let req = json_request(
+        r#"{"username":"alice","email":"alice@example.com","password":"password1","password_confirm":"password1"}"#,
+    );
+    let ValidatedJson(body) = ValidatedJson::<RegisterRequest>::from_request(req, &())
+        .await
+        .unwrap();
+
+    assert_eq!(body.username, "alice");
+}
+
+#[tokio::test]
+async fn reports_one_detail_per_invalid_field() {
+    let req = json_request(
+        r#"{"username":"al","email":"not-an-email","password":"short","password_confirm":"other"}"#,
+    );
+    let err = ValidatedJson::<RegisterRequest>::from_request(req, &())
+        .await
+        .unwrap_err();
+
+    let details = field_errors(err);
+    assert_eq!(
+        locations(&details),
+        [
+            ("/email", "body"),
+            ("/password", "body"),
+            ("/password_confirm", "body"),
+            ("/username", "body"),
+        ]
+    );
+    let reasons: Vec<_> = details.iter().map(|d| d.reason.as_str()).collect();
+    assert_eq!(
+        reasons,
+        [
+            "INVALID_EMAIL",
+            "INVALID_LENGTH",
+            "INVALID_FORMAT",
+            "INVALID_LENGTH"
+        ]
+    );
+}
+
+#[derive(Debug, Deserialize, Serialize, Validate)]
+struct Item {
+    #[validate(length(min = 1))]
+    name: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+struct Order {
+    #[validate(length(min = 1, max = 3), nested)]
+    items: Vec<Item>,
+    #[validate(nested)]
+    shipping: Shipping,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+struct Shipping {
+    #[validate(length(min = 2), email)]
+    contact: String,
+}
+
+#[tokio::test]
+async fn nested_structs_and_list_indices_use_json_pointers() {
+    let req = json_request(r#"{"items":[{"name":"ok"},{"name":""}],"shipping":{"contact":"x"}}"#);
+    let err = ValidatedJson::<Order>::from_request(req, &())
+        .await
+        .unwrap_err();
+
+    let details = field_errors(err);
+    assert_eq!(
+        locations(&details),
+        [
+            ("/items/1/name", "body"),
+            ("/shipping/contact", "body"),
+            ("/shipping/contact", "body"),
+        ]
+    );
+    // 同一字段的多条约束各自一个错误详情
+    let reasons: Vec<_> = details[1..].iter().map(|d| d.reason.as_str()).collect();
+    assert!(reasons.contains(&"INVALID_LENGTH"));
+    assert!(reasons.contains(&"INVALID_EMAIL"));
+}
+
+#[derive(Debug, Deserialize, Validate)]
+struct SearchQuery {
+    #[validate(range(min = 1, max = 50))]
+    limit: u32,
+}
+
+#[tokio::test]
+async fn query_errors_use_query_location_type() {
+    let (mut parts, _) = Request::builder()
+        .uri("/search?limit=99")
+        .body(())
+        .unwrap()
+        .into_parts();
+    let err = ValidatedQuery::<SearchQuery>::from_request_parts(&mut parts, &())
+        .await
+        .unwrap_err();
+
+    let details = field_errors(err);
+    assert_eq!(locations(&details), [("/limit", "query")]);
+    assert_eq!(details[0].reason, "VALUE_OUT_OF_RANGE");
+}
+
+#[test]
+fn schema_reflects_validation_constraints() {
+    let schema = serde_json::to_value(schemars::schema_for!(RegisterRequest)).unwrap();
+    let properties = &schema["properties"];
+
+    assert_eq!(properties["username"]["minLength"], 3);
+    assert_eq!(properties["username"]["maxLength"], 20);
+    assert_eq!(properties["email"]["format"], "email");
+    assert_eq!(properties["password"]["minLength"], 8);
+}
@@ -0,0 +1,15 @@
+//! `app` crate 的业务模块集成测试。
+//!
+//! 只覆盖不依赖数据库的纯业务逻辑，每个子模块对应一个被测功能。
+
+#[path = "modules/user_avatar.rs"]
+mod user_avatar;
+
+#[path = "modules/user_export.rs"]
+mod user_export;
+
+#[path = "modules/user_bulk.rs"]
+mod user_bulk;
+
+#[path = "modules/not_found.rs"]
+mod not_found;
@@ -0,0 +1,121 @@
+//! 404 / 405 内容协商测试。
+//!
+//! 覆盖 API 路径与偏好 JSON 的请求返回 JSON 错误、浏览器导航返回 HTML 页面，
+//! 以及错误方法返回带 `Allow` 头的 405 JSON（包括嵌套路由）。
+
+use app::{handle_404, method_not_allowed_middleware, prefers_html};
+use axum::Router;
+use axum::body::{Body, to_bytes};
+use axum::http::header::{ACCEPT, ALLOW, CONTENT_TYPE};
+use axum::http::{HeaderMap, HeaderValue, Method, Request, StatusCode};
+use axum::response::Response;
+use axum::routing::get;
+use serde_json::Value;
+use tower::ServiceExt;
+
+const BROWSER_ACCEPT: &str = "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8";
+
+fn accept(value: &str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(ACCEPT, HeaderValue::from_str(value).unwrap());
+    headers
+}
+
+#[test]
+fn negotiates_html_only_for_browser_navigation() {
+    assert!(prefers_html("/missing", &accept(BROWSER_ACCEPT)));
+    assert!(!prefers_html("/v1/missing", &accept(BROWSER_ACCEPT)));
+    assert!(!prefers_html("/v1", &accept(BROWSER_ACCEPT)));
+    assert!(prefers_html("/v10", &accept(BROWSER_ACCEPT)));
+    assert!(!prefers_html("/missing", &HeaderMap::new()));
+    assert!(!prefers_html("/missing", &accept("*/*")));
+    assert!(!prefers_html(
+        "/missing",
+        &accept("text/html;q=0.5, application/json")
+    ));
+}
+
+fn router() -> Router {
+    let nested = Router::new().route("/users", get(|| async { "users" }).post(|| async { "" }));
+    Router::new()
+        .route("/health", get(|| async { "ok" }))
+        .nest("/v1", nested)
+        .fallback(handle_404)
+        .layer(axum::middleware::from_fn(method_not_allowed_middleware))
+}
+
+async fn send(method: Method, uri: &str, accept: Option<&str>) -> Response {
+    let mut request = Request::builder().method(method).uri(uri);
+    if let Some(accept) = accept {
+        request = request.header(ACCEPT, accept);
+    }
+    router()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+async fn body_json(response: Response) -> Value {
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn api_paths_return_json_404() {
+    let response = send(Method::GET, "/v1/missing", Some(BROWSER_ACCEPT)).await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert!(
+        response.headers()[CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .starts_with("application/json")
+    );
+    let body = body_json(response).await;
+    assert_eq!(body["error"]["code"], 404);
+    assert_eq!(body["error"]["errors"][0]["reason"], "NOT_FOUND");
+}
+
+#[tokio::test]
+async fn browser_navigation_renders_html_404() {
+    let response = send(Method::GET, "/missing", Some(BROWSER_ACCEPT)).await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert!(
+        response.headers()[CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .starts_with("text/html")
+    );
+}
+
+#[tokio::test]
+async fn json_clients_get_json_404_outside_api_prefix() {
+    let response = send(Method::GET, "/missing", Some("application/json")).await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body = body_json(response).await;
+    assert_eq!(body["error"]["code"], 404);
+}
+
+#[tokio::test]
+async fn wrong_method_returns_json_405_with_allow() {
+    let response = send(Method::DELETE, "/health", None).await;
+
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(response.headers()[ALLOW], "GET,HEAD");
+    let body = body_json(response).await;
+    assert_eq!(body["error"]["code"], 405);
+    assert_eq!(body["error"]["errors"][0]["reason"], "METHOD_NOT_ALLOWED");
+}
+
+#[tokio::test]
+async fn nested_routes_return_json_405() {
+    let response = send(Method::PUT, "/v1/users", Some(BROWSER_ACCEPT)).await;
+
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    let allow = response.headers()[ALLOW].to_str().unwrap().to_string();
+    assert!(allow.contains("GET") && allow.contains("POST"));
+    let body = body_json(response).await;
+    assert_eq!(body["error"]["code"], 405);
+}
@@ -0,0 +1,64 @@
+//! 头像处理测试。
+//!
+//! 覆盖按魔数识别格式、拒绝伪装文件，以及重新编码后的尺寸。
+
+use std::io::Cursor;
+
+use app::FileUploadError;
+use app::user::avatar::{AVATAR_SIZE, THUMBNAIL_SIZES, process_avatar, sniff_image_format};
+use image::{ImageFormat, RgbImage};
+
+fn encode(format: ImageFormat, width: u32, height: u32) -> Vec<u8> {
+    let mut buffer = Cursor::new(Vec::new());
+    RgbImage::new(width, height)
+        .write_to(&mut buffer, format)
+        .unwrap();
+    buffer.into_inner()
+}
+
+#[test]
+fn sniffs_format_from_magic_bytes() {
+    assert_eq!(
+        sniff_image_format(&encode(ImageFormat::Png, 4, 4)),
+        Some(ImageFormat::Png)
+    );
+    assert_eq!(
+        sniff_image_format(&encode(ImageFormat::Jpeg, 4, 4)),
+        Some(ImageFormat::Jpeg)
+    );
+    assert_eq!(
+        sniff_image_format(b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>"),
+        None
+    );
+}
+
+#[test]
+fn rejects_non_image_payload() {
+    let err = process_avatar(b"<?php echo 'hi'; ?>").unwrap_err();
+    assert!(matches!(err, FileUploadError::TypeNotAllowed(_)));
+}
+
+#[test]
+fn rejects_truncated_image() {
+    let png = encode(ImageFormat::Png, 32, 32);
+    let err = process_avatar(&png[..png.len() / 2]).unwrap_err();
+    assert!(matches!(err, FileUploadError::InvalidImage(_)));
+}
+
+#[test]
+fn reencodes_to_png_with_fixed_size_thumbnails() {
+    let processed = process_avatar(&encode(ImageFormat::Jpeg, 800, 600)).unwrap();
+
+    let avatar = image::load_from_memory_with_format(&processed.avatar, ImageFormat::Png).unwrap();
+    assert_eq!(
+        (avatar.width(), avatar.height()),
+        (AVATAR_SIZE, AVATAR_SIZE)
+    );
+
+    let sizes: Vec<u32> = processed.thumbnails.iter().map(|(size, _)| *size).collect();
+    assert_eq!(sizes, THUMBNAIL_SIZES);
+    for (size, data) in &processed.thumbnails {
+        let thumbnail = image::load_from_memory_with_format(data, ImageFormat::Png).unwrap();
+        assert_eq!((thumbnail.width(), thumbnail.height()), (*size, *size));
+    }
+}
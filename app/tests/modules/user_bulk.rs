@@ -0,0 +1,103 @@
+//! 用户批量导入导出测试。
+//!
+//! 覆盖格式识别、CSV/NDJSON 逐行解析与行号、文件内重复检查，以及导出时的公式注入防护。
+
+use app::user::bulk::{
+    ExportUserRow, ImportFormat, ImportKeys, ImportRow, csv_header, csv_line, escape_formula,
+    parse_import,
+};
+use chrono::{TimeZone, Utc};
+
+#[test]
+fn detects_format_from_content_type_then_extension() {
+    assert_eq!(
+        ImportFormat::detect(Some("text/csv; charset=utf-8"), Some("users.txt")),
+        Some(ImportFormat::Csv)
+    );
+    assert_eq!(
+        ImportFormat::detect(Some("application/octet-stream"), Some("users.JSONL")),
+        Some(ImportFormat::Ndjson)
+    );
+    assert_eq!(ImportFormat::detect(None, Some("users.xlsx")), None);
+}
+
+#[test]
+fn csv_rows_keep_line_numbers_and_report_bad_rows() {
+    let csv = b"username,email,password\nalice,alice@example.com,password1\nbob,bob@example.com\n";
+    let rows = parse_import(csv, ImportFormat::Csv).unwrap();
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].line, 2);
+    assert_eq!(rows[0].row.as_ref().unwrap().username, "alice");
+    assert_eq!(rows[1].line, 3);
+    assert!(rows[1].row.is_err());
+}
+
+#[test]
+fn csv_without_required_columns_is_rejected() {
+    let err =
+        parse_import(b"username,email\nalice,a@example.com\n", ImportFormat::Csv).unwrap_err();
+    assert!(err.contains("password"));
+}
+
+#[test]
+fn ndjson_skips_blank_lines() {
+    let ndjson = br#"{"username":"alice","email":"alice@example.com","password":"password1"}
+
+not json
+"#;
+    let rows = parse_import(ndjson, ImportFormat::Ndjson).unwrap();
+
+    assert_eq!(rows.len(), 2);
+    assert!(rows[0].row.is_ok());
+    assert_eq!(rows[1].line, 3);
+    assert!(rows[1].row.is_err());
+}
+
+fn import_row(username: &str, email: &str) -> ImportRow {
+    ImportRow {
+        username: username.to_string(),
+        email: email.to_string(),
+        password: "password1".to_string(),
+    }
+}
+
+#[test]
+fn import_keys_reject_duplicates_without_recording_rejected_rows() {
+    let mut keys = ImportKeys::default();
+
+    assert!(keys.insert(&import_row("alice", "alice@example.com")));
+    assert!(!keys.insert(&import_row("bob", "Alice@Example.com")));
+    assert!(keys.insert(&import_row("bob", "bob@example.com")));
+    assert!(!keys.insert(&import_row("alice", "other@example.com")));
+
+    let (usernames, emails) = keys.into_parts();
+    assert_eq!(usernames.len(), 2);
+    assert!(emails.contains("alice@example.com") && emails.contains("bob@example.com"));
+}
+
+#[test]
+fn export_escapes_spreadsheet_formulas() {
+    assert_eq!(escape_formula("=HYPERLINK(\"x\")"), "'=HYPERLINK(\"x\")");
+    assert_eq!(escape_formula("alice"), "alice");
+
+    let row = ExportUserRow {
+        id: "abc123XY".to_string(),
+        username: "@evil".to_string(),
+        email: "evil@example.com".to_string(),
+        status: "active".to_string(),
+        role: "user".to_string(),
+        created_at: Utc
+            .with_ymd_and_hms(2026, 10, 18, 0, 0, 0)
+            .unwrap()
+            .fixed_offset(),
+    };
+    assert_eq!(
+        String::from_utf8(csv_header()).unwrap(),
+        "id,username,email,status,role,created_at\n"
+    );
+    assert_eq!(
+        String::from_utf8(csv_line(&row)).unwrap(),
+        "abc123XY,'@evil,evil@example.com,active,user,2026-10-18T00:00:00+00:00\n"
+    );
+}
@@ -0,0 +1,92 @@
+//! 个人数据导出测试。
+//!
+//! 覆盖 JSON 与 ZIP 两种格式的内容结构，以及导出文件 key 的生成规则。
+
+use std::io::{Cursor, Read};
+
+use app::user::export::{
+    EXPORT_SCHEMA_VERSION, ExportAccount, ExportAttachment, ExportProfile, UserDataExport,
+    build_json, build_zip, export_file_key,
+};
+use chrono::{TimeZone, Utc};
+use entity::enums::{ExportFormat, UserStatus};
+use serde_json::Value;
+use zip::ZipArchive;
+
+fn sample() -> UserDataExport {
+    let now = Utc
+        .with_ymd_and_hms(2026, 10, 18, 8, 0, 0)
+        .unwrap()
+        .fixed_offset();
+    UserDataExport {
+        schema_version: EXPORT_SCHEMA_VERSION,
+        generated_at: now,
+        profile: ExportProfile {
+            id: "abc123XY".to_string(),
+            username: "alice".to_string(),
+            email: "alice@example.com".to_string(),
+            avatar_url: None,
+        },
+        account: ExportAccount {
+            status: Some(UserStatus::Active),
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+            tokens_valid_after: None,
+        },
+        exports: Vec::new(),
+    }
+}
+
+fn read_entry(archive: &mut ZipArchive<Cursor<Vec<u8>>>, name: &str) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    archive
+        .by_name(name)
+        .unwrap()
+        .read_to_end(&mut bytes)
+        .unwrap();
+    bytes
+}
+
+#[test]
+fn json_export_contains_all_sections_without_password() {
+    let value: Value = serde_json::from_slice(&build_json(&sample()).unwrap()).unwrap();
+
+    assert_eq!(value["schema_version"], EXPORT_SCHEMA_VERSION);
+    assert_eq!(value["profile"]["email"], "alice@example.com");
+    assert_eq!(value["account"]["status"], "active");
+    assert!(value["exports"].as_array().unwrap().is_empty());
+    assert!(!value.to_string().contains("password"));
+}
+
+#[test]
+fn zip_export_contains_manifest_sections_and_attachments() {
+    let attachments = [ExportAttachment {
+        path: "avatar.png".to_string(),
+        bytes: vec![1, 2, 3],
+    }];
+    let bytes = build_zip(&sample(), &attachments).unwrap();
+    let mut archive = ZipArchive::new(Cursor::new(bytes)).unwrap();
+
+    let manifest: Value =
+        serde_json::from_slice(&read_entry(&mut archive, "manifest.json")).unwrap();
+    assert_eq!(manifest["attachments"][0], "avatar.png");
+
+    let profile: Value = serde_json::from_slice(&read_entry(&mut archive, "profile.json")).unwrap();
+    assert_eq!(profile["username"], "alice");
+    assert!(archive.by_name("account.json").is_ok());
+    assert!(archive.by_name("exports.json").is_ok());
+    assert_eq!(read_entry(&mut archive, "avatar.png"), vec![1, 2, 3]);
+}
+
+#[test]
+fn export_file_key_is_scoped_by_user() {
+    assert_eq!(
+        export_file_key(7, 42, ExportFormat::Zip),
+        "exports/7/42.zip"
+    );
+    assert_eq!(
+        export_file_key(7, 43, ExportFormat::Json),
+        "exports/7/43.json"
+    );
+}
@@ -0,0 +1,8 @@
+//! `app` crate 的共享工具集成测试。
+//!
+//! 每个子模块对应 `shared` 下的一个被测工具。
+
+#[path = "shared/id.rs"]
+mod id;
+#[path = "shared/public_id.rs"]
+mod public_id;
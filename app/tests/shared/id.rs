@@ -0,0 +1,66 @@
+//! 分布式 ID 生成器测试。
+//!
+//! 覆盖机器 ID 派生的稳定性、生成的 ID 单调递增且携带机器 ID，以及租约丢失后停止生成、重新分配机器 ID 后恢复生成。
+
+use app::error::IdGeneratorError;
+use app::shared::IdGenerator;
+use app::shared::MachineIdLease;
+use app::shared::id::machine_id_from_name;
+use chrono::{TimeZone, Utc};
+
+#[test]
+fn machine_id_from_name_is_stable() {
+    assert_eq!(machine_id_from_name("api-0"), machine_id_from_name("api-0"));
+    assert_ne!(machine_id_from_name("api-0"), machine_id_from_name("api-1"));
+}
+
+#[test]
+fn generates_increasing_ids_with_machine_id() {
+    let start_time = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+    let generator = IdGenerator::new(42, start_time).unwrap();
+
+    let ids: Vec<i64> = (0..1000).map(|_| generator.next_id().unwrap()).collect();
+
+    assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    for id in ids {
+        assert!(id > 0);
+        assert_eq!(sonyflake::decompose(id as u64).machine_id, 42);
+    }
+}
+
+#[test]
+fn stops_generating_after_lease_lost() {
+    let start_time = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+    let lease = MachineIdLease::new(7);
+    let generator = IdGenerator::leased(&lease, start_time).unwrap();
+    let cloned = generator.clone();
+    assert!(generator.next_id().is_ok());
+
+    lease.mark_lost();
+    assert!(lease.is_lost());
+    for generator in [generator, cloned] {
+        assert!(matches!(
+            generator.next_id(),
+            Err(IdGeneratorError::LeaseLost(7))
+        ));
+    }
+}
+
+#[test]
+fn resumes_with_new_machine_id_after_reassign() {
+    let start_time = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+    let lease = MachineIdLease::new(7);
+    let generator = IdGenerator::leased(&lease, start_time).unwrap();
+    let before = generator.next_id().unwrap();
+
+    lease.mark_lost();
+    assert!(generator.next_id().is_err());
+
+    lease.reassign(9);
+    assert!(!lease.is_lost());
+    assert_eq!(generator.machine_id(), 9);
+    let after = generator.clone().next_id().unwrap();
+    assert_eq!(sonyflake::decompose(after as u64).machine_id, 9);
+    assert!(after > before);
+    assert!(generator.next_id().unwrap() > after);
+}
@@ -0,0 +1,81 @@
+//! 对外 ID 编解码测试。
+//!
+//! 覆盖编码可逆、盐值隔离和非规范编码拒绝，保证对外 ID 不会被枚举或伪造；
+//! 以及最小长度超出范围时配置加载失败、重复安装编码器时只拒绝不同的配置。
+
+use app::core::config::{ConfigSection, PublicIdConfig};
+use app::shared::{PublicId, PublicIdCodec};
+use serde_json::json;
+
+fn salted(salt: &str) -> PublicIdCodec {
+    PublicIdCodec::new(&PublicIdConfig {
+        salt: salt.to_string(),
+        ..Default::default()
+    })
+    .unwrap()
+}
+
+#[test]
+fn encodes_and_decodes_round_trip() {
+    let codec = salted("test-salt");
+
+    for id in [1, 42, 10_000, i32::MAX] {
+        let encoded = codec.encode(id);
+        assert!(encoded.len() >= 8);
+        assert_eq!(codec.decode(&encoded), Some(id));
+    }
+}
+
+#[test]
+fn different_salts_produce_incompatible_ids() {
+    let a = salted("salt-a");
+    let b = salted("salt-b");
+
+    let encoded = a.encode(7);
+    assert_ne!(encoded, b.encode(7));
+    assert_ne!(b.decode(&encoded), Some(7));
+}
+
+#[test]
+fn rejects_garbage_and_non_canonical_ids() {
+    let codec = salted("test-salt");
+
+    assert_eq!(codec.decode(""), None);
+    assert_eq!(codec.decode("!!!"), None);
+
+    let mut padded = codec.encode(7);
+    padded.push('a');
+    assert_eq!(codec.decode(&padded), None);
+}
+
+#[test]
+fn serializes_as_encoded_string() {
+    let id = PublicId::new(42);
+    let json = serde_json::to_string(&id).unwrap();
+
+    assert_eq!(json, format!("\"{}\"", id.encode()));
+    assert_eq!(serde_json::from_str::<PublicId>(&json).unwrap(), id);
+    assert!(serde_json::from_str::<PublicId>("42").is_err());
+}
+
+#[test]
+fn rejects_out_of_range_min_length() {
+    let mut config = PublicIdConfig::default();
+    assert!(config.load_from_value(&json!({"min_length": 256})).is_err());
+    assert_eq!(config.min_length, 8);
+
+    config.load_from_value(&json!({"min_length": 255})).unwrap();
+    assert_eq!(config.min_length, 255);
+}
+
+#[test]
+fn reinstalling_same_codec_is_a_no_op() {
+    PublicId::install(PublicIdCodec::default()).unwrap();
+    PublicId::install(PublicIdCodec::default()).unwrap();
+
+    assert!(PublicId::install(salted("other-salt")).is_err());
+    assert_eq!(
+        PublicId::decode(&PublicId::new(7).encode()),
+        Some(PublicId::new(7))
+    );
+}
@@ -0,0 +1,38 @@
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use schemars::JsonSchema;
+use sea_orm::{DeriveActiveEnum, EnumIter};
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+/// 数据导出格式
+///
+/// 使用 i16 存储在数据库中，与用户状态保持一致
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    DeriveActiveEnum,
+    EnumIter,
+    Display,
+    EnumString,
+    IntoPrimitive,
+    TryFromPrimitive,
+    PartialEq,
+    Eq,
+)]
+#[sea_orm(rs_type = "i16", db_type = "SmallInteger")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+#[repr(i16)]
+#[derive(Default)]
+pub enum ExportFormat {
+    /// 单个 JSON 文档，头像以 URL 形式给出
+    #[default]
+    Json = 0,
+
+    /// ZIP 压缩包，包含各部分 JSON 文件和头像原图
+    Zip = 1,
+}
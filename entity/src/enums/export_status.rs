@@ -0,0 +1,41 @@
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use schemars::JsonSchema;
+use sea_orm::{DeriveActiveEnum, EnumIter};
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+/// 数据导出任务状态
+///
+/// 使用 i16 存储在数据库中，与用户状态保持一致
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    DeriveActiveEnum,
+    EnumIter,
+    Display,
+    EnumString,
+    IntoPrimitive,
+    TryFromPrimitive,
+    PartialEq,
+    Eq,
+)]
+#[sea_orm(rs_type = "i16", db_type = "SmallInteger")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+#[repr(i16)]
+#[derive(Default)]
+pub enum ExportStatus {
+    /// 排队或处理中
+    #[default]
+    Pending = 0,
+
+    /// 已完成，可下载
+    Completed = 1,
+
+    /// 处理失败
+    Failed = 2,
+}
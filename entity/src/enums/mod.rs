@@ -1,3 +1,9 @@
+pub mod export_format;
+pub mod export_status;
+pub mod user_role;
 pub mod user_status;
 
+pub use export_format::ExportFormat;
+pub use export_status::ExportStatus;
+pub use user_role::UserRole;
 pub use user_status::UserStatus;
@@ -0,0 +1,38 @@
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use schemars::JsonSchema;
+use sea_orm::{DeriveActiveEnum, EnumIter};
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+/// 用户角色
+///
+/// 使用 i16 存储在数据库中，与用户状态保持一致
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    DeriveActiveEnum,
+    EnumIter,
+    Display,
+    EnumString,
+    IntoPrimitive,
+    TryFromPrimitive,
+    PartialEq,
+    Eq,
+)]
+#[sea_orm(rs_type = "i16", db_type = "SmallInteger")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+#[repr(i16)]
+#[derive(Default)]
+pub enum UserRole {
+    /// 普通用户
+    #[default]
+    User = 0,
+
+    /// 管理员，可执行批量导入导出等管理操作
+    Admin = 1,
+}
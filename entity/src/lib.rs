@@ -1,8 +1,10 @@
 pub mod enums;
 
+pub mod data_export;
 pub mod user;
 
 pub mod prelude {
+    pub use super::data_export::Entity as DataExport;
     pub use super::enums::*;
     pub use super::user::*;
 }
@@ -13,6 +13,12 @@ pub struct Model {
     pub email: String,
     pub password_hash: String,
     pub status: i16,
+    pub role: i16,
+    pub avatar_key: Option<String>,
+    pub deleted_at: Option<DateTimeWithTimeZone>,
+    pub anonymized_at: Option<DateTimeWithTimeZone>,
+    pub tokens_valid_after: Option<DateTimeWithTimeZone>,
+    pub version: i32,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
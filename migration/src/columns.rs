@@ -0,0 +1,20 @@
+//! 迁移中复用的列定义辅助函数
+//!
+//! 补充 `sea_orm_migration::schema` 中没有的列类型。
+
+use sea_orm_migration::{prelude::*, schema::big_integer};
+
+/// 创建雪花 ID 主键列
+///
+/// 生成 `BIGINT NOT NULL PRIMARY KEY`，不带自增序列。ID 由应用层的
+/// `IdGenerator` 在插入前生成，跨副本全局唯一且可按时间排序。
+/// 新表需要这类主键时用它替代 `pk_auto`：
+///
+/// ```ignore
+/// Table::create()
+///     .table(Order::Table)
+///     .col(pk_snowflake(Order::Id))
+/// ```
+pub fn pk_snowflake<T: IntoIden>(name: T) -> ColumnDef {
+    big_integer(name).primary_key().take()
+}
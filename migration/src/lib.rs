@@ -1,12 +1,25 @@
 pub use sea_orm_migration::prelude::*;
 
+mod columns;
 mod m20220101_000001_create_user_table;
+mod m20261018_000002_add_user_avatar;
+mod m20261018_000003_add_account_privacy;
+mod m20261018_000004_add_user_role;
+mod m20261018_000005_add_user_version;
+
+pub use columns::pk_snowflake;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20220101_000001_create_user_table::Migration)]
+        vec![
+            Box::new(m20220101_000001_create_user_table::Migration),
+            Box::new(m20261018_000002_add_user_avatar::Migration),
+            Box::new(m20261018_000003_add_account_privacy::Migration),
+            Box::new(m20261018_000004_add_user_role::Migration),
+            Box::new(m20261018_000005_add_user_version::Migration),
+        ]
     }
 }
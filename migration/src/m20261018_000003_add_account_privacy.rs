@@ -0,0 +1,124 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use crate::pk_snowflake;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .add_column_if_not_exists(timestamp_with_time_zone_null(User::DeletedAt))
+                    .add_column_if_not_exists(timestamp_with_time_zone_null(User::AnonymizedAt))
+                    .add_column_if_not_exists(timestamp_with_time_zone_null(User::TokensValidAfter))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(DataExport::Table)
+                    .if_not_exists()
+                    .col(pk_snowflake(DataExport::Id))
+                    .col(integer(DataExport::UserId))
+                    .col(small_integer(DataExport::Format).default(0))
+                    .col(small_integer(DataExport::Status).default(0))
+                    .col(string_null(DataExport::FileKey))
+                    .col(text_null(DataExport::Error))
+                    .col(
+                        timestamp_with_time_zone(DataExport::CreatedAt)
+                            .extra("DEFAULT CURRENT_TIMESTAMP"),
+                    )
+                    .col(timestamp_with_time_zone_null(DataExport::CompletedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(DataExport::Table, DataExport::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_data_export_user_id")
+                    .table(DataExport::Table)
+                    .col(DataExport::UserId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DataExport::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .drop_column(User::DeletedAt)
+                    .drop_column(User::AnonymizedAt)
+                    .drop_column(User::TokensValidAfter)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum User {
+    /// 表名
+    Table,
+
+    /// 用户 ID
+    Id,
+
+    /// 注销时间（软删除），为空表示未注销
+    DeletedAt,
+
+    /// 个人信息匿名化时间，宽限期结束后由后台任务设置
+    AnonymizedAt,
+
+    /// 令牌生效起点，早于此时间签发的 JWT 一律失效
+    TokensValidAfter,
+}
+
+#[derive(DeriveIden)]
+enum DataExport {
+    /// 表名
+    Table,
+
+    /// 导出任务 ID，雪花 ID 主键
+    Id,
+
+    /// 所属用户 ID，用户删除时级联删除
+    UserId,
+
+    /// 导出格式（0=JSON，1=ZIP）
+    Format,
+
+    /// 任务状态（0=处理中，1=已完成，2=失败）
+    Status,
+
+    /// 导出文件在私有存储中的 key
+    FileKey,
+
+    /// 失败原因
+    Error,
+
+    /// 创建时间
+    CreatedAt,
+
+    /// 完成时间
+    CompletedAt,
+}